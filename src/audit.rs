@@ -0,0 +1,576 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `audit topology` and `users audit`: check definitions, and for live clusters some runtime state,
+//! against common recommendations. Every finding has a severity and a remediation hint.
+
+use crate::constants::ADMINISTRATOR_TAG;
+use crate::definitions::{DefinitionKind, collection, str_field, vhost_of};
+use crate::users::{self, has_tag};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use tabled::Tabled;
+
+/// Users with full permissions in at least this many virtual hosts are reported.
+pub const DEFAULT_BROAD_PERMISSIONS_THRESHOLD: usize = 5;
+
+const FULL_PERMISSIONS_PATTERN: &str = ".*";
+const GUEST_USER: &str = "guest";
+// Users imported from definitions that predate hashing algorithm support use MD5
+const LEGACY_HASHING_ALGORITHM: &str = "rabbit_password_hashing_md5";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Tabled, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    pub check: String,
+    pub vhost: String,
+    pub subject: String,
+    pub description: String,
+    pub remediation: String,
+}
+
+impl Finding {
+    fn new(
+        severity: Severity,
+        check: &str,
+        vhost: &str,
+        subject: &str,
+        description: String,
+        remediation: &str,
+    ) -> Self {
+        Self {
+            severity,
+            check: check.to_owned(),
+            vhost: vhost.to_owned(),
+            subject: subject.to_owned(),
+            description,
+            remediation: remediation.to_owned(),
+        }
+    }
+}
+
+/// What is audited. Runtime state is only available for live clusters,
+/// the checks that need it are skipped for definition files.
+#[derive(Debug)]
+pub struct AuditInput {
+    pub definitions: Value,
+    /// Consumer counts by (virtual host, queue name)
+    pub consumer_counts: Option<HashMap<(String, String), u64>>,
+    /// Virtual hosts with at least one limit. When not known, the `limits` field
+    /// of virtual hosts in the definitions is used if present
+    pub vhosts_with_limits: Option<HashSet<String>>,
+    pub broad_permissions_threshold: usize,
+}
+
+impl AuditInput {
+    pub fn from_definitions(definitions: Value) -> Self {
+        Self {
+            definitions,
+            consumer_counts: None,
+            vhosts_with_limits: None,
+            broad_permissions_threshold: DEFAULT_BROAD_PERMISSIONS_THRESHOLD,
+        }
+    }
+}
+
+/// Runs all checks. Findings are ordered by severity (most severe first).
+pub fn audit_topology(input: &AuditInput) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    check_queues(input, &mut findings);
+    check_exchanges(&input.definitions, &mut findings);
+    check_policy_priorities(&input.definitions, &mut findings);
+    check_users(input, &mut findings);
+    check_vhost_limits(input, &mut findings);
+
+    sort_findings(&mut findings);
+    findings
+}
+
+fn sort_findings(findings: &mut [Finding]) {
+    findings.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| (&a.check, &a.vhost, &a.subject).cmp(&(&b.check, &b.vhost, &b.subject)))
+    });
+}
+
+// Objects in virtual host-specific definition files have no `vhost` field,
+// so every missing virtual host is treated as the same one, "".
+fn check_queues(input: &AuditInput, findings: &mut Vec<Finding>) {
+    let defs = &input.definitions;
+    let bound_queues: HashSet<(&str, &str)> = collection(defs, DefinitionKind::Bindings)
+        .iter()
+        .filter(|b| str_field(b, "destination_type") == Some("queue"))
+        .filter_map(|b| {
+            Some((
+                vhost_of(b).unwrap_or_default(),
+                str_field(b, "destination")?,
+            ))
+        })
+        .collect();
+
+    for queue in collection(defs, DefinitionKind::Queues) {
+        let vhost = vhost_of(queue).unwrap_or_default();
+        let name = str_field(queue, "name").unwrap_or_default();
+        let queue_type = queue_type(defs, queue);
+
+        if !bound_queues.contains(&(vhost, name)) {
+            findings.push(Finding::new(
+                Severity::Info,
+                "queue_without_bindings",
+                vhost,
+                name,
+                "the queue is not bound to any exchange (other than the default one)".to_owned(),
+                "bind the queue to an exchange, or delete it if it is no longer used",
+            ));
+        }
+
+        if let Some(counts) = &input.consumer_counts
+            && counts.get(&(vhost.to_owned(), name.to_owned())) == Some(&0)
+        {
+            findings.push(Finding::new(
+                Severity::Warning,
+                "queue_without_consumers",
+                vhost,
+                name,
+                "the queue has no consumers, messages in it can pile up".to_owned(),
+                "make sure the applications that consume from the queue are running, or delete the queue if it is no longer used",
+            ));
+        }
+
+        let durable = queue
+            .get("durable")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+        let auto_delete = queue
+            .get("auto_delete")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let exclusive = queue
+            .get("exclusive")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if queue_type == "classic" && durable && !auto_delete && !exclusive {
+            findings.push(Finding::new(
+                Severity::Warning,
+                "quorum_queue_recommended",
+                vhost,
+                name,
+                "a durable, long-lived classic queue is not replicated".to_owned(),
+                "use a quorum queue for data safety, e.g. by migrating the queue or setting the virtual host's default queue type to quorum",
+            ));
+        }
+
+        if queue_type == "quorum" {
+            let policy = effective_policy(defs, vhost, name, "quorum_queues");
+            let is_configured = |argument: &str, policy_key: &str| {
+                queue
+                    .get("arguments")
+                    .and_then(|args| args.get(argument))
+                    .is_some()
+                    || policy
+                        .and_then(|p| p.get("definition"))
+                        .and_then(|d| d.get(policy_key))
+                        .is_some()
+            };
+
+            if !is_configured("x-delivery-limit", "delivery-limit") {
+                findings.push(Finding::new(
+                    Severity::Warning,
+                    "quorum_queue_without_delivery_limit",
+                    vhost,
+                    name,
+                    "no explicit delivery limit is configured for the quorum queue".to_owned(),
+                    "set a delivery limit (the 'delivery-limit' policy key) that matches the expected number of redeliveries",
+                ));
+            }
+            if !is_configured("x-dead-letter-exchange", "dead-letter-exchange") {
+                findings.push(Finding::new(
+                    Severity::Info,
+                    "quorum_queue_without_dead_lettering",
+                    vhost,
+                    name,
+                    "messages that exceed the delivery limit (or expire) will be dropped".to_owned(),
+                    "configure dead lettering (the 'dead-letter-exchange' policy key) to keep such messages",
+                ));
+            }
+        }
+    }
+}
+
+fn check_exchanges(defs: &Value, findings: &mut Vec<Finding>) {
+    let sources: HashSet<(&str, &str)> = collection(defs, DefinitionKind::Bindings)
+        .iter()
+        .filter_map(|b| Some((vhost_of(b).unwrap_or_default(), str_field(b, "source")?)))
+        .collect();
+
+    for exchange in collection(defs, DefinitionKind::Exchanges) {
+        let vhost = vhost_of(exchange).unwrap_or_default();
+        let name = str_field(exchange, "name").unwrap_or_default();
+        // the default and amq.* exchanges are pre-declared
+        if name.is_empty() || name.starts_with("amq.") {
+            continue;
+        }
+        if !sources.contains(&(vhost, name)) {
+            findings.push(Finding::new(
+                Severity::Info,
+                "exchange_without_bindings",
+                vhost,
+                name,
+                "messages published to the exchange are not routed anywhere".to_owned(),
+                "bind queues or exchanges to it, configure an alternate exchange, or delete it if it is no longer used",
+            ));
+        }
+    }
+}
+
+/// For each policy, the indices of the other policies in the same virtual host
+/// that have the same priority. `key` returns the virtual host and priority of a policy.
+///
+/// Used by `list policies_with_conflicting_priorities` and the `conflicting_policy_priorities` check.
+pub fn policies_with_same_priority<T>(
+    policies: &[T],
+    key: impl for<'a> Fn(&'a T) -> (&'a str, i64),
+) -> Vec<Vec<usize>> {
+    let mut groups: HashMap<(&str, i64), Vec<usize>> = HashMap::new();
+    for (i, policy) in policies.iter().enumerate() {
+        groups.entry(key(policy)).or_default().push(i);
+    }
+
+    policies
+        .iter()
+        .enumerate()
+        .map(|(i, policy)| {
+            groups[&key(policy)]
+                .iter()
+                .copied()
+                .filter(|j| *j != i)
+                .collect()
+        })
+        .collect()
+}
+
+/// Like `list policies_with_conflicting_priorities`, reports policies that have the same priority
+/// as another policy in the same virtual host, except that policies that apply to disjoint kinds
+/// of objects (e.g. exchanges and queues) do not conflict.
+fn check_policy_priorities(defs: &Value, findings: &mut Vec<Finding>) {
+    let policies = collection(defs, DefinitionKind::Policies);
+    let same_priority =
+        policies_with_same_priority(policies, |p| (vhost_of(p).unwrap_or_default(), priority(p)));
+
+    for (policy, others) in policies.iter().zip(same_priority) {
+        let conflicting: Vec<&str> = others
+            .into_iter()
+            .map(|j| &policies[j])
+            .filter(|other| targets_overlap(apply_to(policy), apply_to(other)))
+            .filter_map(|other| str_field(other, "name"))
+            .collect();
+        if conflicting.is_empty() {
+            continue;
+        }
+
+        findings.push(Finding::new(
+            Severity::Warning,
+            "conflicting_policy_priorities",
+            vhost_of(policy).unwrap_or_default(),
+            str_field(policy, "name").unwrap_or_default(),
+            format!(
+                "has the same priority ({}) as {}, only one of them applies to an object both match",
+                priority(policy),
+                conflicting.join(", ")
+            ),
+            "give the policies distinct priorities",
+        ));
+    }
+}
+
+fn check_users(input: &AuditInput, findings: &mut Vec<Finding>) {
+    let defs = &input.definitions;
+    check_guest_user(defs, findings);
+
+    for (user, count) in full_permission_vhost_counts(defs) {
+        if count >= input.broad_permissions_threshold {
+            findings.push(Finding::new(
+                Severity::Warning,
+                "broad_user_permissions",
+                "",
+                user,
+                format!("has full ('.*') permissions in {} virtual hosts", count),
+                "grant applications permissions only in the virtual hosts, and to the resources, they use",
+            ));
+        }
+    }
+}
+
+fn check_guest_user(defs: &Value, findings: &mut Vec<Finding>) {
+    if let Some(guest) = collection(defs, DefinitionKind::Users)
+        .iter()
+        .find(|u| str_field(u, "name") == Some(GUEST_USER))
+    {
+        let (severity, description) = if has_tag(guest, ADMINISTRATOR_TAG) {
+            (
+                Severity::Critical,
+                "the default user with well-known credentials exists and is an administrator",
+            )
+        } else {
+            (
+                Severity::Warning,
+                "the default user with well-known credentials exists",
+            )
+        };
+        findings.push(Finding::new(
+            severity,
+            "guest_user",
+            "",
+            GUEST_USER,
+            description.to_owned(),
+            "create dedicated users for applications and operators, then delete the 'guest' user",
+        ));
+    }
+}
+
+/// Returns the number of virtual hosts each user has full ('.*') permissions in.
+fn full_permission_vhost_counts(defs: &Value) -> HashMap<&str, usize> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for permission in collection(defs, DefinitionKind::Permissions) {
+        let is_full = ["configure", "write", "read"]
+            .iter()
+            .all(|field| str_field(permission, field) == Some(FULL_PERMISSIONS_PATTERN));
+        if is_full && let Some(user) = str_field(permission, "user") {
+            *counts.entry(user).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn check_vhost_limits(input: &AuditInput, findings: &mut Vec<Finding>) {
+    for vhost in collection(&input.definitions, DefinitionKind::Vhosts) {
+        let name = str_field(vhost, "name").unwrap_or_default();
+        let has_limits = match &input.vhosts_with_limits {
+            Some(names) => Some(names.contains(name)),
+            None => vhost.get("limits").map(|limits| match limits {
+                Value::Object(map) => !map.is_empty(),
+                Value::Array(items) => !items.is_empty(),
+                _ => false,
+            }),
+        };
+
+        if has_limits == Some(false) {
+            findings.push(Finding::new(
+                Severity::Info,
+                "vhost_without_limits",
+                name,
+                name,
+                "the virtual host has no connection or queue limits".to_owned(),
+                "set limits ('vhost_limits declare') to protect the cluster from runaway applications",
+            ));
+        }
+    }
+}
+
+/// What `users audit` works with. Users come from definitions because, unlike
+/// `GET /api/users`, they include password hashing algorithms.
+#[derive(Debug)]
+pub struct UserAuditInput {
+    pub definitions: Value,
+    /// Users that had at least one client connection during the observation window.
+    /// When not known, the check that needs it is skipped
+    pub connected_users: Option<HashSet<String>>,
+    /// Failed authentication attempts by node
+    pub failed_auth_attempts: HashMap<String, u64>,
+    pub broad_permissions_threshold: usize,
+}
+
+impl UserAuditInput {
+    pub fn from_definitions(definitions: Value) -> Self {
+        Self {
+            definitions,
+            connected_users: None,
+            failed_auth_attempts: HashMap::new(),
+            broad_permissions_threshold: DEFAULT_BROAD_PERMISSIONS_THRESHOLD,
+        }
+    }
+}
+
+/// Runs all user checks. Findings are ordered like those of [`audit_topology`].
+pub fn audit_users(input: &UserAuditInput) -> Vec<Finding> {
+    let defs = &input.definitions;
+    let mut findings = Vec::new();
+    check_guest_user(defs, &mut findings);
+
+    let users_with_permissions: HashSet<&str> = collection(defs, DefinitionKind::Permissions)
+        .iter()
+        .filter_map(|p| str_field(p, "user"))
+        .collect();
+    let full_permission_vhosts = full_permission_vhost_counts(defs);
+    // in clusters with few virtual hosts, full permissions in all of them are broad
+    let vhost_count = collection(defs, DefinitionKind::Vhosts).len();
+    let broad_threshold = input.broad_permissions_threshold.min(vhost_count).max(1);
+
+    for user in collection(defs, DefinitionKind::Users) {
+        let name = str_field(user, "name").unwrap_or_default();
+
+        if !users_with_permissions.contains(name) {
+            findings.push(Finding::new(
+                Severity::Info,
+                "user_without_permissions",
+                "",
+                name,
+                "the user has no permissions in any virtual host".to_owned(),
+                "grant the user the permissions it needs, or delete it if it is no longer used",
+            ));
+        } else if let Some(connected) = &input.connected_users
+            && !connected.contains(name)
+        {
+            findings.push(Finding::new(
+                Severity::Info,
+                "user_without_connections",
+                "",
+                name,
+                "the user has permissions but no client connections were observed".to_owned(),
+                "check whether the user is still used, and delete it if it is not",
+            ));
+        }
+
+        let full_permissions = full_permission_vhosts.get(name).copied().unwrap_or(0);
+        if has_tag(user, ADMINISTRATOR_TAG) && full_permissions >= broad_threshold {
+            findings.push(Finding::new(
+                Severity::Warning,
+                "administrator_with_broad_permissions",
+                "",
+                name,
+                format!(
+                    "is an administrator with full ('.*') permissions in {} virtual hosts",
+                    full_permissions
+                ),
+                "use separate users for administration and for applications, and grant application users only the permissions they need",
+            ));
+        }
+
+        let has_password = str_field(user, "password_hash").is_some_and(|h| !h.is_empty());
+        let algorithm = str_field(user, "hashing_algorithm").unwrap_or(LEGACY_HASHING_ALGORITHM);
+        if has_password
+            && users::hashing_algorithm_module(algorithm)
+                .is_none_or(|module| module == LEGACY_HASHING_ALGORITHM)
+        {
+            findings.push(Finding::new(
+                Severity::Warning,
+                "weak_password_hashing",
+                "",
+                name,
+                format!("the password is hashed using {}", algorithm),
+                "change the password (e.g. with 'users rotate_password') so that it is hashed using SHA-256 or SHA-512",
+            ));
+        }
+    }
+
+    for (node, failed) in &input.failed_auth_attempts {
+        if *failed > 0 {
+            findings.push(Finding::new(
+                Severity::Warning,
+                "failed_authentication_attempts",
+                "",
+                node,
+                format!("{} failed authentication attempts were recorded", failed),
+                "look for clients that use outdated credentials, or unauthorized access attempts, in the node's logs",
+            ));
+        }
+    }
+
+    sort_findings(&mut findings);
+    findings
+}
+
+/// Returns the type of a queue: the `x-queue-type` argument, the `type` field,
+/// or the default queue type of its virtual host.
+fn queue_type<'a>(defs: &'a Value, queue: &'a Value) -> &'a str {
+    let default_queue_type = |vhost: &'a Value| {
+        vhost
+            .get("metadata")
+            .and_then(|m| str_field(m, "default_queue_type"))
+            .or_else(|| str_field(vhost, "default_queue_type"))
+    };
+    // in virtual host-specific definition files, the virtual host's metadata is top-level
+    let from_vhost = || match vhost_of(queue) {
+        Some(vhost) => collection(defs, DefinitionKind::Vhosts)
+            .iter()
+            .find(|v| str_field(v, "name") == Some(vhost))
+            .and_then(default_queue_type),
+        None => default_queue_type(defs),
+    };
+
+    queue
+        .get("arguments")
+        .and_then(|args| str_field(args, "x-queue-type"))
+        .or_else(|| str_field(queue, "type"))
+        .or_else(from_vhost)
+        .unwrap_or("classic")
+}
+
+/// Returns the highest priority policy that matches a queue of the given type.
+fn effective_policy<'a>(
+    defs: &'a Value,
+    vhost: &str,
+    queue: &str,
+    target: &str,
+) -> Option<&'a Value> {
+    collection(defs, DefinitionKind::Policies)
+        .iter()
+        .filter(|p| vhost_of(p).unwrap_or_default() == vhost)
+        .filter(|p| targets_overlap(apply_to(p), target))
+        .filter(|p| {
+            str_field(p, "pattern")
+                .and_then(|pattern| Regex::new(pattern).ok())
+                .is_some_and(|re| re.is_match(queue))
+        })
+        .max_by_key(|p| priority(p))
+}
+
+fn priority(policy: &Value) -> i64 {
+    policy.get("priority").and_then(Value::as_i64).unwrap_or(0)
+}
+
+fn apply_to(policy: &Value) -> &str {
+    str_field(policy, "apply-to").unwrap_or("all")
+}
+
+fn targets_overlap(a: &str, b: &str) -> bool {
+    const QUEUE_TYPES: [&str; 3] = ["classic_queues", "quorum_queues", "streams"];
+    a == b
+        || a == "all"
+        || b == "all"
+        || (a == "queues" && QUEUE_TYPES.contains(&b))
+        || (b == "queues" && QUEUE_TYPES.contains(&a))
+}
@@ -31,6 +31,12 @@ pub enum SkipReason {
     /// The entity was not present at the time of the action, and the
     /// caller asked for idempotent behavior.
     AlreadyAbsent,
+    /// The entity already exists, and the caller is converging towards
+    /// a desired state rather than creating it unconditionally.
+    AlreadyPresent,
+    /// The entity conflicts with an existing one, and the caller asked
+    /// for conflicts to be skipped.
+    Conflict { reason: String },
 }
 
 impl fmt::Display for SkipReason {
@@ -38,6 +44,8 @@ impl fmt::Display for SkipReason {
         match self {
             SkipReason::Protected { reason } => write!(f, "protected: {reason}"),
             SkipReason::AlreadyAbsent => write!(f, "already absent"),
+            SkipReason::AlreadyPresent => write!(f, "already present"),
+            SkipReason::Conflict { reason } => write!(f, "conflict: {reason}"),
         }
     }
 }
@@ -18,6 +18,7 @@ use crate::arg_helpers::ArgMatchesExt;
 use crate::commands;
 use crate::errors::CommandRunError;
 use crate::output::{BulkOutputFormat, BulkReportOpts, ResultHandler};
+use crate::permission_check::{PermissionCheck, PermissionMatrixRow};
 use clap::ArgMatches;
 use rabbitmq_http_client::commons::PolicyTarget;
 use sysexits::ExitCode;
@@ -43,6 +44,7 @@ pub fn dispatch_command_group(
     res_handler: &mut ResultHandler,
 ) -> ExitCode {
     match first_level {
+        "audit" => dispatch_audit(second_level, args, client, res_handler),
         "auth_attempts" => dispatch_auth_attempts(second_level, args, client, res_handler),
         "bindings" => dispatch_bindings(second_level, args, client, &vhost, res_handler),
         "channels" => dispatch_channels(second_level, args, client, res_handler),
@@ -77,6 +79,10 @@ pub fn dispatch_command_group(
         "show" => dispatch_show(second_level, args, client, &endpoint, res_handler),
         "shovels" => dispatch_shovels(second_level, args, client, &vhost, res_handler),
         "streams" => dispatch_streams(second_level, args, client, &vhost, res_handler),
+        "tenants" => dispatch_tenants(second_level, args, client, res_handler),
+        "topic_permissions" => {
+            dispatch_topic_permissions(second_level, args, client, &vhost, res_handler)
+        }
         "users" => dispatch_users(second_level, args, client, res_handler),
         "user_limits" => dispatch_user_limits(second_level, args, client, res_handler),
         "vhosts" => dispatch_vhosts(second_level, args, client, res_handler),
@@ -98,6 +104,33 @@ fn unknown_subcommand(
     res_handler.exit_code.unwrap_or(ExitCode::Usage)
 }
 
+/// Used by `list permissions`, `permissions list` and `users permissions`.
+fn list_permissions(args: &ArgMatches, client: APIClient, res_handler: &mut ResultHandler) {
+    if args.optional_typed_or::<bool>("include_topic", false) {
+        let result = commands::list_permissions_including_topic(client);
+        res_handler.tabular_result(result);
+    } else {
+        let result = commands::list_permissions(client);
+        res_handler.tabular_result(result);
+    }
+}
+
+fn dispatch_audit(
+    subcommand: &str,
+    args: &ArgMatches,
+    client: APIClient,
+    res_handler: &mut ResultHandler,
+) -> ExitCode {
+    match subcommand {
+        "topology" => {
+            let result = commands::audit_topology(client, args);
+            res_handler.tabular_result(result);
+        }
+        _ => return unknown_subcommand("audit", subcommand, res_handler),
+    }
+    res_handler.exit_code.unwrap_or(ExitCode::Usage)
+}
+
 fn dispatch_auth_attempts(
     subcommand: &str,
     args: &ArgMatches,
@@ -281,13 +314,33 @@ fn dispatch_definitions(
             res_handler.no_output_on_success(result);
         }
         "import" => {
-            let result = commands::import_definitions(client, args);
-            res_handler.no_output_on_success(result);
+            if args.contains_id("from_dir") {
+                let mut prog_rep = res_handler.instantiate_progress_reporter();
+                let result = commands::import_definitions_from_dir(client, args, prog_rep.as_mut());
+                res_handler.no_output_on_success(result);
+            } else if args.contains_id("on_conflict") {
+                let opts = bulk_report_opts(args);
+                let mut prog_rep = res_handler.instantiate_progress_reporter();
+                let result =
+                    commands::import_definitions_object_by_object(client, args, &mut *prog_rep);
+                drop(prog_rep);
+                match result {
+                    Ok(report) => res_handler.render_bulk_report(report, opts),
+                    Err(e) => res_handler.no_output_on_success::<()>(Err(e)),
+                }
+            } else {
+                let result = commands::import_definitions(client, args);
+                res_handler.no_output_on_success(result);
+            }
         }
         "import_into_vhost" => {
             let result = commands::import_vhost_definitions(client, vhost, args);
             res_handler.no_output_on_success(result);
         }
+        "merge" => {
+            let result = commands::merge_definitions(args);
+            res_handler.local_no_output_on_success(result);
+        }
         _ => return unknown_subcommand("definitions", subcommand, res_handler),
     }
     res_handler.exit_code.unwrap_or(ExitCode::Usage)
@@ -558,6 +611,18 @@ fn dispatch_health_check(
             let result = commands::health_check_cluster_wide_alarms(client);
             res_handler.health_check_result(result);
         }
+        "connections" => {
+            let result = commands::health_check_connections(client, args);
+            res_handler.health_check_result(result);
+        }
+        "consumers" => {
+            let result = commands::health_check_consumers(client, args);
+            res_handler.health_check_result(result);
+        }
+        "deprecated_features_in_use" => {
+            let result = commands::health_check_deprecated_features_in_use(client);
+            res_handler.health_check_result(result);
+        }
         "local_alarms" => {
             let result = commands::health_check_local_alarms(client);
             res_handler.health_check_result(result);
@@ -574,6 +639,14 @@ fn dispatch_health_check(
             let result = commands::health_check_protocol_listener(client, args);
             res_handler.health_check_result(result);
         }
+        "queue_depth" => {
+            let result = commands::health_check_queue_depth(client, args);
+            res_handler.health_check_result(result);
+        }
+        "unacked" => {
+            let result = commands::health_check_unacknowledged_messages(client, args);
+            res_handler.health_check_result(result);
+        }
         _ => return unknown_subcommand("health_check", subcommand, res_handler),
     }
     res_handler.exit_code.unwrap_or(ExitCode::Usage)
@@ -648,8 +721,7 @@ fn dispatch_list(
             res_handler.tabular_result(result);
         }
         "permissions" => {
-            let result = commands::list_permissions(client);
-            res_handler.tabular_result(result);
+            list_permissions(args, client, res_handler);
         }
         "policies" => {
             let result = commands::list_policies(client);
@@ -808,10 +880,23 @@ fn dispatch_passwords(
     res_handler: &mut ResultHandler,
 ) -> ExitCode {
     match subcommand {
+        "generate" => {
+            let generated = commands::generate_passwords(args);
+            if args.get_one::<String>("output").map(String::as_str) == Some("json") {
+                let json = serde_json::to_string_pretty(&generated).unwrap();
+                res_handler.single_value_output_with_result(Ok(json));
+            } else {
+                res_handler.local_tabular_result(Ok(generated));
+            }
+        }
         "salt_and_hash" => {
             let result = commands::salt_and_hash_password(args);
             res_handler.show_salted_and_hashed_value(result);
         }
+        "verify" => {
+            let result = commands::verify_password_hash(args);
+            res_handler.single_value_output_with_result(result);
+        }
         _ => return unknown_subcommand("passwords", subcommand, res_handler),
     }
     res_handler.exit_code.unwrap_or(ExitCode::Usage)
@@ -826,8 +911,7 @@ fn dispatch_permissions(
 ) -> ExitCode {
     match subcommand {
         "list" => {
-            let result = commands::list_permissions(client);
-            res_handler.tabular_result(result);
+            list_permissions(args, client, res_handler);
         }
         "declare" => {
             let result = commands::declare_permissions(client, vhost, args);
@@ -837,6 +921,29 @@ fn dispatch_permissions(
             let result = commands::delete_permissions(client, vhost, args);
             res_handler.no_output_on_success(result);
         }
+        "check" => {
+            if args.optional_typed_or::<bool>("matrix", false) {
+                let result = commands::permissions_matrix(client, args);
+                res_handler.permission_check_result(result, PermissionMatrixRow::is_evaluated);
+            } else {
+                let result = commands::check_permissions(client, vhost, args);
+                res_handler.permission_check_result(result, PermissionCheck::is_evaluated);
+            }
+        }
+        "copy" | "declare_in_all" => {
+            let opts = bulk_report_opts(args);
+            let mut prog_rep = res_handler.instantiate_progress_reporter();
+            let result = if subcommand == "copy" {
+                commands::copy_permissions(client, args, &mut *prog_rep)
+            } else {
+                commands::declare_permissions_in_all(client, args, &mut *prog_rep)
+            };
+            drop(prog_rep);
+            match result {
+                Ok(report) => res_handler.render_bulk_report(report, opts),
+                Err(e) => res_handler.no_output_on_success::<()>(Err(e)),
+            }
+        }
         _ => return unknown_subcommand("permissions", subcommand, res_handler),
     }
     res_handler.exit_code.unwrap_or(ExitCode::Usage)
@@ -1072,6 +1179,10 @@ fn dispatch_show(
             let result = commands::show_overview(client);
             res_handler.show_overview(result);
         }
+        "whoami" => {
+            let result = commands::show_whoami(client);
+            res_handler.show_whoami(result);
+        }
         _ => return unknown_subcommand("show", subcommand, res_handler),
     }
     res_handler.exit_code.unwrap_or(ExitCode::Usage)
@@ -1196,6 +1307,73 @@ fn dispatch_streams(
     res_handler.exit_code.unwrap_or(ExitCode::Usage)
 }
 
+fn dispatch_tenants(
+    subcommand: &str,
+    args: &ArgMatches,
+    client: APIClient,
+    res_handler: &mut ResultHandler,
+) -> ExitCode {
+    match subcommand {
+        "provision" => {
+            // A partially provisioned (and then rolled back) tenant is never a success
+            let output = args.optional_string("output");
+            let opts = BulkReportOpts {
+                strict: true,
+                detailed_exit_codes: false,
+                output_format: BulkOutputFormat::parse(output.as_deref()),
+            };
+            let mut prog_rep = res_handler.instantiate_progress_reporter();
+            let result = commands::provision_tenant(client, args, &mut *prog_rep);
+            drop(prog_rep);
+            match result {
+                Ok(report) => res_handler.render_bulk_report(report, opts),
+                Err(e) => res_handler.no_output_on_success::<()>(Err(e)),
+            }
+        }
+        "deprovision" => {
+            let opts = bulk_report_opts(args);
+            let mut prog_rep = res_handler.instantiate_progress_reporter();
+            let result = commands::deprovision_tenant(client, args, &mut *prog_rep);
+            drop(prog_rep);
+            match result {
+                Ok(report) => res_handler.render_bulk_report(report, opts),
+                Err(e) => res_handler.no_output_on_success::<()>(Err(e)),
+            }
+        }
+        _ => return unknown_subcommand("tenants", subcommand, res_handler),
+    }
+    res_handler.exit_code.unwrap_or(ExitCode::Usage)
+}
+
+fn dispatch_topic_permissions(
+    subcommand: &str,
+    args: &ArgMatches,
+    client: APIClient,
+    vhost: &str,
+    res_handler: &mut ResultHandler,
+) -> ExitCode {
+    match subcommand {
+        "list" => {
+            let result = commands::list_topic_permissions(client);
+            res_handler.tabular_result(result);
+        }
+        "list_in" => {
+            let result = commands::list_topic_permissions_in(client, vhost);
+            res_handler.tabular_result(result);
+        }
+        "declare" => {
+            let result = commands::declare_topic_permissions(client, vhost, args);
+            res_handler.no_output_on_success(result);
+        }
+        "delete" => {
+            let result = commands::delete_topic_permissions(client, vhost, args);
+            res_handler.no_output_on_success(result);
+        }
+        _ => return unknown_subcommand("topic_permissions", subcommand, res_handler),
+    }
+    res_handler.exit_code.unwrap_or(ExitCode::Usage)
+}
+
 fn dispatch_users(
     subcommand: &str,
     args: &ArgMatches,
@@ -1203,6 +1381,15 @@ fn dispatch_users(
     res_handler: &mut ResultHandler,
 ) -> ExitCode {
     match subcommand {
+        "audit" => {
+            let result = commands::audit_users(client, args);
+            if args.get_one::<String>("output").map(String::as_str) == Some("json") {
+                let json = result.map(|findings| serde_json::to_string_pretty(&findings).unwrap());
+                res_handler.single_value_output_with_result(json);
+            } else {
+                res_handler.tabular_result(result);
+            }
+        }
         "connections" => {
             let result = commands::list_user_connections(client, args);
             res_handler.tabular_result(result);
@@ -1215,6 +1402,30 @@ fn dispatch_users(
             let result = commands::delete_user(client, args);
             res_handler.delete_operation_result(result);
         }
+        "delete_multiple" => {
+            let opts = bulk_report_opts(args);
+            let mut prog_rep = res_handler.instantiate_progress_reporter();
+            let result = commands::delete_multiple_users(client, args, &mut *prog_rep);
+            drop(prog_rep);
+            match result {
+                Ok(report) => res_handler.render_bulk_report(report, opts),
+                Err(e) => res_handler.no_output_on_success::<()>(Err(e)),
+            }
+        }
+        "export" => {
+            let result = commands::export_users_to_csv(client, args);
+            res_handler.no_output_on_success(result);
+        }
+        "import" => {
+            let opts = bulk_report_opts(args);
+            let mut prog_rep = res_handler.instantiate_progress_reporter();
+            let result = commands::import_users_from_csv(client, args, &mut *prog_rep);
+            drop(prog_rep);
+            match result {
+                Ok(report) => res_handler.render_bulk_report(report, opts),
+                Err(e) => res_handler.no_output_on_success::<()>(Err(e)),
+            }
+        }
         "limits" => {
             let result = commands::list_user_limits(client, args);
             res_handler.tabular_result(result);
@@ -1224,8 +1435,11 @@ fn dispatch_users(
             res_handler.tabular_result(result);
         }
         "permissions" => {
-            let result = commands::list_permissions(client);
-            res_handler.tabular_result(result);
+            list_permissions(args, client, res_handler);
+        }
+        "update" => {
+            let result = commands::update_user_tags(client, args);
+            res_handler.no_output_on_success(result);
         }
         _ => return unknown_subcommand("users", subcommand, res_handler),
     }
@@ -1285,6 +1499,26 @@ fn dispatch_vhosts(
             let result = commands::list_vhosts(client);
             res_handler.tabular_result(result);
         }
+        "clone" | "rename" => {
+            // A partially completed clone is never a success
+            let output = args.optional_string("output");
+            let opts = BulkReportOpts {
+                strict: true,
+                detailed_exit_codes: false,
+                output_format: BulkOutputFormat::parse(output.as_deref()),
+            };
+            let mut prog_rep = res_handler.instantiate_progress_reporter();
+            let result = if subcommand == "clone" {
+                commands::clone_vhost(client, args, &mut *prog_rep)
+            } else {
+                commands::rename_vhost(client, args, &mut *prog_rep)
+            };
+            drop(prog_rep);
+            match result {
+                Ok(report) => res_handler.render_bulk_report(report, opts),
+                Err(e) => res_handler.no_output_on_success::<()>(Err(e)),
+            }
+        }
         "enable_deletion_protection" => {
             let result = commands::enable_vhost_deletion_protection(client, args);
             res_handler.no_output_on_success(result);
@@ -20,7 +20,10 @@ use super::constants::*;
 use super::static_urls::*;
 use super::tanzu_cli::tanzu_subcommands;
 use crate::config::PreFlightSettings;
+use crate::health_checks;
 use crate::output::TableStyle;
+use crate::passwords;
+use crate::transformations::{CLUSTER_WIDE_TRANSFORMATIONS, VHOST_TRANSFORMATIONS};
 use clap::{Arg, ArgAction, ArgGroup, Command, crate_name, crate_version, value_parser};
 use rabbitmq_http_client::commons::{
     BindingDestinationType, ChannelUseMode, ExchangeType, MessageTransferAcknowledgementMode,
@@ -61,6 +64,16 @@ pub fn parser(pre_flight_settings: PreFlightSettings) -> Command {
         ))
         .arg_required_else_help(true)
         .subcommands(auth_attempts_subcommands(pre_flight_settings.clone()));
+    let audit_group = Command::new("audit")
+        .about("Checks the topology against common recommendations")
+        .infer_subcommands(pre_flight_settings.infer_subcommands)
+        .infer_long_args(pre_flight_settings.infer_long_options)
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            PRODUCTION_CHECKLIST_GUIDE_URL
+        ))
+        .arg_required_else_help(true)
+        .subcommands(audit_subcommands(pre_flight_settings.clone()));
     let bindings_group = Command::new("bindings")
         .about("Operations on bindings")
         .infer_subcommands(pre_flight_settings.infer_subcommands)
@@ -273,6 +286,17 @@ pub fn parser(pre_flight_settings: PreFlightSettings) -> Command {
         .subcommand_value_name("permission")
         .arg_required_else_help(true)
         .subcommands(permissions_subcommands(pre_flight_settings.clone()));
+    let topic_permissions_group = Command::new("topic_permissions")
+        .about("Operations on topic permissions")
+        .infer_subcommands(pre_flight_settings.infer_subcommands)
+        .infer_long_args(pre_flight_settings.infer_long_options)
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            TOPIC_PERMISSIONS_GUIDE_URL
+        ))
+        .subcommand_value_name("topic_permission")
+        .arg_required_else_help(true)
+        .subcommands(topic_permissions_subcommands(pre_flight_settings.clone()));
     let plugins_group = Command::new("plugins")
         .about("Lists enabled plugins")
         .infer_subcommands(pre_flight_settings.infer_subcommands)
@@ -367,6 +391,17 @@ pub fn parser(pre_flight_settings: PreFlightSettings) -> Command {
         .subcommand_value_name("subcommand")
         .arg_required_else_help(true)
         .subcommands(tanzu_subcommands());
+    let tenants_group = Command::new("tenants")
+        .about("Provisions and deprovisions tenants: a virtual host with its users, permissions, limits and topology")
+        .infer_subcommands(pre_flight_settings.infer_subcommands)
+        .infer_long_args(pre_flight_settings.infer_long_options)
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            VIRTUAL_HOST_GUIDE_URL
+        ))
+        .subcommand_value_name("subcommand")
+        .arg_required_else_help(true)
+        .subcommands(tenants_subcommands(pre_flight_settings.clone()));
     let users_group = Command::new("users")
         .about("Operations on users")
         .infer_subcommands(pre_flight_settings.infer_subcommands)
@@ -414,6 +449,7 @@ pub fn parser(pre_flight_settings: PreFlightSettings) -> Command {
         .subcommands(vhost_limits_subcommands(pre_flight_settings.clone()));
 
     let command_groups = [
+        audit_group,
         auth_attempts_group,
         bindings_group,
         channels_group,
@@ -449,6 +485,8 @@ pub fn parser(pre_flight_settings: PreFlightSettings) -> Command {
         shovels_group,
         streams_group,
         tanzu_group,
+        tenants_group,
+        topic_permissions_group,
         users_group,
         user_limits_group,
         vhosts_group,
@@ -690,6 +728,115 @@ fn columns_arg() -> Arg {
         .value_parser(value_parser!(String))
 }
 
+fn transformation_rules_arg() -> Arg {
+    Arg::new("transformation_rules")
+        .long("transformation-rules")
+        .help("path to a TOML file with transformation rules to apply, in order")
+        .long_help(
+            r#"
+Path to a TOML file with user-defined transformation rules.
+The rules are applied in order, after the --transformations (if any).
+
+Supported rule actions:
+
+ * drop: drops objects of a kind whose names match `name_pattern`
+ * rename_vhost: renames virtual host `from` to `to`, including all references to it
+ * set_queue_argument: sets an optional queue argument `key` to `value`
+ * remove_queue_argument: removes an optional queue argument `key`
+ * rewrite_policy_pattern: replaces regular expression `from` in policy patterns with `to`
+ * replace_uri_host: replaces host `from` with `to` in shovel and federation upstream URIs
+
+Example:
+
+ [[rule]]
+ action = "drop"
+ kind = "queues"
+ name_pattern = "^tmp\\."
+
+ [[rule]]
+ action = "rename_vhost"
+ from = "staging"
+ to = "production"
+            "#,
+        )
+        .required(false)
+        .value_parser(value_parser!(PathBuf))
+}
+
+fn uri_credentials_args() -> [Arg; 2] {
+    [
+        Arg::new("uri_credentials_from_env")
+            .long("uri-credentials-from-env")
+            .help("substitute URI credential placeholders left by the redact_uri_credentials transformation with values of environment variables of the same name")
+            .required(false)
+            .action(ArgAction::SetTrue),
+        Arg::new("uri_credentials_file")
+            .long("uri-credentials-file")
+            .help("path to a TOML file that maps URI credential placeholder names to 'username:password' values; takes precedence over --uri-credentials-from-env")
+            .required(false)
+            .value_parser(value_parser!(PathBuf)),
+    ]
+}
+
+fn canonical_export_args() -> [Arg; 2] {
+    [
+        Arg::new("canonical")
+            .long("canonical")
+            .help("sort collections and keys, normalize optional argument value types to produce diff-friendly output")
+            .required(false)
+            .action(ArgAction::SetTrue),
+        Arg::new("drop_volatile_fields")
+            .long("drop-volatile-fields")
+            .help("omit fields that change between exports of the same topology, such as rabbitmq_version")
+            .required(false)
+            .requires("canonical")
+            .action(ArgAction::SetTrue),
+    ]
+}
+
+fn vhost_filter_args() -> [Arg; 2] {
+    [
+        Arg::new("include_vhosts")
+            .long("include-vhosts")
+            .help("only export virtual hosts (and objects in them) whose names match this regular expression")
+            .required(false),
+        Arg::new("exclude_vhosts")
+            .long("exclude-vhosts")
+            .help("do not export virtual hosts (and objects in them) whose names match this regular expression")
+            .required(false),
+    ]
+}
+
+fn kind_and_name_filter_args() -> [Arg; 4] {
+    let kinds = ["queues", "exchanges", "bindings", "policies", "parameters"];
+    [
+        Arg::new("include_kinds")
+            .long("include-kinds")
+            .help("a comma-separated list of virtual host-scoped object kinds to export")
+            .required(false)
+            .num_args(1..)
+            .value_delimiter(',')
+            .value_parser(kinds)
+            .action(ArgAction::Append),
+        Arg::new("exclude_kinds")
+            .long("exclude-kinds")
+            .help("a comma-separated list of virtual host-scoped object kinds to leave out")
+            .required(false)
+            .num_args(1..)
+            .value_delimiter(',')
+            .value_parser(kinds)
+            .action(ArgAction::Append),
+        Arg::new("include_names")
+            .long("include-names")
+            .help("only export virtual host-scoped objects whose names match this regular expression (bindings match by source or destination)")
+            .required(false),
+        Arg::new("exclude_names")
+            .long("exclude-names")
+            .help("do not export virtual host-scoped objects whose names match this regular expression (bindings match by source or destination)")
+            .required(false),
+    ]
+}
+
 fn list_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
     let nodes_cmd = Command::new("nodes")
         .long_about("Lists cluster nodes")
@@ -772,7 +919,14 @@ fn list_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
         .after_help(color_print::cformat!(
             "<bold>Doc guide</bold>: {}",
             ACCESS_CONTROL_GUIDE_URL
-        ));
+        ))
+        .arg(
+            Arg::new("include_topic")
+                .long("include-topic")
+                .action(ArgAction::SetTrue)
+                .help("also list topic permissions")
+                .required(false),
+        );
     let user_connections_cmd = Command::new("user_connections")
         .arg(
             Arg::new("username")
@@ -1254,6 +1408,12 @@ fn show_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
     let overview_cmd = Command::new("overview")
         .about("Displays essential information about target node and its cluster");
     let churn_cmd = Command::new("churn").about("Displays object churn metrics");
+    let whoami_cmd = Command::new("whoami")
+        .about("Displays the user the CLI is authenticated as and its tags")
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            HTTP_API_ACCESS_PERMISSIONS_GUIDE_URL
+        ));
     let endpoint_cmd = Command::new("endpoint")
         .about("Displays the computed HTTP API endpoint URI. Use for troubleshooting only.");
     let memory_breakdown_in_bytes_cmd = Command::new("memory_breakdown_in_bytes")
@@ -1288,6 +1448,7 @@ fn show_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
         endpoint_cmd,
         memory_breakdown_in_bytes_cmd,
         memory_breakdown_in_percent_cmd,
+        whoami_cmd,
     ]
     .into_iter()
     .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
@@ -1492,6 +1653,59 @@ fn purge_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
         .collect()
 }
 
+fn audit_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
+    let topology_cmd = Command::new("topology")
+        .about("Reports topology, permission and configuration issues, each with a severity and a remediation hint")
+        .long_about(
+"Audits the definitions of the cluster, or of a definitions file, and reports:
+
+ * queues without bindings, or without consumers (live clusters only)
+ * exchanges without bindings
+ * durable classic queues that would be better off as quorum queues
+ * quorum queues without a delivery limit or dead lettering
+ * policies with conflicting priorities
+ * users with full ('.*') permissions in many virtual hosts, and the 'guest' user
+ * virtual hosts without limits",
+        )
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            PRODUCTION_CHECKLIST_GUIDE_URL,
+            QUORUM_QUEUE_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("file")
+                .long("file")
+                .help("audit a cluster-wide definitions JSON file instead of the cluster")
+                .required(false)
+                .conflicts_with("stdin"),
+        )
+        .arg(
+            Arg::new("stdin")
+                .long("stdin")
+                .help("audit cluster-wide definitions read from the standard input stream")
+                .required(false)
+                .num_args(0)
+                .action(ArgAction::SetTrue)
+                .conflicts_with("file"),
+        )
+        .arg(
+            Arg::new("broad_permissions_threshold")
+                .long("broad-permissions-threshold")
+                .help("report users with full permissions in at least this many virtual hosts")
+                .required(false)
+                .default_value("5")
+                .value_parser(value_parser!(usize)),
+        );
+
+    [topology_cmd]
+        .into_iter()
+        .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
+        .collect()
+}
+
 fn auth_attempts_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
     let stats_cmd = Command::new("stats")
         .about("Displays authentication attempt statistics for a cluster node")
@@ -2492,13 +2706,257 @@ fn health_check_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Comma
             HEALTH_CHECK_GUIDE_URL
         ));
 
+    let certificate_expiration = Command::new("certificate_expiration")
+        .about("Fails if any of the TLS-enabled listeners on the target node use a certificate that expires within the given period")
+        .arg(
+            Arg::new("within")
+                .long("within")
+                .value_parser(value_parser!(u32))
+                .help("the period, in --unit units")
+                .required(true),
+        )
+        .arg(
+            Arg::new("unit")
+                .long("unit")
+                .value_parser(health_checks::CERTIFICATE_EXPIRATION_UNITS)
+                .default_value("days")
+                .help("the unit of --within")
+                .required(false),
+        )
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            HEALTH_CHECK_GUIDE_URL,
+            TLS_GUIDE_URL
+        ));
+    let virtual_hosts = Command::new("virtual_hosts")
+        .about("Fails if any of the virtual hosts are down on the target node")
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            HEALTH_CHECK_GUIDE_URL
+        ));
+    let ready_to_serve_clients = Command::new("ready_to_serve_clients")
+        .about("Fails if the target node is not ready to serve clients: it is booting, in maintenance mode, or its listeners are not running")
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            HEALTH_CHECK_GUIDE_URL
+        ));
+    let is_in_service = Command::new("is_in_service")
+        .about("Fails if the target node is not in service, e.g. because it is in maintenance mode")
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            HEALTH_CHECK_GUIDE_URL
+        ));
+
+    let queue_depth = Command::new("queue_depth")
+        .about("Fails if any of the queues across all virtual hosts have more than the given number of messages")
+        .arg(
+            Arg::new("max")
+                .long("max")
+                .value_parser(value_parser!(u64))
+                .help("the maximum number of messages a queue can have")
+                .required(true),
+        )
+        .arg(
+            Arg::new("name_pattern")
+                .long("name-pattern")
+                .help("a regular expression that will be used to match queue names")
+                .required(false),
+        )
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            HEALTH_CHECK_GUIDE_URL,
+            MONITORING_GUIDE_URL
+        ));
+    let consumers = Command::new("consumers")
+        .about("Fails if any of the queues across all virtual hosts have fewer than the given number of consumers")
+        .arg(
+            Arg::new("min")
+                .long("min")
+                .value_parser(value_parser!(u64))
+                .default_value("1")
+                .help("the minimum number of consumers a queue must have")
+                .required(false),
+        )
+        .arg(
+            Arg::new("name_pattern")
+                .long("name-pattern")
+                .help("a regular expression that will be used to match queue names")
+                .required(false),
+        )
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            HEALTH_CHECK_GUIDE_URL,
+            CONSUMER_GUIDE_URL
+        ));
+    let unacked = Command::new("unacked")
+        .about("Fails if any of the queues across all virtual hosts have more than the given number of unacknowledged messages")
+        .arg(
+            Arg::new("max")
+                .long("max")
+                .value_parser(value_parser!(u64))
+                .help("the maximum number of unacknowledged messages a queue can have")
+                .required(true),
+        )
+        .arg(
+            Arg::new("name_pattern")
+                .long("name-pattern")
+                .help("a regular expression that will be used to match queue names")
+                .required(false),
+        )
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            HEALTH_CHECK_GUIDE_URL,
+            CONFIRMS_GUIDE_URL
+        ));
+    let connections = Command::new("connections")
+        .about("Fails if any user has more than the given number of connections open")
+        .arg(
+            Arg::new("max_per_user")
+                .long("max-per-user")
+                .value_parser(value_parser!(u64))
+                .help("the maximum number of connections a user can have open")
+                .required(true),
+        )
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            HEALTH_CHECK_GUIDE_URL,
+            CONNECTION_GUIDE_URL
+        ));
+
+    let all = Command::new("all")
+        .about("Runs multiple health checks and reports the outcome of each")
+        .long_about(
+"Runs multiple health checks in one go and reports the status, duration and failure details of each.
+By default, all checks are run, except for port_listener, protocol_listener and certificate_expiration
+unless --port, --protocol and --within, respectively, are provided. Likewise, the queue_depth, consumers,
+unacked and connections checks only run by default when --max-queue-depth, --min-consumers, --max-unacked
+and --max-connections-per-user, respectively, are provided.
+
+Unless --continue-on-failure is set, the checks after the first failed one are not run.
+Exits with a non-zero code if any of the checks failed."
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            HEALTH_CHECK_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("checks")
+                .long("checks")
+                .value_delimiter(',')
+                .action(ArgAction::Append)
+                .value_parser(health_checks::HealthCheck::names())
+                .help("a comma-separated list of health checks to run")
+                .required(false),
+        )
+        .arg(
+            Arg::new("port")
+                .long("port")
+                .value_parser(value_parser!(u16))
+                .help("the port used by the port_listener check")
+                .required(false),
+        )
+        .arg(
+            Arg::new("protocol")
+                .long("protocol")
+                .value_parser(value_parser!(SupportedProtocol))
+                .help("the protocol alias used by the protocol_listener check")
+                .required(false),
+        )
+        .arg(
+            Arg::new("within")
+                .long("within")
+                .value_parser(value_parser!(u32))
+                .help("the period used by the certificate_expiration check, in --unit units")
+                .required(false),
+        )
+        .arg(
+            Arg::new("unit")
+                .long("unit")
+                .value_parser(health_checks::CERTIFICATE_EXPIRATION_UNITS)
+                .default_value("days")
+                .help("the unit of --within")
+                .required(false),
+        )
+        .arg(
+            Arg::new("max_queue_depth")
+                .long("max-queue-depth")
+                .value_parser(value_parser!(u64))
+                .help("the maximum number of messages a queue can have, used by the queue_depth check")
+                .required(false),
+        )
+        .arg(
+            Arg::new("min_consumers")
+                .long("min-consumers")
+                .value_parser(value_parser!(u64))
+                .help("the minimum number of consumers a queue must have, used by the consumers check (default: 1)")
+                .required(false),
+        )
+        .arg(
+            Arg::new("max_unacked")
+                .long("max-unacked")
+                .value_parser(value_parser!(u64))
+                .help("the maximum number of unacknowledged messages a queue can have, used by the unacked check")
+                .required(false),
+        )
+        .arg(
+            Arg::new("name_pattern")
+                .long("name-pattern")
+                .help("a regular expression that will be used to match the queue names the queue_depth, consumers and unacked checks cover")
+                .required(false),
+        )
+        .arg(
+            Arg::new("max_connections_per_user")
+                .long("max-connections-per-user")
+                .value_parser(value_parser!(u64))
+                .help("the maximum number of connections a user can have open, used by the connections check")
+                .required(false),
+        )
+        .arg(
+            Arg::new("continue_on_failure")
+                .long("continue-on-failure")
+                .action(ArgAction::SetTrue)
+                .help("run the remaining checks after a check fails")
+                .required(false),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "json"])
+                .help("output format (default: table)")
+                .required(false),
+        );
+
     [
+        all,
         local_alarms,
         cluster_wide_alarms,
         node_is_quorum_critical,
         deprecated_features_in_use,
         port_listener,
         protocol_listener,
+        certificate_expiration,
+        virtual_hosts,
+        ready_to_serve_clients,
+        is_in_service,
+        queue_depth,
+        consumers,
+        unacked,
+        connections,
     ]
     .into_iter()
     .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
@@ -2785,6 +3243,24 @@ fn definitions_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Comman
                 .action(ArgAction::SetTrue)
                 .conflicts_with("file"),
         )
+        .arg(
+            Arg::new("split_into")
+                .long("split-into")
+                .help("write a cluster-level definitions file plus one file per virtual host into this directory")
+                .required(false)
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with_all(["file", "stdout"]),
+        )
+        .arg(
+            Arg::new("prune")
+                .long("prune")
+                .help("allow --split-into to use a non-empty directory, removing the files of virtual hosts that no longer exist")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .requires("split_into"),
+        )
+        .args(vhost_filter_args())
+        .args(kind_and_name_filter_args())
         .arg(
             Arg::new("transformations")
                 .long("transformations")
@@ -2803,9 +3279,10 @@ Supported transformations:
  * exclude_permissions
  * exclude_runtime_parameters
  * exclude_policies
+ * redact_uri_credentials
  * no_op
 
-All unknown transformations will be ignored (will be replaced with a `no_op`).
+Unknown transformation names are rejected.
 
 Examples:
 
@@ -2814,14 +3291,18 @@ Examples:
  * --transformations exclude_users,exclude_permissions
  * --transformations obfuscate_usernames
  * --transformations exclude_runtime_parameters,exclude_policies
+ * --transformations redact_uri_credentials
  * --transformations no_op
                 "#,
                 )
                 .num_args(1..)
                 .value_delimiter(',')
+                .value_parser(CLUSTER_WIDE_TRANSFORMATIONS)
                 .action(ArgAction::Append)
                 .required(false),
-        );
+        )
+        .arg(transformation_rules_arg())
+        .args(canonical_export_args());
 
     let export_from_vhost_cmd = Command::new("export_from_vhost")
         .about("Exports definitions of a specific virtual host")
@@ -2861,22 +3342,28 @@ Supported transformations:
  * prepare_for_quorum_queue_migration
  * strip_cmq_keys_from_policies
  * drop_empty_policies
+ * redact_uri_credentials
  * no_op
 
-All unknown transformations will be ignored (will be replaced with a `no_op`).
+Unknown transformation names are rejected.
 
 Examples:
 
  * --transformations prepare_for_quorum_queue_migration,drop_empty_policies
  * --transformations strip_cmq_keys_from_policies,drop_empty_policies
+ * --transformations redact_uri_credentials
  * --transformations no_op
                 "#,
                 )
                 .num_args(1..)
                 .value_delimiter(',')
+                .value_parser(VHOST_TRANSFORMATIONS)
                 .action(ArgAction::Append)
                 .required(false),
-        );
+        )
+        .arg(transformation_rules_arg())
+        .args(canonical_export_args())
+        .args(kind_and_name_filter_args());
 
     let import_cmd = Command::new("import")
         .about("Imports cluster-wide definitions (of multiple virtual hosts)")
@@ -2888,10 +3375,20 @@ Examples:
             Arg::new("file")
                 .group("input")
                 .long("file")
-                .help("cluster-wide definitions JSON file path; mutually exclusive with --stdin")
+                .help("cluster-wide definitions JSON file path; mutually exclusive with --stdin. Can be repeated to merge multiple files (see 'definitions merge')")
                 .required(true)
+                .action(ArgAction::Append)
                 .conflicts_with("stdin"),
         )
+        .arg(
+            Arg::new("from_dir")
+                .group("input")
+                .long("from-dir")
+                .help("import a directory produced by 'definitions export --split-into', file by file")
+                .required(false)
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with_all(["file", "stdin"]),
+        )
         .arg(
             Arg::new("stdin")
                 .group("input")
@@ -2901,6 +3398,57 @@ Examples:
                 .num_args(0)
                 .action(ArgAction::SetTrue)
                 .conflicts_with("file"),
+        )
+        .arg(transformation_rules_arg())
+        .args(uri_credentials_args())
+        .arg(
+            Arg::new("on_conflict")
+                .long("on-conflict")
+                .help("import objects one by one; what to do with queues and exchanges that already exist with different properties. 'recreate' deletes them (with their messages and bindings) and requires --approve")
+                .required(false)
+                .value_parser(["fail", "skip", "recreate"])
+                .conflicts_with("from_dir"),
+        )
+        .arg(
+            Arg::new("approve")
+                .long("approve")
+                .action(ArgAction::SetTrue)
+                .help("approve the deletion of conflicting queues and exchanges with --on-conflict recreate")
+                .required(false)
+                .requires("on_conflict"),
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .action(ArgAction::SetTrue)
+                .help("with --on-conflict, treat partial success (some objects not imported) as a total failure")
+                .required(false)
+                .requires("on_conflict"),
+        )
+        .arg(
+            Arg::new("fail_fast")
+                .long("fail-fast")
+                .action(ArgAction::SetTrue)
+                .help("with --on-conflict, stop at the first object that fails to import")
+                .required(false)
+                .requires("on_conflict"),
+        )
+        .arg(
+            Arg::new("detailed_exit_codes")
+                .long("detailed-exit-codes")
+                .action(ArgAction::SetTrue)
+                .help("with --on-conflict, opt in to exit code 3 on partial success")
+                .required(false)
+                .requires("on_conflict")
+                .conflicts_with("strict"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "json"])
+                .help("with --on-conflict, output format for the per-object report (default: table)")
+                .required(false)
+                .requires("on_conflict"),
         );
 
     let import_into_vhost_cmd = Command::new("import_into_vhost")
@@ -2926,13 +3474,98 @@ Examples:
                 .num_args(0)
                 .action(ArgAction::SetTrue)
                 .conflicts_with("file"),
+        )
+        .arg(transformation_rules_arg())
+        .args(uri_credentials_args());
+
+    let merge_cmd = Command::new("merge")
+        .about("Merges multiple definitions files (fragments) into one")
+        .long_about(
+"Merges multiple definitions files (fragments) of the same format, cluster-wide or virtual host-specific, into one.
+
+Collections are unioned. Objects with the same natural key (e.g. queues with the same name in the same
+virtual host) must be identical, otherwise they are reported as conflicts and nothing is written.",
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            DEFINITION_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("file")
+                .long("file")
+                .help("definitions JSON file path; repeat to merge multiple files")
+                .required(true)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("output_file")
+                .short('o')
+                .long("output-file")
+                .help("merged definitions JSON file path; '-' to print to the standard output stream")
+                .required(true),
         );
 
+    let copy_cmd = Command::new("copy")
+        .about("Copies cluster-wide definitions from one node (cluster) to another")
+        .long_about(
+"Exports cluster-wide definitions from the node (cluster) configured in the --from-node section
+of the configuration file and imports them into the one configured in the --to-node section.
+
+A plan, the list of objects that would be created or updated on the destination, is displayed first.
+Importing definitions never deletes anything. --dry-run only displays the plan.",
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            DEFINITION_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("from_node")
+                .long("from-node")
+                .help("configuration file section (node alias) to export definitions from")
+                .required(true),
+        )
+        .arg(
+            Arg::new("to_node")
+                .long("to-node")
+                .help("configuration file section (node alias) to import definitions into")
+                .required(true),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("display the plan without importing anything")
+                .required(false),
+        )
+        .arg(
+            Arg::new("approve")
+                .long("approve")
+                .action(ArgAction::SetTrue)
+                .help("this operation modifies the destination cluster and requires an explicit approval")
+                .required(false),
+        )
+        .arg(
+            Arg::new("transformations")
+                .long("transformations")
+                .short('t')
+                .help("a comma-separated list of names of the definition transformations to apply, see 'definitions export --help'")
+                .num_args(1..)
+                .value_delimiter(',')
+                .value_parser(CLUSTER_WIDE_TRANSFORMATIONS)
+                .action(ArgAction::Append)
+                .required(false),
+        )
+        .arg(transformation_rules_arg())
+        .args(vhost_filter_args())
+        .args(kind_and_name_filter_args());
+
     [
         export_cmd,
         export_from_vhost_cmd,
         import_cmd,
         import_into_vhost_cmd,
+        merge_cmd,
+        copy_cmd,
     ]
     .into_iter()
     .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
@@ -3115,6 +3748,7 @@ Supported transformations:
  * exclude_permissions
  * exclude_runtime_parameters
  * exclude_policies
+ * redact_uri_credentials
 
 Examples:
 
@@ -3123,14 +3757,18 @@ Examples:
  * --transformations exclude_users,exclude_permissions
  * --transformations obfuscate_usernames
  * --transformations exclude_runtime_parameters,exclude_policies
+ * --transformations redact_uri_credentials
  * --transformations no_op
                 "#,
                 )
                 .num_args(1..)
                 .value_delimiter(',')
+                .value_parser(CLUSTER_WIDE_TRANSFORMATIONS)
                 .action(ArgAction::Append)
                 .required(false),
-        );
+        )
+        .arg(transformation_rules_arg())
+        .args(canonical_export_args());
     [definitions]
         .into_iter()
         .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
@@ -3149,7 +3787,9 @@ fn import_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
                 .long("file")
                 .help("JSON file with definitions")
                 .required(true),
-        )]
+        )
+        .arg(transformation_rules_arg())
+        .args(uri_credentials_args())]
     .into_iter()
     .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
     .collect()
@@ -3397,6 +4037,62 @@ Exit codes:
                 .required(false),
         )
         .arg(idempotently_arg.clone());
+    let clone_source_args = [
+        Arg::new("source")
+            .long("source")
+            .help("the virtual host to copy")
+            .required(true),
+        Arg::new("destination")
+            .long("destination")
+            .help("the virtual host to create; must not exist")
+            .required(true),
+        Arg::new("dry_run")
+            .long("dry-run")
+            .action(ArgAction::SetTrue)
+            .help("show the steps that would be performed without performing them")
+            .required(false),
+        Arg::new("output")
+            .long("output")
+            .value_parser(["table", "json"])
+            .help("output format for the report (default: table)")
+            .required(false),
+    ];
+    let clone_cmd = Command::new("clone")
+        .about("Creates a copy of a virtual host, including its metadata, limits, permissions and definitions")
+        .long_about(
+"Creates a copy of a virtual host: the new virtual host gets the description, tags, default queue type,
+limits, user and topic permissions of the source, plus its definitions (queues, exchanges, bindings,
+policies, runtime parameters). References to the source virtual host in the definitions are updated.
+
+Messages are not copied. If a step fails, the partially populated destination virtual host is left in place."
+        )
+        .after_help(color_print::cformat!("<bold>Doc guide</bold>: {}", VIRTUAL_HOST_GUIDE_URL))
+        .args(clone_source_args.clone());
+    let rename_cmd = Command::new("rename")
+        .about(color_print::cstr!("<bold><red>DANGER ZONE.</red></bold> 'Renames' a virtual host by cloning it and deleting the source"))
+        .long_about(color_print::cstr!(
+"<bold><red>DANGER ZONE.</red></bold> RabbitMQ cannot rename virtual hosts. This command performs the same steps
+as 'vhosts clone', then deletes the source virtual host, together with all messages in it.
+
+The source virtual host is only deleted after every other step has succeeded.
+Use --keep-source to leave it in place, and --dry-run to preview the steps."
+        ))
+        .after_help(color_print::cformat!("<bold>Doc guide</bold>: {}", VIRTUAL_HOST_GUIDE_URL))
+        .args(clone_source_args)
+        .arg(
+            Arg::new("keep_source")
+                .long("keep-source")
+                .action(ArgAction::SetTrue)
+                .help("do not delete the source virtual host")
+                .required(false),
+        )
+        .arg(
+            Arg::new("approve")
+                .long("approve")
+                .action(ArgAction::SetTrue)
+                .help("deleting the source virtual host is very destructive and requires an explicit approval")
+                .required(false),
+        );
     let enable_deletion_protection_cmd = Command::new("enable_deletion_protection")
         .about("Enables deletion protection for a virtual host")
         .after_help(color_print::cformat!(
@@ -3427,6 +4123,8 @@ Exit codes:
         declare_cmd,
         delete_cmd,
         bulk_delete_cmd,
+        clone_cmd,
+        rename_cmd,
         enable_deletion_protection_cmd,
         disable_deletion_protection_cmd,
     ]
@@ -3435,6 +4133,142 @@ Exit codes:
     .collect()
 }
 
+pub fn tenants_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
+    let provision_cmd = Command::new("provision")
+        .about("Creates a tenant's virtual host, users, permissions, limits, policies and topology from a spec file")
+        .long_about(
+"Creates everything described in a TOML tenant spec: a virtual host, users, permissions,
+topic permissions, policies, exchanges, queues, bindings and virtual host limits.
+
+Provisioning is idempotent: objects that already exist are left as they are and reported as skipped.
+If a step fails, the objects created by this run are deleted again.
+What was created is recorded in a global runtime parameter, which 'tenants deprovision' uses.
+
+An example spec:
+
+name = \"payments\"
+
+[vhost]
+description = \"Payments team\"
+default_queue_type = \"quorum\"
+
+[limits]
+max-connections = 500
+
+[[users]]
+name = \"payments-app\"
+password = \"s3kRe7\"
+
+[[permissions]]
+user = \"payments-app\"
+configure = \"^payments\\\\.\"
+write = \".*\"
+read = \".*\"
+
+[[exchanges]]
+name = \"payments.events\"
+type = \"topic\"
+
+[[queues]]
+name = \"payments.settlements\"
+
+[[bindings]]
+source = \"payments.events\"
+destination = \"payments.settlements\"
+routing_key = \"settlement.#\""
+        )
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            VIRTUAL_HOST_GUIDE_URL,
+            ACCESS_CONTROL_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("spec")
+                .long("spec")
+                .help("path to a TOML tenant spec file")
+                .required(true),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("show the steps that would be performed without performing them")
+                .required(false),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "json"])
+                .help("output format for the report (default: table)")
+                .required(false),
+        );
+    let deprovision_cmd = Command::new("deprovision")
+        .about(color_print::cstr!("<bold><red>DANGER ZONE.</red></bold> Deletes everything that was created for a tenant by 'tenants provision'"))
+        .long_about(color_print::cstr!(
+"<bold><red>DANGER ZONE.</red></bold> Deletes the objects recorded by 'tenants provision' for this tenant,
+including its virtual host and all messages in it, and its users. Objects that existed before
+the tenant was provisioned are not deleted. Use --dry-run to preview what would be deleted."
+        ))
+        .after_help(color_print::cformat!("<bold>Doc guide</bold>: {}", VIRTUAL_HOST_GUIDE_URL))
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .help("tenant name, as specified in the spec file")
+                .required(true),
+        )
+        .arg(
+            Arg::new("approve")
+                .long("approve")
+                .action(ArgAction::SetTrue)
+                .help("this operation is very destructive and requires an explicit approval")
+                .required(false),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("show what would be deleted without performing the actual deletion")
+                .required(false),
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .action(ArgAction::SetTrue)
+                .help("treat partial success (some failures) as a total failure")
+                .required(false),
+        )
+        .arg(
+            Arg::new("fail_fast")
+                .long("fail-fast")
+                .action(ArgAction::SetTrue)
+                .help("stop at the first per-item failure instead of trying every object")
+                .required(false),
+        )
+        .arg(
+            Arg::new("detailed_exit_codes")
+                .long("detailed-exit-codes")
+                .action(ArgAction::SetTrue)
+                .help("opt in to exit code 3 on partial success; off by default for backwards compatibility")
+                .required(false)
+                .conflicts_with("strict"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "json"])
+                .help("output format for the bulk-operation report (default: table)")
+                .required(false),
+        );
+
+    [provision_cmd, deprovision_cmd]
+        .into_iter()
+        .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
+        .collect()
+}
+
 pub fn users_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
     let declare_cmd = Command::new("declare")
         .about("Creates a user")
@@ -3480,6 +4314,53 @@ pub fn users_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command>
                 .help("a list of comma-separated tags")
                 .default_value(""),
         );
+    let audit_cmd = Command::new("audit")
+        .about("Reports unused and risky users, each with a severity and a remediation hint")
+        .long_about(
+"Cross-references users, their permissions, client connections and authentication attempt
+statistics, and reports:
+
+ * users without permissions
+ * users with permissions but no client connections during the observation window
+ * administrators with full ('.*') permissions in many (or all) virtual hosts
+ * users whose passwords are hashed using a weak (legacy) algorithm
+ * nodes that recorded failed authentication attempts
+ * the 'guest' user
+
+Connections are listed every 5 seconds during the observation window (--observation-window).
+By default they are listed once.",
+        )
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            ACCESS_CONTROL_GUIDE_URL,
+            PASSWORD_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("observation_window")
+                .long("observation-window")
+                .help("for how long, in seconds, to observe client connections")
+                .required(false)
+                .default_value("0")
+                .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("broad_permissions_threshold")
+                .long("broad-permissions-threshold")
+                .help("report administrators with full permissions in at least this many virtual hosts")
+                .required(false)
+                .default_value("5")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "json"])
+                .help("output format (default: table)")
+                .required(false),
+        );
     let list_cmd = Command::new("list")
         .long_about("Lists users in the internal database")
         .args(pagination_args());
@@ -3488,7 +4369,14 @@ pub fn users_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command>
         .after_help(color_print::cformat!(
             "<bold>Doc guide</bold>: {}",
             ACCESS_CONTROL_GUIDE_URL
-        ));
+        ))
+        .arg(
+            Arg::new("include_topic")
+                .long("include-topic")
+                .action(ArgAction::SetTrue)
+                .help("also list topic permissions")
+                .required(false),
+        );
     let connections_cmd = Command::new("connections")
         .arg(
             Arg::new("username")
@@ -3531,24 +4419,302 @@ pub fn users_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command>
                 .required(true),
         )
         .arg(idempotently_arg.clone());
+    let delete_multiple_cmd = Command::new("delete_multiple")
+        .about(color_print::cstr!("<bold><red>DANGER ZONE.</red></bold> Deletes multiple users at once using a name matching pattern"))
+        .long_about(color_print::cstr!(
+"<bold><red>DANGER ZONE.</red></bold> Deletes multiple users at once using a name matching pattern.
 
-    [
-        connections_cmd,
-        declare_cmd,
-        delete_cmd,
-        limits_cmd,
-        list_cmd,
-        permissions_cmd,
-    ]
-    .into_iter()
-    .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
-    .collect()
-}
+The currently authenticated user and the last remaining administrator are always skipped,
+even if they match.
 
-pub fn passwords_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
-    let hash_password = Command::new("salt_and_hash")
-        .arg(
-            Arg::new("password")
+Exit codes:
+  0  — every matching user deleted, or none matched
+  3  — partial success: some deleted, some failed (only when --detailed-exit-codes is set)
+  65 — total failure (none deleted), or partial failure when --strict is set"
+        ))
+        .after_help(color_print::cformat!("<bold>Doc guide</bold>: {}", ACCESS_CONTROL_GUIDE_URL))
+        .arg(
+            Arg::new("name_pattern")
+                .long("name-pattern")
+                .help("a regular expression that will be used to match usernames")
+                .required(true),
+        )
+        .arg(
+            Arg::new("tag")
+                .long("tag")
+                .help("only delete users that have this tag")
+                .required(false),
+        )
+        .arg(
+            Arg::new("approve")
+                .long("approve")
+                .action(ArgAction::SetTrue)
+                .help("this operation is very destructive and requires an explicit approval")
+                .required(false),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("show what would be deleted without performing the actual deletion")
+                .required(false),
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .action(ArgAction::SetTrue)
+                .help("treat partial success (some failures) as a total failure")
+                .required(false),
+        )
+        .arg(
+            Arg::new("fail_fast")
+                .long("fail-fast")
+                .action(ArgAction::SetTrue)
+                .help("stop at the first per-item failure instead of trying every match")
+                .required(false),
+        )
+        .arg(
+            Arg::new("detailed_exit_codes")
+                .long("detailed-exit-codes")
+                .action(ArgAction::SetTrue)
+                .help("opt in to exit code 3 on partial success; off by default for backwards compatibility")
+                .required(false)
+                .conflicts_with("strict"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "json"])
+                .help("output format for the bulk-operation report (default: table)")
+                .required(false),
+        )
+        .arg(idempotently_arg.clone());
+
+    let update_cmd = Command::new("update")
+        .about("Updates the tags of a user without changing its credentials")
+        .long_about(
+"Updates the tags of an existing user. Either replaces all tags with --tags, or adds and removes
+individual tags with --add-tag and --remove-tag.
+
+The password hash, its hashing algorithm and user limits are preserved."
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            ACCESS_CONTROL_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .help("username")
+                .required(true),
+        )
+        .arg(
+            Arg::new("tags")
+                .long("tags")
+                .help("a list of comma-separated tags that replaces the current ones")
+                .conflicts_with_all(["add_tag", "remove_tag"]),
+        )
+        .arg(
+            Arg::new("add_tag")
+                .long("add-tag")
+                .help("a tag to add, can be specified multiple times")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("remove_tag")
+                .long("remove-tag")
+                .help("a tag to remove, can be specified multiple times")
+                .action(ArgAction::Append),
+        )
+        .group(
+            ArgGroup::new("tag_changes")
+                .args(["tags", "add_tag", "remove_tag"])
+                .multiple(true)
+                .required(true),
+        );
+
+    let import_cmd = Command::new("import")
+        .about("Imports users and their permissions from a CSV file")
+        .long_about(
+"Imports users and their permissions from a CSV file with a header row.
+
+Supported columns: name, password, password_hash, hashing_algorithm, tags, vhost, configure, write, read.
+Every user needs either a password or a password_hash. Passwords are hashed locally using the
+hashing_algorithm (SHA256 by default) and never sent to the node. Tags are comma-separated
+(enclose the field in double quotes).
+
+A user with permissions in multiple virtual hosts spans multiple rows, one per virtual host.
+Users that already exist are skipped. Users with invalid rows are reported as failed and not imported.
+MD5 (the legacy rabbit_password_hashing_md5 module) is accepted for existing password hashes only.
+
+Exit codes:
+  0  — every user imported or skipped
+  3  — partial success: some users imported, some failed (only when --detailed-exit-codes is set)
+  65 — total failure (no users imported), or partial failure when --strict is set"
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guides</bold>:
+
+ * {}
+ * {}",
+            ACCESS_CONTROL_GUIDE_URL,
+            PASSWORD_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("csv")
+                .long("csv")
+                .value_parser(value_parser!(PathBuf))
+                .help("path to the CSV file")
+                .required(true),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("show what users would be imported without importing them")
+                .required(false),
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .action(ArgAction::SetTrue)
+                .help("treat partial success (some failures) as a total failure")
+                .required(false),
+        )
+        .arg(
+            Arg::new("fail_fast")
+                .long("fail-fast")
+                .action(ArgAction::SetTrue)
+                .help("stop at the first per-item failure instead of trying every user")
+                .required(false),
+        )
+        .arg(
+            Arg::new("detailed_exit_codes")
+                .long("detailed-exit-codes")
+                .action(ArgAction::SetTrue)
+                .help("opt in to exit code 3 on partial success; off by default for backwards compatibility")
+                .required(false)
+                .conflicts_with("strict"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "json"])
+                .help("output format for the bulk-operation report (default: table)")
+                .required(false),
+        );
+
+    let export_cmd = Command::new("export")
+        .about("Exports users and their permissions as CSV")
+        .long_about(
+"Exports users and their permissions as CSV, one row per user and virtual host, in the format
+'users import --csv' accepts. Password hashes are only exported with --include-password-hashes."
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            ACCESS_CONTROL_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("csv")
+                .long("csv")
+                .help("output file path; the standard output stream is used when omitted or '-'")
+                .num_args(0..=1)
+                .default_missing_value("-")
+                .required(true),
+        )
+        .arg(
+            Arg::new("include_password_hashes")
+                .long("include-password-hashes")
+                .action(ArgAction::SetTrue)
+                .help("include password_hash and hashing_algorithm columns")
+                .required(false),
+        );
+
+    let rotate_password_cmd = Command::new("rotate_password")
+        .about("Replaces the password of a user with a randomly generated one")
+        .long_about(
+"Replaces the password of an existing user with a randomly generated one, preserving its tags.
+
+The password is hashed locally, so the new password is never sent to the node. It is printed
+to standard output, or written to a new file with --output-file, exactly once.
+
+When rotating the password of the user rabbitmqadmin is authenticated as, --update-config-file
+also updates the password in the --node section of the configuration file."
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            PASSWORD_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .help("username")
+                .required(true),
+        )
+        .arg(
+            Arg::new("length")
+                .long("length")
+                .value_parser(value_parser!(u16).range(
+                    passwords::MIN_PASSWORD_LENGTH as i64..=passwords::MAX_PASSWORD_LENGTH as i64,
+                ))
+                .default_value("32")
+                .help("length of the generated password")
+                .required(false),
+        )
+        .arg(
+            Arg::new("charset")
+                .long("charset")
+                .value_parser(["alphanumeric", "alphanumeric_with_symbols", "hex"])
+                .default_value("alphanumeric")
+                .help("characters the generated password is made of")
+                .required(false),
+        )
+        .arg(
+            Arg::new("hashing_algorithm")
+                .long("hashing-algorithm")
+                .required(false)
+                .value_parser(value_parser!(HashingAlgorithm))
+                .default_value("SHA256")
+                .help("The hashing algorithm to use: SHA256 or SHA512"),
+        )
+        .arg(
+            Arg::new("output_file")
+                .long("output-file")
+                .value_parser(value_parser!(PathBuf))
+                .help("write the new password to this file instead of standard output; the file must not exist")
+                .required(false),
+        )
+        .arg(
+            Arg::new("update_config_file")
+                .long("update-config-file")
+                .action(ArgAction::SetTrue)
+                .help("update the password in the configuration file section used to connect")
+                .required(false),
+        );
+
+    [
+        audit_cmd,
+        connections_cmd,
+        declare_cmd,
+        delete_cmd,
+        delete_multiple_cmd,
+        export_cmd,
+        import_cmd,
+        limits_cmd,
+        list_cmd,
+        permissions_cmd,
+        rotate_password_cmd,
+        update_cmd,
+    ]
+    .into_iter()
+    .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
+    .collect()
+}
+
+pub fn passwords_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
+    let hash_password = Command::new("salt_and_hash")
+        .arg(
+            Arg::new("password")
                 .required(true)
                 .help("A cleartext password value to hash"),
         )
@@ -3560,8 +4726,91 @@ pub fn passwords_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Comm
                 .default_value("SHA256")
                 .help("The hashing algorithm to use: SHA256 or SHA512"),
         );
+    let verify_cmd = Command::new("verify")
+        .about("Checks a password against a password hash locally")
+        .long_about(
+"Checks a cleartext password against a salted password hash locally, the same way RabbitMQ nodes do it.
+No nodes are contacted.
+
+Exits with code 0 if the password matches the hash and with a non-zero code otherwise."
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            PASSWORD_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("hash")
+                .long("hash")
+                .required(true)
+                .help("a Base64-encoded salted password hash"),
+        )
+        .arg(
+            Arg::new("password")
+                .long("password")
+                .required(true)
+                .help("the cleartext password to check"),
+        )
+        .arg(
+            Arg::new("hashing_algorithm")
+                .long("hashing-algorithm")
+                .required(false)
+                .value_parser(value_parser!(HashingAlgorithm))
+                .default_value("SHA256")
+                .help("The hashing algorithm the hash was produced with: SHA256 or SHA512"),
+        );
+    let generate_cmd = Command::new("generate")
+        .about("Generates random passwords and their salted hashes")
+        .long_about(
+            "Generates random passwords and salts and hashes them locally, so that credentials
+can be provisioned using password hashes without sending cleartext passwords to any nodes.",
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            PASSWORD_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("count")
+                .long("count")
+                .value_parser(value_parser!(u16).range(1..=1000))
+                .default_value("1")
+                .help("how many passwords to generate")
+                .required(false),
+        )
+        .arg(
+            Arg::new("length")
+                .long("length")
+                .value_parser(value_parser!(u16).range(
+                    passwords::MIN_PASSWORD_LENGTH as i64..=passwords::MAX_PASSWORD_LENGTH as i64,
+                ))
+                .default_value("32")
+                .help("length of the generated passwords")
+                .required(false),
+        )
+        .arg(
+            Arg::new("charset")
+                .long("charset")
+                .value_parser(["alphanumeric", "alphanumeric_with_symbols", "hex"])
+                .default_value("alphanumeric")
+                .help("characters the generated passwords are made of")
+                .required(false),
+        )
+        .arg(
+            Arg::new("hashing_algorithm")
+                .long("hashing-algorithm")
+                .required(false)
+                .value_parser(value_parser!(HashingAlgorithm))
+                .default_value("SHA256")
+                .help("The hashing algorithm to use: SHA256 or SHA512"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "json"])
+                .help("output format (default: table)")
+                .required(false),
+        );
 
-    [hash_password]
+    [generate_cmd, hash_password, verify_cmd]
         .into_iter()
         .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
         .collect()
@@ -3580,7 +4829,14 @@ pub fn permissions_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Co
         .after_help(color_print::cformat!(
             "<bold>Doc guide</bold>: {}",
             ACCESS_CONTROL_GUIDE_URL
-        ));
+        ))
+        .arg(
+            Arg::new("include_topic")
+                .long("include-topic")
+                .action(ArgAction::SetTrue)
+                .help("also list topic permissions")
+                .required(false),
+        );
 
     let declare_cmd = Command::new("declare")
         .about("grants permissions to a user")
@@ -3625,7 +4881,246 @@ pub fn permissions_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Co
         )
         .arg(idempotently_arg.clone());
 
-    [list_cmd, declare_cmd, delete_cmd]
+    let check_cmd = Command::new("check")
+        .about("Evaluates whether a user can perform an operation on a resource, and which rule decides it")
+        .long_about(
+"Fetches the permissions (and topic permissions) of a user and evaluates their patterns locally,
+the same way the internal authorization backend does: patterns are unanchored regular expressions,
+and an empty pattern only matches an empty name (the default exchange).
+
+With --matrix, evaluates configure, write and read access to the resource for every user in every virtual host.
+
+Patterns that use features the local regular expression engine does not support, such as lookarounds
+or backreferences, are reported as 'cannot evaluate', and the command exits with code 65."
+        )
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            ACCESS_CONTROL_GUIDE_URL,
+            TOPIC_PERMISSIONS_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("username")
+                .long("username")
+                .alias("user")
+                .help("username")
+                .required_unless_present("matrix"),
+        )
+        .arg(
+            Arg::new("resource_type")
+                .long("resource-type")
+                .value_parser(["queue", "exchange", "topic"])
+                .help("resource type; for topic, --name is the exchange and --routing-key is required")
+                .required(true),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .help("queue or exchange name")
+                .required(true),
+        )
+        .arg(
+            Arg::new("routing_key")
+                .long("routing-key")
+                .help("routing key to check topic permissions for")
+                .required_if_eq("resource_type", "topic"),
+        )
+        .arg(
+            Arg::new("operation")
+                .long("operation")
+                .value_parser(["configure", "write", "read"])
+                .help("operation to check")
+                .required_unless_present("matrix"),
+        )
+        .arg(
+            Arg::new("matrix")
+                .long("matrix")
+                .action(ArgAction::SetTrue)
+                .help("check all operations for all users in all virtual hosts")
+                .conflicts_with_all(["username", "operation"])
+                .required(false),
+        );
+
+    let bulk_args = [
+        Arg::new("vhost_pattern")
+            .long("vhost-pattern")
+            .help("a regular expression that virtual host names must match (default: all virtual hosts)")
+            .required(false),
+        Arg::new("dry_run")
+            .long("dry-run")
+            .action(ArgAction::SetTrue)
+            .help("show what permissions would be declared without declaring them")
+            .required(false),
+        Arg::new("strict")
+            .long("strict")
+            .action(ArgAction::SetTrue)
+            .help("treat partial success (some failures) as a total failure")
+            .required(false),
+        Arg::new("fail_fast")
+            .long("fail-fast")
+            .action(ArgAction::SetTrue)
+            .help("stop at the first per-item failure instead of trying every virtual host")
+            .required(false),
+        Arg::new("detailed_exit_codes")
+            .long("detailed-exit-codes")
+            .action(ArgAction::SetTrue)
+            .help("opt in to exit code 3 on partial success; off by default for backwards compatibility")
+            .required(false)
+            .conflicts_with("strict"),
+        Arg::new("output")
+            .long("output")
+            .value_parser(["table", "json"])
+            .help("output format for the bulk-operation report (default: table)")
+            .required(false),
+    ];
+
+    let copy_cmd = Command::new("copy")
+        .about("Grants a user the same permissions another user has, in every virtual host")
+        .long_about(
+"Grants a user the same configure, write and read permissions another user has, in every virtual host
+(or every virtual host matching --vhost-pattern) the source user has permissions in.
+
+Existing permissions of the target user in those virtual hosts are replaced; virtual hosts
+where the target user already has identical permissions are skipped. Topic permissions are not copied."
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            ACCESS_CONTROL_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("from_user")
+                .long("from-user")
+                .help("the user to copy permissions from")
+                .required(true),
+        )
+        .arg(
+            Arg::new("to_user")
+                .long("to-user")
+                .help("the user to grant permissions to")
+                .required(true),
+        )
+        .args(bulk_args.clone());
+
+    let declare_in_all_cmd = Command::new("declare_in_all")
+        .about("Grants a user the same permissions in all virtual hosts matching a pattern")
+        .long_about(
+            "Grants a user the same configure, write and read permissions in every virtual host
+(or every virtual host matching --vhost-pattern).
+
+Virtual hosts where the user already has identical permissions are skipped.",
+        )
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            ACCESS_CONTROL_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("username")
+                .long("username")
+                .alias("user")
+                .help("username")
+                .required(true),
+        )
+        .arg(
+            Arg::new("configure")
+                .long("configure")
+                .help("name pattern for configuration access")
+                .required(true),
+        )
+        .arg(
+            Arg::new("read")
+                .long("read")
+                .help("name pattern for read access")
+                .required(true),
+        )
+        .arg(
+            Arg::new("write")
+                .long("write")
+                .help("name pattern for write access")
+                .required(true),
+        )
+        .args(bulk_args);
+
+    [
+        list_cmd,
+        declare_cmd,
+        delete_cmd,
+        check_cmd,
+        copy_cmd,
+        declare_in_all_cmd,
+    ]
+    .into_iter()
+    .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
+    .collect()
+}
+
+pub fn topic_permissions_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command> {
+    let idempotently_arg = Arg::new("idempotently")
+        .long("idempotently")
+        .value_parser(value_parser!(bool))
+        .action(ArgAction::SetTrue)
+        .help("do not consider 404 Not Found API responses to be errors")
+        .required(false);
+
+    let list_cmd = Command::new("list")
+        .long_about("Lists topic permissions in all virtual hosts")
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            TOPIC_PERMISSIONS_GUIDE_URL
+        ));
+
+    let list_in_cmd = Command::new("list_in")
+        .long_about("Lists topic permissions in a virtual host")
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            TOPIC_PERMISSIONS_GUIDE_URL
+        ));
+
+    let declare_cmd = Command::new("declare")
+        .about("grants topic permissions on an exchange to a user")
+        .after_help(color_print::cformat!(
+            "<bold>Doc guide</bold>: {}",
+            TOPIC_PERMISSIONS_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("username")
+                .long("username")
+                .alias("user")
+                .help("username")
+                .required(true),
+        )
+        .arg(
+            Arg::new("exchange")
+                .long("exchange")
+                .help("topic exchange name")
+                .required(true),
+        )
+        .arg(
+            Arg::new("write")
+                .long("write")
+                .help("routing key pattern for publishing")
+                .required(true),
+        )
+        .arg(
+            Arg::new("read")
+                .long("read")
+                .help("routing key pattern for consuming (binding)")
+                .required(true),
+        );
+
+    let delete_cmd = Command::new("delete")
+        .about("Revokes all topic permissions of a user in a given virtual host")
+        .arg(
+            Arg::new("username")
+                .long("username")
+                .alias("user")
+                .help("username")
+                .required(true),
+        )
+        .arg(idempotently_arg.clone());
+
+    [list_cmd, list_in_cmd, declare_cmd, delete_cmd]
         .into_iter()
         .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
         .collect()
@@ -4080,6 +5575,118 @@ pub fn shovel_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command
             SHOVEL_TLS_GUIDE_URL
         ));
 
+    let migrate_cluster_cmd = Command::new("migrate_cluster")
+        .about("Moves messages from every (matching) queue to another cluster using one dynamic shovel per queue")
+        .long_about(color_print::cstr!(r#"Helps with blue/green deployments: declares the matching queues on the target cluster (a node alias in the configuration file),
+then declares one AMQP 0-9-1 dynamic shovel per queue on this cluster.
+
+Each shovel is named after its queue with a 'migrate_cluster.' prefix and deletes itself after it has moved the messages
+that were in the queue when it started. Use <bold>--status</bold> to see which queues have been migrated.
+The queues shovels were declared for are recorded in the 'rabbitmqadmin.migrate_cluster' global runtime parameter.
+
+Unless <bold>--destination-uri</bold> is provided, the shovels connect to the target cluster with the credentials
+of its node alias. Shovel URIs, credentials included, are stored in runtime parameters on this cluster.
+
+Exit codes:
+  0  — every shovel declared, or no queues matched
+  3  — partial success: some shovels declared, some failed (only when --detailed-exit-codes is set)
+  65 — total failure (no shovels declared), or partial failure when --strict is set"#))
+        .after_help(color_print::cformat!(
+            r#"<bold>Doc guides</bold>:
+
+ * {}
+ * {}"#,
+            SHOVEL_GUIDE_URL,
+            BLUE_GREEN_UPGRADE_GUIDE_URL
+        ))
+        .arg(
+            Arg::new("to_node")
+                .long("to-node")
+                .help("configuration file node alias of the target cluster")
+                .required_unless_present("status"),
+        )
+        .arg(
+            Arg::new("destination_uri")
+                .long("destination-uri")
+                .help("AMQP 0-9-1 URI of the target cluster the shovels will connect to; by default, built from the target node's host name and HTTP API credentials, which end up stored in the shovels' runtime parameters")
+                .required(false)
+                .conflicts_with("status"),
+        )
+        .arg(
+            Arg::new("status")
+                .long("status")
+                .action(ArgAction::SetTrue)
+                .help("report the state of migration shovels instead of declaring them")
+                .required(false),
+        )
+        .arg(
+            Arg::new("include_vhosts")
+                .long("include-vhosts")
+                .help("only migrate queues in virtual hosts whose names match this regular expression")
+                .required(false),
+        )
+        .arg(
+            Arg::new("exclude_vhosts")
+                .long("exclude-vhosts")
+                .help("do not migrate queues in virtual hosts whose names match this regular expression")
+                .required(false),
+        )
+        .arg(
+            Arg::new("include_names")
+                .long("include-names")
+                .help("only migrate queues whose names match this regular expression")
+                .required(false),
+        )
+        .arg(
+            Arg::new("exclude_names")
+                .long("exclude-names")
+                .help("do not migrate queues whose names match this regular expression")
+                .required(false),
+        )
+        .arg(
+            Arg::new("approve")
+                .long("approve")
+                .action(ArgAction::SetTrue)
+                .help("this operation moves messages between clusters and requires an explicit approval")
+                .required(false),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("show the queues that would be migrated without declaring anything")
+                .required(false),
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .action(ArgAction::SetTrue)
+                .help("treat partial success (some failures) as a total failure")
+                .required(false),
+        )
+        .arg(
+            Arg::new("fail_fast")
+                .long("fail-fast")
+                .action(ArgAction::SetTrue)
+                .help("stop at the first per-queue failure instead of trying every queue")
+                .required(false),
+        )
+        .arg(
+            Arg::new("detailed_exit_codes")
+                .long("detailed-exit-codes")
+                .action(ArgAction::SetTrue)
+                .help("opt in to exit code 3 on partial success; off by default for backwards compatibility")
+                .required(false)
+                .conflicts_with("strict"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "json"])
+                .help("output format for the bulk-operation report (default: table)")
+                .required(false),
+        );
+
     [
         list_all_cmd,
         list_cmd,
@@ -4090,6 +5697,7 @@ pub fn shovel_subcommands(pre_flight_settings: PreFlightSettings) -> Vec<Command
         disable_tls_peer_verification_dest_cmd,
         enable_tls_peer_verification_source_cmd,
         enable_tls_peer_verification_dest_cmd,
+        migrate_cluster_cmd,
     ]
     .into_iter()
     .map(|cmd| cmd.infer_long_args(pre_flight_settings.infer_long_options))
@@ -14,15 +14,35 @@
 #![allow(clippy::result_large_err)]
 
 use crate::arg_helpers::ArgMatchesExt;
-use crate::bulk::{self, BulkMode, BulkReport, ItemAction, SkipReason};
+use crate::audit::{self, AuditInput, Finding, UserAuditInput};
+use crate::bulk::{self, BulkMode, BulkReport, ItemAction, ItemOutcome, SkipReason};
 use crate::config::{
     ConfigPathEntry, NodeConfigEntry, Scheme, SharedSettings, add_node_to_config_file,
     config_file_exists, delete_node_from_config_file, list_all_nodes, update_node_in_config_file,
 };
-use crate::constants::{DEFAULT_BLANKET_POLICY_PRIORITY, DEFAULT_HOST, DEFAULT_VHOST};
+use crate::constants::{
+    ADMINISTRATOR_TAG, DEFAULT_BLANKET_POLICY_PRIORITY, DEFAULT_HOST, DEFAULT_VHOST,
+};
+use crate::definitions::{
+    self, ConflictStrategy, DefinitionChange, DefinitionFilter, DefinitionKind, DefinitionObject,
+    DefinitionsFragment,
+};
 use crate::errors::CommandRunError;
+use crate::health_checks::{self, HealthCheck, HealthCheckClient, HealthCheckReport};
 use crate::output::{BulkPreviewRow, ProgressReporter};
+use crate::passwords::{self, PasswordCharset};
+use crate::permission_check::{
+    self, Operation, PermissionCheck, PermissionMatrixRow, Resource, ResourceType, UserAccess,
+};
 use crate::pre_flight;
+use crate::shovel_migration::{
+    self, MIGRATION_RECORD_PARAMETER_NAME, MIGRATION_SHOVEL_NAME_PREFIX, MigrationRecord,
+    MigrationState, QueueMigration, QueueMigrationStatus,
+};
+use crate::tenants::{self, TenantObject, TenantRecord, TenantStep};
+use crate::transformations::{self, TransformationRules};
+use crate::users::{self, TagChanges, UserRecord};
+use crate::vhost_cloning::{self, CloneSource, CloneStep};
 use clap::ArgMatches;
 use rabbitmq_http_client::blocking_api::Client;
 use rabbitmq_http_client::commons;
@@ -49,11 +69,15 @@ use rabbitmq_http_client::uris::UriBuilder;
 use rabbitmq_http_client::{password_hashing, requests, responses};
 use regex::Regex;
 use serde::de::DeserializeOwned;
-use serde_json::Value;
-use std::collections::HashMap;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
 use tabled::Tabled;
 
 type APIClient = Client<String, String, String>;
@@ -204,6 +228,17 @@ pub fn show_overview(client: APIClient) -> CommandResult<responses::Overview> {
     Ok(client.overview()?)
 }
 
+pub fn show_whoami(client: APIClient) -> CommandResult<responses::CurrentUser> {
+    Ok(client.current_user()?)
+}
+
+/// Fails early if the current user does not have the administrator tag
+/// `action` requires.
+fn require_administrator(client: &APIClient, action: &str) -> CommandResult<()> {
+    let user = client.current_user()?;
+    pre_flight::require_tag(&user, ADMINISTRATOR_TAG, action)
+}
+
 pub fn show_memory_breakdown(
     client: APIClient,
     command_args: &ArgMatches,
@@ -351,30 +386,91 @@ pub fn list_policies_with_conflicting_priorities_in(
 fn filter_policies_with_conflicting_priorities(
     policies: Vec<responses::Policy>,
 ) -> Vec<responses::Policy> {
-    let mut priority_counts: HashMap<(&str, i16), usize> = HashMap::new();
-
-    for pol in &policies {
-        *priority_counts
-            .entry((pol.vhost.as_str(), pol.priority))
-            .or_insert(0) += 1;
-    }
-
-    let dominated: Vec<bool> = policies
-        .iter()
-        .map(|pol| {
-            priority_counts
-                .get(&(pol.vhost.as_str(), pol.priority))
-                .is_some_and(|&count| count > 1)
-        })
-        .collect();
+    let same_priority = audit::policies_with_same_priority(&policies, |pol| {
+        (pol.vhost.as_str(), i64::from(pol.priority))
+    });
 
     policies
         .into_iter()
-        .zip(dominated)
-        .filter_map(|(pol, is_conflicting)| is_conflicting.then_some(pol))
+        .zip(same_priority)
+        .filter_map(|(pol, others)| (!others.is_empty()).then_some(pol))
         .collect()
 }
 
+pub fn audit_topology(client: APIClient, command_args: &ArgMatches) -> CommandResult<Vec<Finding>> {
+    let audits_file = command_args.contains_id("file") || command_args.get_flag("stdin");
+    let mut input = if audits_file {
+        AuditInput::from_definitions(read_and_parse_definitions(command_args)?)
+    } else {
+        let mut input = AuditInput::from_definitions(parse_exported_definitions(
+            &client.export_cluster_wide_definitions()?,
+        )?);
+        input.consumer_counts = Some(
+            client
+                .list_queues()?
+                .into_iter()
+                .map(|q| ((q.vhost, q.name), u64::from(q.consumer_count)))
+                .collect(),
+        );
+        input.vhosts_with_limits = Some(
+            client
+                .list_all_vhost_limits()?
+                .into_iter()
+                .filter(|l| !l.limits.is_empty())
+                .map(|l| l.vhost)
+                .collect(),
+        );
+        input
+    };
+    input.broad_permissions_threshold = command_args.optional_typed_or::<usize>(
+        "broad_permissions_threshold",
+        audit::DEFAULT_BROAD_PERMISSIONS_THRESHOLD,
+    );
+
+    Ok(audit::audit_topology(&input))
+}
+
+/// How often client connections are listed during the `users audit` observation window.
+const CONNECTION_SAMPLING_INTERVAL: Duration = Duration::from_secs(5);
+
+pub fn audit_users(client: APIClient, command_args: &ArgMatches) -> CommandResult<Vec<Finding>> {
+    let observation_window =
+        Duration::from_secs(command_args.optional_typed_or::<u64>("observation_window", 0));
+
+    let mut input = UserAuditInput::from_definitions(parse_exported_definitions(
+        &client.export_cluster_wide_definitions()?,
+    )?);
+    input.broad_permissions_threshold = command_args.optional_typed_or::<usize>(
+        "broad_permissions_threshold",
+        audit::DEFAULT_BROAD_PERMISSIONS_THRESHOLD,
+    );
+
+    // Connections are short-lived for some applications, so they are sampled
+    // repeatedly over the observation window instead of listed once
+    let started = Instant::now();
+    let mut connected_users = HashSet::new();
+    loop {
+        connected_users.extend(client.list_connections()?.into_iter().map(|c| c.username));
+        let elapsed = started.elapsed();
+        if elapsed >= observation_window {
+            break;
+        }
+        thread::sleep(CONNECTION_SAMPLING_INTERVAL.min(observation_window - elapsed));
+    }
+    input.connected_users = Some(connected_users);
+
+    for node in client.list_nodes()? {
+        let failed = client
+            .auth_attempts_statistics(&node.name)?
+            .iter()
+            .map(|stats| stats.failure_count)
+            .sum();
+        input.failed_auth_attempts.insert(node.name, failed);
+    }
+
+    Ok(audit::audit_users(&input))
+}
+
 pub fn list_operator_policies(client: APIClient) -> CommandResult<Vec<responses::Policy>> {
     Ok(client.list_operator_policies()?)
 }
@@ -443,6 +539,149 @@ pub fn list_permissions(client: APIClient) -> CommandResult<Vec<responses::Permi
     Ok(client.list_permissions()?)
 }
 
+/// A regular or topic permission, so that both kinds can be listed in a single table.
+#[derive(Debug, Clone, Tabled)]
+pub struct UserPermission {
+    pub user: String,
+    pub vhost: String,
+    pub kind: String,
+    pub exchange: String,
+    pub configure: String,
+    pub write: String,
+    pub read: String,
+}
+
+impl From<responses::Permissions> for UserPermission {
+    fn from(p: responses::Permissions) -> Self {
+        UserPermission {
+            user: p.user,
+            vhost: p.vhost,
+            kind: "regular".to_owned(),
+            exchange: String::new(),
+            configure: p.configure,
+            write: p.write,
+            read: p.read,
+        }
+    }
+}
+
+impl From<responses::TopicPermission> for UserPermission {
+    fn from(p: responses::TopicPermission) -> Self {
+        UserPermission {
+            user: p.user,
+            vhost: p.vhost,
+            kind: "topic".to_owned(),
+            exchange: p.exchange,
+            configure: String::new(),
+            write: p.write,
+            read: p.read,
+        }
+    }
+}
+
+pub fn list_permissions_including_topic(client: APIClient) -> CommandResult<Vec<UserPermission>> {
+    let mut rows: Vec<UserPermission> = client
+        .list_permissions()?
+        .into_iter()
+        .map(UserPermission::from)
+        .chain(
+            client
+                .list_topic_permissions()?
+                .into_iter()
+                .map(UserPermission::from),
+        )
+        .collect();
+    rows.sort_by(|a, b| {
+        (&a.user, &a.vhost, &a.kind, &a.exchange).cmp(&(&b.user, &b.vhost, &b.kind, &b.exchange))
+    });
+    Ok(rows)
+}
+
+pub fn check_permissions(
+    client: APIClient,
+    vhost: &str,
+    command_args: &ArgMatches,
+) -> CommandResult<Vec<PermissionCheck>> {
+    let user = command_args.str_arg("username");
+    let operation = Operation::from(command_args.str_arg("operation").as_str());
+    let resource = permission_check_resource(command_args);
+
+    let permissions = match client.get_permissions(vhost, user) {
+        Ok(p) => Some(p),
+        Err(err) => match CommandRunError::from(err) {
+            CommandRunError::NotFound => None,
+            err => return Err(err),
+        },
+    };
+    let topic_permissions = if resource.resource_type == ResourceType::Topic {
+        client.list_topic_permissions_in(vhost)?
+    } else {
+        Vec::new()
+    };
+    let access = UserAccess {
+        permissions: permissions.as_ref(),
+        topic_permissions: topic_permissions
+            .iter()
+            .filter(|tp| &tp.user == user)
+            .collect(),
+    };
+
+    let verdict = permission_check::check(user, vhost, &access, &resource, operation);
+    Ok(vec![PermissionCheck {
+        user: user.to_owned(),
+        vhost: vhost.to_owned(),
+        resource: resource.to_string(),
+        operation: operation.to_string(),
+        result: verdict.decision.to_string(),
+        rule: verdict.rule,
+    }])
+}
+
+pub fn permissions_matrix(
+    client: APIClient,
+    command_args: &ArgMatches,
+) -> CommandResult<Vec<PermissionMatrixRow>> {
+    let resource = permission_check_resource(command_args);
+
+    let mut users: Vec<String> = client.list_users()?.into_iter().map(|u| u.name).collect();
+    let mut vhosts: Vec<String> = client.list_vhosts()?.into_iter().map(|v| v.name).collect();
+    users.sort();
+    vhosts.sort();
+    let permissions = client.list_permissions()?;
+    let topic_permissions = if resource.resource_type == ResourceType::Topic {
+        client.list_topic_permissions()?
+    } else {
+        Vec::new()
+    };
+
+    Ok(permission_check::matrix(
+        &users,
+        &vhosts,
+        &permissions,
+        &topic_permissions,
+        &resource,
+    ))
+}
+
+fn permission_check_resource(command_args: &ArgMatches) -> Resource {
+    Resource {
+        resource_type: ResourceType::from(command_args.str_arg("resource_type").as_str()),
+        name: command_args.string_arg("name"),
+        routing_key: command_args.optional_string("routing_key"),
+    }
+}
+
+pub fn list_topic_permissions(client: APIClient) -> CommandResult<Vec<responses::TopicPermission>> {
+    Ok(client.list_topic_permissions()?)
+}
+
+pub fn list_topic_permissions_in(
+    client: APIClient,
+    vhost: &str,
+) -> CommandResult<Vec<responses::TopicPermission>> {
+    Ok(client.list_topic_permissions_in(vhost)?)
+}
+
 pub fn list_all_parameters(client: APIClient) -> CommandResult<Vec<responses::RuntimeParameter>> {
     Ok(client.list_runtime_parameters()?)
 }
@@ -644,6 +883,195 @@ pub fn delete_shovel(
     Ok(client.delete_shovel(vhost, &name, idempotently)?)
 }
 
+impl BulkPreviewRow for QueueMigration {
+    fn preview_name(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Declares the matching queues on the target cluster, then one self-deleting
+/// shovel per queue on the current one.
+pub fn migrate_cluster_with_shovels(
+    client: &APIClient,
+    target: &APIClient,
+    target_base_uri: &str,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<QueueMigration>, CommandRunError> {
+    let approve = command_args.optional_typed_or::<bool>("approve", false);
+    let dry_run = command_args.optional_typed_or::<bool>("dry_run", false);
+    let fail_fast = command_args.optional_typed_or::<bool>("fail_fast", false);
+    let non_interactive_cli = command_args
+        .optional_typed::<bool>("non_interactive")
+        .unwrap_or_else(|| pre_flight::InteractivityMode::from_env().is_non_interactive());
+
+    let target_base_uri = url::Url::parse(target_base_uri).map_err(|_| {
+        CommandRunError::UnsupportedArgumentValue {
+            property: "destination_uri".to_string(),
+        }
+    })?;
+
+    let filter = DefinitionFilter {
+        include_kinds: Some(vec![DefinitionKind::Queues]),
+        ..definition_filter(command_args)?
+    };
+    let mut definitions =
+        export_and_transform_cluster_wide_definitions_as_value(client, vec![], None, filter)?;
+    shovel_migration::retain_vhosts_and_queues(&mut definitions);
+    let migrations = shovel_migration::plan_queue_migrations(&definitions);
+
+    if dry_run {
+        return Ok(BulkReport::dry_run(migrations));
+    }
+
+    if !approve && !pre_flight::is_non_interactive() && !non_interactive_cli {
+        return Err(CommandRunError::FailureDuringExecution {
+            message: "This operation moves messages to the target cluster and requires the --approve flag; use --dry-run to only see the queues that would be migrated".to_string(),
+        });
+    }
+
+    if !migrations.is_empty() {
+        target.import_definitions(definitions)?;
+    }
+
+    let mode = if fail_fast {
+        BulkMode::FailFast
+    } else {
+        BulkMode::ContinueOnError
+    };
+
+    let report = bulk::bulk_op(
+        migrations,
+        mode,
+        |m| m.to_string(),
+        |m| match declare_migration_shovel(client, m, &target_base_uri) {
+            Ok(_) => ItemAction::Ok,
+            Err(e) => ItemAction::Fail(e.to_string()),
+        },
+        prog_rep,
+        "Declaring migration shovels",
+    );
+
+    let declared: Vec<&QueueMigration> = report
+        .items
+        .iter()
+        .zip(&report.results)
+        .filter(|(_, result)| result.outcome == ItemOutcome::Succeeded)
+        .map(|(migration, _)| migration)
+        .collect();
+    if !declared.is_empty() {
+        let mut record = find_migration_record(client)?.unwrap_or_default();
+        record.extend(declared);
+        save_migration_record(client, &record)?;
+    }
+    Ok(report)
+}
+
+fn find_migration_record(client: &APIClient) -> Result<Option<MigrationRecord>, CommandRunError> {
+    match client.get_global_runtime_parameter(MIGRATION_RECORD_PARAMETER_NAME) {
+        Ok(param) => MigrationRecord::from_parameter_value(param.value.0)
+            .map(Some)
+            .map_err(|err| CommandRunError::FailureDuringExecution {
+                message: format!(
+                    "Global runtime parameter '{}' is not a valid cluster migration record: {}",
+                    MIGRATION_RECORD_PARAMETER_NAME, err
+                ),
+            }),
+        Err(err) => match CommandRunError::from(err) {
+            CommandRunError::NotFound => Ok(None),
+            err => Err(err),
+        },
+    }
+}
+
+fn save_migration_record(
+    client: &APIClient,
+    record: &MigrationRecord,
+) -> Result<(), CommandRunError> {
+    let params = requests::GlobalRuntimeParameterDefinition {
+        name: MIGRATION_RECORD_PARAMETER_NAME,
+        value: record.to_parameter_value(),
+    };
+    Ok(client.upsert_global_runtime_parameter(&params)?)
+}
+
+fn declare_migration_shovel(
+    client: &APIClient,
+    migration: &QueueMigration,
+    target_base_uri: &url::Url,
+) -> CommandResult<()> {
+    let name = migration.shovel_name();
+    let source_uri = migration.source_uri();
+    let destination_uri = migration.destination_uri(target_base_uri);
+
+    // Both queues exist by now. Letting the shovel declare them could fail
+    // because of queue type or optional argument mismatches.
+    let params = Amqp091ShovelParams {
+        name: &name,
+        vhost: &migration.vhost,
+        acknowledgement_mode: MessageTransferAcknowledgementMode::WhenConfirmed,
+        reconnect_delay: Some(5),
+        source: Amqp091ShovelSourceParams::predeclared_queue_source(&source_uri, &migration.queue),
+        destination: Amqp091ShovelDestinationParams::predeclared_queue_destination(
+            &destination_uri,
+            &migration.queue,
+        ),
+    };
+    let mut param = RuntimeParameterDefinition::from(params);
+    // Amqp091ShovelParams has no equivalent of this key
+    param
+        .value
+        .insert("src-delete-after".to_owned(), json!("queue-length"));
+    Ok(client.upsert_runtime_parameter(&param)?)
+}
+
+/// Reports the state of migration shovels for every matching queue on the current cluster.
+pub fn shovel_migration_status(
+    client: APIClient,
+    command_args: &ArgMatches,
+) -> CommandResult<Vec<QueueMigrationStatus>> {
+    let filter = definition_filter(command_args)?;
+    let is_kept = |re: &Option<Regex>, s: &str, default: bool| {
+        re.as_ref().map_or(default, |re| re.is_match(s))
+    };
+
+    let shovels: HashMap<(String, String), responses::ShovelState> = client
+        .list_shovels()?
+        .into_iter()
+        .filter(|s| s.name.starts_with(MIGRATION_SHOVEL_NAME_PREFIX))
+        .map(|s| ((s.vhost.unwrap_or_default(), s.name), s.state))
+        .collect();
+    let record = find_migration_record(&client)?.unwrap_or_default();
+
+    let mut statuses: Vec<QueueMigrationStatus> = client
+        .list_queues()?
+        .into_iter()
+        .filter(|q| {
+            is_kept(&filter.include_vhosts, &q.vhost, true)
+                && !is_kept(&filter.exclude_vhosts, &q.vhost, false)
+                && is_kept(&filter.include_names, &q.name, true)
+                && !is_kept(&filter.exclude_names, &q.name, false)
+        })
+        .map(|q| {
+            let shovel = shovel_migration::migration_shovel_name(&q.name);
+            let state = shovels.get(&(q.vhost.clone(), shovel.clone()));
+            QueueMigrationStatus {
+                state: MigrationState::of(
+                    state,
+                    record.contains(&q.vhost, &q.name),
+                    q.message_count,
+                ),
+                vhost: q.vhost,
+                queue: q.name,
+                shovel,
+                messages: q.message_count,
+            }
+        })
+        .collect();
+    statuses.sort_by(|a, b| (&a.vhost, &a.queue).cmp(&(&b.vhost, &b.queue)));
+    Ok(statuses)
+}
+
 //
 // Federation
 //
@@ -914,10 +1342,12 @@ pub fn enable_tls_peer_verification_for_all_destination_uris(
 
 pub fn enable_feature_flag(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
     let name = command_args.string_arg("name");
+    require_administrator(&client, "enable feature flags")?;
     Ok(client.enable_feature_flag(&name)?)
 }
 
 pub fn enable_all_stable_feature_flags(client: APIClient) -> CommandResult<()> {
+    require_administrator(&client, "enable feature flags")?;
     Ok(client.enable_all_stable_feature_flags()?)
 }
 
@@ -1212,6 +1642,7 @@ pub fn delete_multiple_vhosts(
             message: "This operation is destructive and requires the --approve flag".to_string(),
         });
     }
+    require_administrator(&client, "delete virtual hosts")?;
 
     let mode = if fail_fast {
         BulkMode::FailFast
@@ -1248,86 +1679,1148 @@ pub fn delete_multiple_vhosts(
     ))
 }
 
-pub fn delete_user(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
-    let name = command_args.str_arg("name");
-    let idempotently = command_args.optional_typed_or::<bool>("idempotently", false);
-    Ok(client.delete_user(name, idempotently)?)
+impl BulkPreviewRow for CloneStep {
+    fn preview_name(&self) -> String {
+        self.to_string()
+    }
 }
 
-pub fn delete_permissions(
+pub fn clone_vhost(
     client: APIClient,
-    vhost: &str,
     command_args: &ArgMatches,
-) -> CommandResult<()> {
-    let username = command_args.str_arg("username");
-    let idempotently = command_args.optional_typed_or::<bool>("idempotently", false);
-    Ok(client.clear_permissions(vhost, username, idempotently)?)
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<CloneStep>, CommandRunError> {
+    let source = command_args.str_arg("source");
+    let destination = command_args.str_arg("destination");
+    let dry_run = command_args.optional_typed_or::<bool>("dry_run", false);
+
+    let steps = plan_vhost_clone(&client, source, destination, false)?;
+    if dry_run {
+        return Ok(BulkReport::dry_run(steps));
+    }
+    Ok(execute_vhost_clone(
+        &client,
+        steps,
+        prog_rep,
+        "Cloning virtual host",
+    ))
 }
 
-pub fn declare_user(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
-    let name = command_args.str_arg("name");
-    let password = command_args.str_arg("password");
-    let provided_hash = command_args.str_arg("password_hash");
-    let tags = command_args.str_arg("tags");
+pub fn rename_vhost(
+    client: APIClient,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<CloneStep>, CommandRunError> {
+    let source = command_args.str_arg("source");
+    let destination = command_args.str_arg("destination");
+    let keep_source = command_args.optional_typed_or::<bool>("keep_source", false);
+    let approve = command_args.optional_typed_or::<bool>("approve", false);
+    let dry_run = command_args.optional_typed_or::<bool>("dry_run", false);
+    let non_interactive_cli = command_args
+        .optional_typed::<bool>("non_interactive")
+        .unwrap_or_else(|| pre_flight::InteractivityMode::from_env().is_non_interactive());
 
-    let has_password = !password.is_empty();
-    let has_hash = !provided_hash.is_empty();
+    let steps = plan_vhost_clone(&client, source, destination, !keep_source)?;
+    if dry_run {
+        return Ok(BulkReport::dry_run(steps));
+    }
 
-    if !has_password && !has_hash {
-        return Err(CommandRunError::MissingOptions {
-            message: "Please provide either --password or --password-hash".to_string(),
+    if !keep_source && !approve && !pre_flight::is_non_interactive() && !non_interactive_cli {
+        return Err(CommandRunError::FailureDuringExecution {
+            message:
+                "This operation deletes the source virtual host and requires the --approve flag"
+                    .to_string(),
         });
     }
+    Ok(execute_vhost_clone(
+        &client,
+        steps,
+        prog_rep,
+        "Renaming virtual host",
+    ))
+}
 
-    if has_password && has_hash {
+fn plan_vhost_clone(
+    client: &APIClient,
+    source: &str,
+    destination: &str,
+    delete_source: bool,
+) -> Result<Vec<CloneStep>, CommandRunError> {
+    if source == destination {
         return Err(CommandRunError::ConflictingOptions {
-            message: "Please provide either --password or --password-hash, not both".to_string(),
+            message: "--source and --destination must be different virtual hosts".to_string(),
         });
     }
 
-    let password_hash = if provided_hash.is_empty() {
-        let hashing_algo = command_args
-            .get_one::<HashingAlgorithm>("hashing_algorithm")
-            .unwrap();
-        let salt = password_hashing::salt();
-        hashing_algo.salt_and_hash(&salt, password).map_err(|e| {
-            CommandRunError::FailureDuringExecution {
-                message: format!("Password hashing failed: {}", e),
-            }
-        })?
-    } else {
-        provided_hash.to_owned()
-    };
+    match client.get_vhost(destination) {
+        Ok(_) => {
+            return Err(CommandRunError::FailureDuringExecution {
+                message: format!("Virtual host '{}' already exists", destination),
+            });
+        }
+        Err(err) => match CommandRunError::from(err) {
+            CommandRunError::NotFound => {}
+            err => return Err(err),
+        },
+    }
 
-    let params = requests::UserParams {
-        name,
-        password_hash: password_hash.as_str(),
-        tags,
+    let vhost = client.get_vhost(source)?;
+    if delete_source && vhost.protected_from_deletion == Some(true) {
+        return Err(CommandRunError::FailureDuringExecution {
+            message: format!(
+                "Virtual host '{}' is protected from deletion, use --keep-source or disable deletion protection first",
+                source
+            ),
+        });
+    }
+
+    let clone_source = CloneSource {
+        limits: client.list_vhost_limits(source)?,
+        permissions: client.list_permissions_in(source)?,
+        topic_permissions: client.list_topic_permissions_in(source)?,
+        definitions: parse_exported_definitions(&client.export_vhost_definitions(source)?)?,
+        vhost,
     };
-    Ok(client.create_user(&params)?)
+    Ok(vhost_cloning::plan_clone(
+        clone_source,
+        destination,
+        delete_source,
+    ))
 }
 
-pub fn salt_and_hash_password(command_args: &ArgMatches) -> Result<String, HashingError> {
-    let password = command_args.string_arg("password");
-    let hashing_algo = command_args
-        .get_one::<HashingAlgorithm>("hashing_algorithm")
-        .unwrap();
-
-    let salt = password_hashing::salt();
-    let password_hash = hashing_algo.salt_and_hash(&salt, &password)?;
-
-    Ok(password_hash)
+// A failed step leaves the destination virtual host in place for inspection,
+// and the source virtual host is only deleted after everything else succeeded
+fn execute_vhost_clone(
+    client: &APIClient,
+    steps: Vec<CloneStep>,
+    prog_rep: &mut dyn ProgressReporter,
+    op_name: &str,
+) -> BulkReport<CloneStep> {
+    bulk::bulk_op(
+        steps,
+        BulkMode::FailFast,
+        |step| step.to_string(),
+        |step| match execute_clone_step(client, step) {
+            Ok(()) => ItemAction::Ok,
+            Err(err) => ItemAction::Fail(err.to_string()),
+        },
+        prog_rep,
+        op_name,
+    )
 }
 
-pub fn declare_permissions(
-    client: APIClient,
-    vhost: &str,
-    command_args: &ArgMatches,
-) -> CommandResult<()> {
-    let username = command_args.str_arg("username");
-    let configure = command_args.str_arg("configure");
-    let read = command_args.str_arg("read");
-    let write = command_args.str_arg("write");
+fn execute_clone_step(client: &APIClient, step: &CloneStep) -> Result<(), CommandRunError> {
+    match step {
+        CloneStep::CreateVirtualHost {
+            name,
+            description,
+            tags,
+            default_queue_type,
+        } => {
+            let params = requests::VirtualHostParams {
+                name,
+                description: description.as_deref(),
+                tags: (!tags.is_empty()).then(|| tags.iter().map(String::as_str).collect()),
+                default_queue_type: default_queue_type.as_deref().map(Into::<QueueType>::into),
+                tracing: false,
+            };
+            client.create_vhost(&params)?;
+        }
+        CloneStep::ImportDefinitions { vhost, definitions } => {
+            client.import_vhost_definitions(vhost, definitions.clone())?;
+        }
+        CloneStep::GrantPermissions {
+            user,
+            vhost,
+            configure,
+            read,
+            write,
+        } => {
+            client.grant_permissions(&requests::Permissions {
+                user,
+                vhost,
+                configure,
+                read,
+                write,
+            })?;
+        }
+        CloneStep::GrantTopicPermissions {
+            user,
+            vhost,
+            exchange,
+            read,
+            write,
+        } => {
+            client.declare_topic_permissions(&requests::TopicPermissions {
+                user,
+                vhost,
+                exchange,
+                read,
+                write,
+            })?;
+        }
+        CloneStep::SetLimit { vhost, kind, value } => {
+            let limit =
+                EnforcedLimitParams::new(VirtualHostLimitTarget::from(kind.as_str()), *value);
+            client.set_vhost_limit(vhost, limit)?;
+        }
+        CloneStep::DeleteVirtualHost { name } => {
+            client.delete_vhost(name, false)?;
+        }
+    }
+    Ok(())
+}
+
+impl BulkPreviewRow for TenantStep {
+    fn preview_name(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl BulkPreviewRow for TenantObject {
+    fn preview_name(&self) -> String {
+        format!("delete {}", self)
+    }
+}
+
+/// Provisions a tenant from a spec. Objects that already exist are left as they are.
+/// If a step fails, the objects created by this run are removed again.
+pub fn provision_tenant(
+    client: APIClient,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<TenantStep>, CommandRunError> {
+    let path = command_args.str_arg("spec");
+    let dry_run = command_args.optional_typed_or::<bool>("dry_run", false);
+
+    let contents =
+        fs::read_to_string(path).map_err(|err| CommandRunError::FailureDuringExecution {
+            message: format!("Failed to read tenant spec file '{}': {}", path, err),
+        })?;
+    let spec = tenants::parse_spec(&contents).map_err(|message| {
+        CommandRunError::FailureDuringExecution {
+            message: format!("Invalid tenant spec file '{}': {}", path, message),
+        }
+    })?;
+
+    let steps = tenants::plan_provisioning(&spec);
+    if dry_run {
+        return Ok(BulkReport::dry_run(steps));
+    }
+
+    let record =
+        find_tenant_record(&client, &spec.name)?.unwrap_or_else(|| TenantRecord::new(&spec.name));
+    let mut created: Vec<TenantObject> = Vec::new();
+    let mut report = bulk::bulk_op(
+        steps,
+        BulkMode::FailFast,
+        |step| step.to_string(),
+        |step| {
+            let result = match step {
+                TenantStep::SaveRecord { .. } => {
+                    let mut updated = record.clone();
+                    updated.extend(created.iter().cloned());
+                    save_tenant_record(&client, &updated).map(|()| true)
+                }
+                _ => execute_tenant_step(&client, step),
+            };
+            match result {
+                Ok(true) => {
+                    created.extend(step.object());
+                    ItemAction::Ok
+                }
+                Ok(false) => ItemAction::Skip(SkipReason::AlreadyPresent),
+                Err(err) => ItemAction::Fail(err.to_string()),
+            }
+        },
+        prog_rep,
+        "Provisioning tenant",
+    );
+
+    if report.failed_count() > 0 && !created.is_empty() {
+        let rollback = bulk::bulk_op(
+            tenants::plan_removal(&created),
+            BulkMode::ContinueOnError,
+            |object| format!("roll back {}", object),
+            |object| remove_tenant_object(&client, object),
+            prog_rep,
+            "Rolling back tenant provisioning",
+        );
+        report.results.extend(rollback.results);
+    }
+    Ok(report)
+}
+
+/// Removes everything recorded as created for a tenant, then the record itself.
+pub fn deprovision_tenant(
+    client: APIClient,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<TenantObject>, CommandRunError> {
+    let name = command_args.str_arg("name");
+    let approve = command_args.optional_typed_or::<bool>("approve", false);
+    let dry_run = command_args.optional_typed_or::<bool>("dry_run", false);
+    let fail_fast = command_args.optional_typed_or::<bool>("fail_fast", false);
+    let non_interactive_cli = command_args
+        .optional_typed::<bool>("non_interactive")
+        .unwrap_or_else(|| pre_flight::InteractivityMode::from_env().is_non_interactive());
+
+    let record = find_tenant_record(&client, name)?.ok_or_else(|| {
+        CommandRunError::FailureDuringExecution {
+            message: format!("No provisioning record found for tenant '{}'", name),
+        }
+    })?;
+    let objects = tenants::plan_removal(&record.objects);
+    if dry_run {
+        return Ok(BulkReport::dry_run(objects));
+    }
+
+    if !approve && !pre_flight::is_non_interactive() && !non_interactive_cli {
+        return Err(CommandRunError::FailureDuringExecution {
+            message: "This operation deletes the tenant's virtual host, users and all messages and requires the --approve flag".to_string(),
+        });
+    }
+
+    let mode = if fail_fast {
+        BulkMode::FailFast
+    } else {
+        BulkMode::ContinueOnError
+    };
+    let report = bulk::bulk_op(
+        objects,
+        mode,
+        |object| format!("delete {}", object),
+        |object| remove_tenant_object(&client, object),
+        prog_rep,
+        "Deprovisioning tenant",
+    );
+
+    // Keep the record around so that deprovisioning can be retried
+    if report.failed_count() == 0 {
+        client.clear_global_runtime_parameter(&record.parameter_name())?;
+    }
+    Ok(report)
+}
+
+fn find_tenant_record(
+    client: &APIClient,
+    tenant: &str,
+) -> Result<Option<TenantRecord>, CommandRunError> {
+    let name = tenants::record_parameter_name(tenant);
+    match client.get_global_runtime_parameter(&name) {
+        Ok(param) => TenantRecord::from_parameter_value(param.value.0)
+            .map(Some)
+            .map_err(|err| CommandRunError::FailureDuringExecution {
+                message: format!(
+                    "Global runtime parameter '{}' is not a valid tenant provisioning record: {}",
+                    name, err
+                ),
+            }),
+        Err(err) => match CommandRunError::from(err) {
+            CommandRunError::NotFound => Ok(None),
+            err => Err(err),
+        },
+    }
+}
+
+fn save_tenant_record(client: &APIClient, record: &TenantRecord) -> Result<(), CommandRunError> {
+    let name = record.parameter_name();
+    let params = requests::GlobalRuntimeParameterDefinition {
+        name: &name,
+        value: record.to_parameter_value(),
+    };
+    Ok(client.upsert_global_runtime_parameter(&params)?)
+}
+
+// Ok(true) means "found", Ok(false) means "not found"
+fn object_exists<T, E>(result: Result<T, E>) -> Result<bool, CommandRunError>
+where
+    CommandRunError: From<E>,
+{
+    match result {
+        Ok(_) => Ok(true),
+        Err(err) => match CommandRunError::from(err) {
+            CommandRunError::NotFound => Ok(false),
+            err => Err(err),
+        },
+    }
+}
+
+// Returns false when the object already exists and was left untouched
+fn execute_tenant_step(client: &APIClient, step: &TenantStep) -> Result<bool, CommandRunError> {
+    match step {
+        TenantStep::CreateVirtualHost { name, spec } => {
+            if object_exists(client.get_vhost(name))? {
+                return Ok(false);
+            }
+            let params = requests::VirtualHostParams {
+                name,
+                description: spec.description.as_deref(),
+                tags: (!spec.tags.is_empty())
+                    .then(|| spec.tags.iter().map(String::as_str).collect()),
+                default_queue_type: spec
+                    .default_queue_type
+                    .as_deref()
+                    .map(Into::<QueueType>::into),
+                tracing: false,
+            };
+            client.create_vhost(&params)?;
+        }
+        TenantStep::CreateUser(spec) => {
+            if object_exists(client.get_user(&spec.name))? {
+                return Ok(false);
+            }
+            // only definitions carry the hashing algorithm of a password hash
+            let doc = spec
+                .to_definitions()
+                .map_err(|message| CommandRunError::FailureDuringExecution { message })?;
+            client.import_definitions(doc)?;
+        }
+        TenantStep::GrantPermissions { vhost, spec } => {
+            if object_exists(client.get_permissions(vhost, &spec.user))? {
+                return Ok(false);
+            }
+            client.declare_permissions(&requests::Permissions {
+                user: &spec.user,
+                vhost,
+                configure: &spec.configure,
+                read: &spec.read,
+                write: &spec.write,
+            })?;
+        }
+        TenantStep::GrantTopicPermissions { vhost, spec } => {
+            let existing = client.list_topic_permissions_in(vhost)?;
+            if existing
+                .iter()
+                .any(|p| p.user == spec.user && p.exchange == spec.exchange)
+            {
+                return Ok(false);
+            }
+            client.declare_topic_permissions(&requests::TopicPermissions {
+                user: &spec.user,
+                vhost,
+                exchange: &spec.exchange,
+                read: &spec.read,
+                write: &spec.write,
+            })?;
+        }
+        TenantStep::DeclarePolicy { vhost, spec } => {
+            if object_exists(client.get_policy(vhost, &spec.name))? {
+                return Ok(false);
+            }
+            client.declare_policy(&PolicyParams {
+                vhost,
+                name: &spec.name,
+                pattern: &spec.pattern,
+                apply_to: PolicyTarget::from(spec.apply_to.as_str()),
+                priority: spec.priority,
+                definition: spec.definition.clone(),
+            })?;
+        }
+        TenantStep::DeclareExchange { vhost, spec } => {
+            if object_exists(client.get_exchange_info(vhost, &spec.name))? {
+                return Ok(false);
+            }
+            client.declare_exchange(
+                vhost,
+                &requests::ExchangeParams {
+                    name: &spec.name,
+                    exchange_type: ExchangeType::from(spec.exchange_type.as_str()),
+                    durable: spec.durable,
+                    auto_delete: spec.auto_delete,
+                    arguments: (!spec.arguments.is_empty()).then(|| spec.arguments.clone()),
+                },
+            )?;
+        }
+        TenantStep::DeclareQueue { vhost, spec } => {
+            if object_exists(client.get_queue_info(vhost, &spec.name))? {
+                return Ok(false);
+            }
+            let queue_type = QueueType::from(spec.queue_type.as_deref().unwrap_or_default());
+            let arguments = (!spec.arguments.is_empty()).then(|| spec.arguments.clone());
+            client.declare_queue(
+                vhost,
+                &requests::QueueParams::new(
+                    &spec.name,
+                    queue_type,
+                    spec.durable,
+                    spec.auto_delete,
+                    arguments,
+                ),
+            )?;
+        }
+        TenantStep::DeclareBinding { vhost, spec } => {
+            let is_queue = spec.destination_type == "queue";
+            let existing = if is_queue {
+                client.list_queue_bindings(vhost, &spec.destination)?
+            } else {
+                client.list_exchange_bindings_with_destination(vhost, &spec.destination)?
+            };
+            if existing
+                .iter()
+                .any(|b| b.source == spec.source && b.routing_key == spec.routing_key)
+            {
+                return Ok(false);
+            }
+            let arguments = (!spec.arguments.is_empty()).then(|| spec.arguments.clone());
+            if is_queue {
+                client.bind_queue(
+                    vhost,
+                    &spec.destination,
+                    &spec.source,
+                    Some(&spec.routing_key),
+                    arguments,
+                )?;
+            } else {
+                client.bind_exchange(
+                    vhost,
+                    &spec.destination,
+                    &spec.source,
+                    Some(&spec.routing_key),
+                    arguments,
+                )?;
+            }
+        }
+        TenantStep::SetLimit { vhost, kind, value } => {
+            let limits = client.list_vhost_limits(vhost)?;
+            if limits.iter().any(|l| l.limits.contains_key(kind)) {
+                return Ok(false);
+            }
+            let limit =
+                EnforcedLimitParams::new(VirtualHostLimitTarget::from(kind.as_str()), *value);
+            client.set_vhost_limit(vhost, limit)?;
+        }
+        // The record is saved by the caller, which knows what has been created
+        TenantStep::SaveRecord { .. } => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn remove_tenant_object(client: &APIClient, object: &TenantObject) -> ItemAction {
+    let result: Result<(), CommandRunError> = match object {
+        TenantObject::VirtualHost { name } => client.delete_vhost(name, false).map_err(Into::into),
+        TenantObject::User { name } => client.delete_user(name, false).map_err(Into::into),
+        TenantObject::Permissions { vhost, user } => client
+            .clear_permissions(vhost, user, false)
+            .map_err(Into::into),
+        TenantObject::TopicPermissions { vhost, user } => client
+            .clear_topic_permissions(vhost, user, false)
+            .map_err(Into::into),
+        TenantObject::Limit { vhost, kind } => client
+            .clear_vhost_limit(vhost, VirtualHostLimitTarget::from(kind.as_str()))
+            .map_err(Into::into),
+        TenantObject::Policy { vhost, name } => {
+            client.delete_policy(vhost, name, false).map_err(Into::into)
+        }
+        TenantObject::Exchange { vhost, name } => client
+            .delete_exchange(vhost, name, false)
+            .map_err(Into::into),
+        TenantObject::Queue { vhost, name } => {
+            client.delete_queue(vhost, name, false).map_err(Into::into)
+        }
+        TenantObject::Binding {
+            vhost,
+            source,
+            destination,
+            destination_type,
+            routing_key,
+            arguments,
+        } => client
+            .delete_binding(
+                &BindingDeletionParams {
+                    virtual_host: vhost,
+                    source,
+                    destination,
+                    destination_type: BindingDestinationType::from(destination_type.as_str()),
+                    routing_key,
+                    arguments: (!arguments.is_empty()).then(|| arguments.clone()),
+                },
+                false,
+            )
+            .map_err(Into::into),
+    };
+    match result {
+        Ok(()) => ItemAction::Ok,
+        Err(CommandRunError::NotFound) => ItemAction::Skip(SkipReason::AlreadyAbsent),
+        Err(err) => ItemAction::Fail(err.to_string()),
+    }
+}
+
+pub fn delete_user(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
+    let name = command_args.str_arg("name");
+    let idempotently = command_args.optional_typed_or::<bool>("idempotently", false);
+    Ok(client.delete_user(name, idempotently)?)
+}
+
+impl BulkPreviewRow for responses::User {
+    fn preview_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+pub fn delete_multiple_users(
+    client: APIClient,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<responses::User>, CommandRunError> {
+    let name_pattern = command_args.str_arg("name_pattern");
+    let tag = command_args.optional_string("tag");
+    let approve = command_args.optional_typed_or::<bool>("approve", false);
+    let dry_run = command_args.optional_typed_or::<bool>("dry_run", false);
+    let idempotently = command_args.optional_typed_or::<bool>("idempotently", false);
+    let fail_fast = command_args.optional_typed_or::<bool>("fail_fast", false);
+    let non_interactive_cli = command_args
+        .optional_typed::<bool>("non_interactive")
+        .unwrap_or_else(|| pre_flight::InteractivityMode::from_env().is_non_interactive());
+
+    let regex =
+        Regex::new(name_pattern).map_err(|_| CommandRunError::UnsupportedArgumentValue {
+            property: "name_pattern".to_string(),
+        })?;
+
+    let current_user = client.current_user()?;
+    let all_users = client.list_users()?;
+    let administrators = all_users
+        .iter()
+        .filter(|u| u.tags.contains(ADMINISTRATOR_TAG))
+        .count();
+    let mut matching: Vec<responses::User> = all_users
+        .into_iter()
+        .filter(|u| regex.is_match(&u.name))
+        .filter(|u| tag.as_ref().is_none_or(|t| u.tags.contains(t)))
+        .collect();
+    // Deterministic ordering: dry-run preview and live execution
+    // process items in the same order, making both reproducible.
+    matching.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let candidates: Vec<(&str, bool)> = matching
+        .iter()
+        .map(|u| (u.name.as_str(), u.tags.contains(ADMINISTRATOR_TAG)))
+        .collect();
+    let protection: HashMap<String, String> = candidates
+        .iter()
+        .zip(users::deletion_protection(
+            &candidates,
+            administrators,
+            &current_user.name,
+        ))
+        .filter_map(|((name, _), reason)| reason.map(|r| (name.to_string(), r)))
+        .collect();
+
+    if dry_run {
+        // Protected users are excluded from the dry-run preview;
+        // the live path below skips them via Skip(Protected).
+        let preview: Vec<_> = matching
+            .into_iter()
+            .filter(|u| !protection.contains_key(&u.name))
+            .collect();
+        return Ok(BulkReport::dry_run(preview));
+    }
+
+    if !approve && !pre_flight::is_non_interactive() && !non_interactive_cli {
+        return Err(CommandRunError::FailureDuringExecution {
+            message: "This operation is destructive and requires the --approve flag".to_string(),
+        });
+    }
+    pre_flight::require_tag(&current_user, ADMINISTRATOR_TAG, "delete users")?;
+
+    let mode = if fail_fast {
+        BulkMode::FailFast
+    } else {
+        BulkMode::ContinueOnError
+    };
+
+    Ok(bulk::bulk_op(
+        matching,
+        mode,
+        |u| u.name.clone(),
+        |u| {
+            if let Some(reason) = protection.get(&u.name) {
+                return ItemAction::Skip(SkipReason::Protected {
+                    reason: reason.clone(),
+                });
+            }
+            match client.delete_user(&u.name, idempotently) {
+                Ok(_) => ItemAction::Ok,
+                Err(e) => {
+                    let err = CommandRunError::from(e);
+                    if idempotently && matches!(err, CommandRunError::NotFound) {
+                        ItemAction::Skip(SkipReason::AlreadyAbsent)
+                    } else {
+                        ItemAction::Fail(err.to_string())
+                    }
+                }
+            }
+        },
+        prog_rep,
+        "Deleting users",
+    ))
+}
+
+pub fn delete_permissions(
+    client: APIClient,
+    vhost: &str,
+    command_args: &ArgMatches,
+) -> CommandResult<()> {
+    let username = command_args.str_arg("username");
+    let idempotently = command_args.optional_typed_or::<bool>("idempotently", false);
+    Ok(client.clear_permissions(vhost, username, idempotently)?)
+}
+
+/// A set of configure, write and read patterns to be granted to a user
+/// in a virtual host by a bulk permissions operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionGrant {
+    pub user: String,
+    pub vhost: String,
+    pub configure: String,
+    pub write: String,
+    pub read: String,
+}
+
+impl PermissionGrant {
+    fn is_granted_by(&self, existing: &responses::Permissions) -> bool {
+        self.configure == existing.configure
+            && self.write == existing.write
+            && self.read == existing.read
+    }
+}
+
+impl fmt::Display for PermissionGrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "user '{}' in virtual host '{}' (configure: '{}', write: '{}', read: '{}')",
+            self.user, self.vhost, self.configure, self.write, self.read
+        )
+    }
+}
+
+impl BulkPreviewRow for PermissionGrant {
+    fn preview_name(&self) -> String {
+        self.to_string()
+    }
+}
+
+fn optional_vhost_regex(command_args: &ArgMatches) -> CommandResult<Option<Regex>> {
+    command_args
+        .optional_string("vhost_pattern")
+        .map(|pattern| {
+            Regex::new(&pattern).map_err(|_| CommandRunError::UnsupportedArgumentValue {
+                property: "vhost_pattern".to_string(),
+            })
+        })
+        .transpose()
+}
+
+/// Declares every grant, skipping those the user already has. With `--dry-run`,
+/// only previews them.
+fn apply_permission_grants(
+    client: &APIClient,
+    mut grants: Vec<PermissionGrant>,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+    op_name: &str,
+) -> Result<BulkReport<PermissionGrant>, CommandRunError> {
+    let dry_run = command_args.optional_typed_or::<bool>("dry_run", false);
+    let fail_fast = command_args.optional_typed_or::<bool>("fail_fast", false);
+
+    grants.sort_by(|a, b| a.vhost.cmp(&b.vhost));
+    if dry_run {
+        return Ok(BulkReport::dry_run(grants));
+    }
+
+    let mode = if fail_fast {
+        BulkMode::FailFast
+    } else {
+        BulkMode::ContinueOnError
+    };
+
+    Ok(bulk::bulk_op(
+        grants,
+        mode,
+        |g| g.to_string(),
+        |g| {
+            if let Ok(existing) = client.get_permissions(&g.vhost, &g.user)
+                && g.is_granted_by(&existing)
+            {
+                return ItemAction::Skip(SkipReason::AlreadyPresent);
+            }
+            let params = requests::Permissions {
+                user: &g.user,
+                vhost: &g.vhost,
+                configure: &g.configure,
+                read: &g.read,
+                write: &g.write,
+            };
+            match client.declare_permissions(&params) {
+                Ok(_) => ItemAction::Ok,
+                Err(e) => ItemAction::Fail(CommandRunError::from(e).to_string()),
+            }
+        },
+        prog_rep,
+        op_name,
+    ))
+}
+
+pub fn copy_permissions(
+    client: APIClient,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<PermissionGrant>, CommandRunError> {
+    let from_user = command_args.str_arg("from_user");
+    let to_user = command_args.str_arg("to_user");
+    let vhost_regex = optional_vhost_regex(command_args)?;
+
+    if from_user == to_user {
+        return Err(CommandRunError::ConflictingOptions {
+            message: "--from-user and --to-user must be different users".to_string(),
+        });
+    }
+    // Fail early with a clear error instead of a failure per virtual host
+    client.get_user(to_user)?;
+
+    let grants: Vec<PermissionGrant> = client
+        .list_permissions_of(from_user)?
+        .into_iter()
+        .filter(|p| vhost_regex.as_ref().is_none_or(|r| r.is_match(&p.vhost)))
+        .map(|p| PermissionGrant {
+            user: to_user.to_string(),
+            vhost: p.vhost,
+            configure: p.configure,
+            write: p.write,
+            read: p.read,
+        })
+        .collect();
+
+    apply_permission_grants(
+        &client,
+        grants,
+        command_args,
+        prog_rep,
+        "Copying permissions",
+    )
+}
+
+pub fn declare_permissions_in_all(
+    client: APIClient,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<PermissionGrant>, CommandRunError> {
+    let username = command_args.str_arg("username");
+    let configure = command_args.str_arg("configure");
+    let write = command_args.str_arg("write");
+    let read = command_args.str_arg("read");
+    let vhost_regex = optional_vhost_regex(command_args)?;
+
+    let grants: Vec<PermissionGrant> = client
+        .list_vhosts()?
+        .into_iter()
+        .filter(|v| vhost_regex.as_ref().is_none_or(|r| r.is_match(&v.name)))
+        .map(|v| PermissionGrant {
+            user: username.to_string(),
+            vhost: v.name,
+            configure: configure.to_string(),
+            write: write.to_string(),
+            read: read.to_string(),
+        })
+        .collect();
+
+    apply_permission_grants(
+        &client,
+        grants,
+        command_args,
+        prog_rep,
+        "Declaring permissions",
+    )
+}
+
+pub fn declare_user(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
+    let name = command_args.str_arg("name");
+    let password = command_args.str_arg("password");
+    let provided_hash = command_args.str_arg("password_hash");
+    let tags = command_args.str_arg("tags");
+
+    let has_password = !password.is_empty();
+    let has_hash = !provided_hash.is_empty();
+
+    if !has_password && !has_hash {
+        return Err(CommandRunError::MissingOptions {
+            message: "Please provide either --password or --password-hash".to_string(),
+        });
+    }
+
+    if has_password && has_hash {
+        return Err(CommandRunError::ConflictingOptions {
+            message: "Please provide either --password or --password-hash, not both".to_string(),
+        });
+    }
+
+    let password_hash = if provided_hash.is_empty() {
+        let hashing_algo = command_args
+            .get_one::<HashingAlgorithm>("hashing_algorithm")
+            .unwrap();
+        let salt = password_hashing::salt();
+        hashing_algo.salt_and_hash(&salt, password).map_err(|e| {
+            CommandRunError::FailureDuringExecution {
+                message: format!("Password hashing failed: {}", e),
+            }
+        })?
+    } else {
+        provided_hash.to_owned()
+    };
+
+    let params = requests::UserParams {
+        name,
+        password_hash: password_hash.as_str(),
+        tags,
+    };
+    Ok(client.create_user(&params)?)
+}
+
+/// Changes the tags of an existing user. The user is re-imported from its
+/// exported definition, which carries the password hash together with its hashing
+/// algorithm, so credentials (and limits) are left as they are.
+pub fn update_user_tags(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
+    let name = command_args.str_arg("name");
+    let changes = TagChanges {
+        replace: command_args
+            .get_one::<String>("tags")
+            .map(|s| users::parse_tags(s)),
+        add: command_args
+            .get_many::<String>("add_tag")
+            .map(|vals| vals.cloned().collect())
+            .unwrap_or_default(),
+        remove: command_args
+            .get_many::<String>("remove_tag")
+            .map(|vals| vals.cloned().collect())
+            .unwrap_or_default(),
+    };
+
+    let defs = parse_exported_definitions(&client.export_cluster_wide_definitions()?)?;
+    let user = users::find_user(&defs, name).ok_or(CommandRunError::NotFound)?;
+    let current = users::tags_of(user);
+    let updated = changes.apply(&current);
+    if updated == current {
+        return Ok(());
+    }
+
+    let doc = json!({ "users": [users::with_tags(user, &updated)] });
+    Ok(client.import_definitions(doc)?)
+}
+
+impl BulkPreviewRow for UserRecord {
+    fn preview_name(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Imports users and their permissions from a CSV file. Users that already
+/// exist are skipped. Each user is imported as a definitions document, which is
+/// the only way to pass the hashing algorithm of a pre-computed password hash.
+pub fn import_users_from_csv(
+    client: APIClient,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<UserRecord>, CommandRunError> {
+    let path = command_args.get_one::<PathBuf>("csv").ok_or_else(|| {
+        CommandRunError::MissingRequiredArgument {
+            name: "csv".to_string(),
+        }
+    })?;
+    let dry_run = command_args.optional_typed_or::<bool>("dry_run", false);
+    let fail_fast = command_args.optional_typed_or::<bool>("fail_fast", false);
+
+    let input = fs::read_to_string(path)?;
+    let records = users::users_from_csv(&input).map_err(|message| {
+        CommandRunError::FailureDuringExecution {
+            message: format!("Could not parse '{}': {}", path.display(), message),
+        }
+    })?;
+
+    if dry_run {
+        return Ok(BulkReport::dry_run(records));
+    }
+    require_administrator(&client, "import users")?;
+
+    let existing: HashSet<String> = client.list_users()?.into_iter().map(|u| u.name).collect();
+    let mode = if fail_fast {
+        BulkMode::FailFast
+    } else {
+        BulkMode::ContinueOnError
+    };
+
+    Ok(bulk::bulk_op(
+        records,
+        mode,
+        UserRecord::item_name,
+        |u| {
+            if !u.errors.is_empty() {
+                return ItemAction::Fail(u.errors.join("; "));
+            }
+            if existing.contains(&u.name) {
+                return ItemAction::Skip(SkipReason::AlreadyPresent);
+            }
+            let doc = match u.to_definitions() {
+                Ok(doc) => doc,
+                Err(e) => return ItemAction::Fail(e),
+            };
+            match client.import_definitions(doc) {
+                Ok(_) => ItemAction::Ok,
+                Err(e) => ItemAction::Fail(CommandRunError::from(e).to_string()),
+            }
+        },
+        prog_rep,
+        "Importing users",
+    ))
+}
+
+pub fn export_users_to_csv(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
+    let target = command_args.str_arg("csv");
+    let include_password_hashes = command_args.get_flag("include_password_hashes");
+
+    let defs = parse_exported_definitions(&client.export_cluster_wide_definitions()?)?;
+    let csv = users::users_to_csv(&defs, include_password_hashes);
+    if target == "-" {
+        print!("{}", csv);
+    } else {
+        fs::write(target, csv)?;
+    }
+    Ok(())
+}
+
+/// Replaces the password of an existing user with a randomly generated one,
+/// preserving its tags. Returns the new password.
+pub fn rotate_user_password(
+    client: &APIClient,
+    command_args: &ArgMatches,
+) -> CommandResult<String> {
+    let name = command_args.str_arg("name");
+    let length = command_args
+        .optional_typed::<u16>("length")
+        .unwrap_or(passwords::DEFAULT_PASSWORD_LENGTH);
+    let charset = command_args
+        .get_one::<String>("charset")
+        .map(|s| PasswordCharset::from(s.as_str()))
+        .unwrap_or_default();
+    let hashing_algo = command_args
+        .get_one::<HashingAlgorithm>("hashing_algorithm")
+        .unwrap();
+
+    // Only definitions carry the hashing algorithm, a password hash passed any other way
+    // is interpreted using the node's default algorithm
+    let defs = parse_exported_definitions(&client.export_cluster_wide_definitions()?)?;
+    let user = users::find_user(&defs, name).ok_or(CommandRunError::NotFound)?;
+    let password = passwords::generate(length as usize, charset);
+    let salt = password_hashing::salt();
+    let password_hash = hashing_algo.salt_and_hash(&salt, &password).map_err(|e| {
+        CommandRunError::FailureDuringExecution {
+            message: format!("Password hashing failed: {}", e),
+        }
+    })?;
+
+    let updated = users::with_password_hash(user, &password_hash, users::module_of(hashing_algo));
+    client.import_definitions(json!({ "users": [updated] }))?;
+
+    Ok(password)
+}
+
+/// Writes a rotated password to a new file only its owner can read.
+/// Existing files are never overwritten.
+pub fn write_rotated_password(path: &Path, password: &str) -> CommandResult<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    writeln!(file, "{}", password)?;
+    Ok(())
+}
+
+pub fn update_password_in_config_file(
+    config_path: &Path,
+    node_name: &str,
+    password: &str,
+) -> CommandResult<()> {
+    let settings = SharedSettings {
+        password: Some(password.to_owned()),
+        ..Default::default()
+    };
+    update_node_in_config_file(config_path, node_name, &settings, false).map_err(|e| {
+        CommandRunError::FailureDuringExecution {
+            message: format!(
+                "The password was rotated but the configuration file could not be updated: {}",
+                e
+            ),
+        }
+    })
+}
+
+pub fn salt_and_hash_password(command_args: &ArgMatches) -> Result<String, HashingError> {
+    let password = command_args.string_arg("password");
+    let hashing_algo = command_args
+        .get_one::<HashingAlgorithm>("hashing_algorithm")
+        .unwrap();
+
+    let salt = password_hashing::salt();
+    let password_hash = hashing_algo.salt_and_hash(&salt, &password)?;
+
+    Ok(password_hash)
+}
+
+/// Checks a password against a password hash locally, without contacting any nodes.
+pub fn verify_password_hash(command_args: &ArgMatches) -> Result<String, CommandRunError> {
+    let password_hash = command_args.str_arg("hash");
+    let password = command_args.str_arg("password");
+    let hashing_algo = command_args
+        .get_one::<HashingAlgorithm>("hashing_algorithm")
+        .unwrap();
+
+    let matches = passwords::verify(password_hash, password, hashing_algo).map_err(|message| {
+        CommandRunError::InvalidArgumentValue {
+            name: "hash".to_string(),
+            message,
+        }
+    })?;
+    if matches {
+        Ok("the password matches the hash".to_string())
+    } else {
+        Err(CommandRunError::FailureDuringExecution {
+            message: "the password does not match the hash".to_string(),
+        })
+    }
+}
+
+pub fn generate_passwords(command_args: &ArgMatches) -> Vec<passwords::GeneratedPassword> {
+    let count = command_args.optional_typed_or::<u16>("count", 1);
+    let length = command_args
+        .optional_typed::<u16>("length")
+        .unwrap_or(passwords::DEFAULT_PASSWORD_LENGTH);
+    let charset = command_args
+        .get_one::<String>("charset")
+        .map(|s| PasswordCharset::from(s.as_str()))
+        .unwrap_or_default();
+    let hashing_algo = command_args
+        .get_one::<HashingAlgorithm>("hashing_algorithm")
+        .unwrap();
+
+    (0..count)
+        .map(|_| passwords::generate_with_hash(length as usize, charset, hashing_algo))
+        .collect()
+}
+
+pub fn declare_permissions(
+    client: APIClient,
+    vhost: &str,
+    command_args: &ArgMatches,
+) -> CommandResult<()> {
+    let username = command_args.str_arg("username");
+    let configure = command_args.str_arg("configure");
+    let read = command_args.str_arg("read");
+    let write = command_args.str_arg("write");
 
     let params = requests::Permissions {
         user: username,
@@ -1340,6 +2833,37 @@ pub fn declare_permissions(
     Ok(client.declare_permissions(&params)?)
 }
 
+pub fn declare_topic_permissions(
+    client: APIClient,
+    vhost: &str,
+    command_args: &ArgMatches,
+) -> CommandResult<()> {
+    let username = command_args.str_arg("username");
+    let exchange = command_args.str_arg("exchange");
+    let read = command_args.str_arg("read");
+    let write = command_args.str_arg("write");
+
+    let params = requests::TopicPermissions {
+        user: username,
+        vhost,
+        exchange,
+        read,
+        write,
+    };
+
+    Ok(client.declare_topic_permissions(&params)?)
+}
+
+pub fn delete_topic_permissions(
+    client: APIClient,
+    vhost: &str,
+    command_args: &ArgMatches,
+) -> CommandResult<()> {
+    let username = command_args.str_arg("username");
+    let idempotently = command_args.optional_typed_or::<bool>("idempotently", false);
+    Ok(client.clear_topic_permissions(vhost, username, idempotently)?)
+}
+
 pub fn declare_queue(
     client: APIClient,
     vhost: &str,
@@ -1976,23 +3500,244 @@ pub fn health_check_node_is_quorum_critical(client: APIClient) -> CommandResult<
     Ok(client.health_check_if_node_is_quorum_critical()?)
 }
 
-pub fn health_check_port_listener(
-    client: APIClient,
-    command_args: &ArgMatches,
-) -> CommandResult<()> {
-    let port = command_args.get_one::<u16>("port").cloned().unwrap();
-    Ok(client.health_check_port_listener(port)?)
+pub fn health_check_port_listener(
+    client: APIClient,
+    command_args: &ArgMatches,
+) -> CommandResult<()> {
+    let port = command_args.get_one::<u16>("port").cloned().unwrap();
+    Ok(client.health_check_port_listener(port)?)
+}
+
+pub fn health_check_protocol_listener(
+    client: APIClient,
+    command_args: &ArgMatches,
+) -> CommandResult<()> {
+    let proto = command_args
+        .get_one::<SupportedProtocol>("protocol")
+        .cloned()
+        .unwrap();
+    Ok(client.health_check_protocol_listener(proto)?)
+}
+
+pub fn health_check_deprecated_features_in_use(client: APIClient) -> CommandResult<()> {
+    check_deprecated_features_in_use(&client)
+}
+
+pub fn health_check_certificate_expiration(
+    client: &HealthCheckClient,
+    command_args: &ArgMatches,
+) -> CommandResult<()> {
+    let within = command_args.get_one::<u32>("within").copied().unwrap();
+    let unit = command_args.str_arg("unit");
+    client.certificate_expiration(within, unit)
+}
+
+pub fn health_check_virtual_hosts(client: &HealthCheckClient) -> CommandResult<()> {
+    client.virtual_hosts()
+}
+
+pub fn health_check_ready_to_serve_clients(client: &HealthCheckClient) -> CommandResult<()> {
+    client.ready_to_serve_clients()
+}
+
+pub fn health_check_is_in_service(client: &HealthCheckClient) -> CommandResult<()> {
+    client.is_in_service()
+}
+
+pub fn health_check_queue_depth(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
+    let max = command_args.get_one::<u64>("max").copied().unwrap();
+    let name_pattern = optional_regex_arg(command_args, "name_pattern")?;
+    check_queue_threshold(&client, HealthCheck::QueueDepth, |queues| {
+        health_checks::check_queue_depth(queues, max, name_pattern.as_ref())
+    })
+}
+
+pub fn health_check_consumers(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
+    let min = command_args.get_one::<u64>("min").copied().unwrap();
+    let name_pattern = optional_regex_arg(command_args, "name_pattern")?;
+    check_queue_threshold(&client, HealthCheck::Consumers, |queues| {
+        health_checks::check_consumers(queues, min, name_pattern.as_ref())
+    })
+}
+
+pub fn health_check_unacknowledged_messages(
+    client: APIClient,
+    command_args: &ArgMatches,
+) -> CommandResult<()> {
+    let max = command_args.get_one::<u64>("max").copied().unwrap();
+    let name_pattern = optional_regex_arg(command_args, "name_pattern")?;
+    check_queue_threshold(&client, HealthCheck::Unacked, |queues| {
+        health_checks::check_unacknowledged_messages(queues, max, name_pattern.as_ref())
+    })
+}
+
+pub fn health_check_connections(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
+    let max_per_user = command_args
+        .get_one::<u64>("max_per_user")
+        .copied()
+        .unwrap();
+    check_connections_threshold(&client, max_per_user)
+}
+
+fn check_queue_threshold<F>(client: &APIClient, check: HealthCheck, run: F) -> CommandResult<()>
+where
+    F: FnOnce(&[responses::QueueInfo]) -> Result<(), health_checks::ThresholdCheckFailure>,
+{
+    let queues = client.list_queues()?;
+    run(&queues).map_err(|failure| failure.into_error(check))
+}
+
+fn check_connections_threshold(client: &APIClient, max_per_user: u64) -> CommandResult<()> {
+    let connections = client.list_connections()?;
+    health_checks::check_connections_per_user(&connections, max_per_user)
+        .map_err(|failure| failure.into_error(HealthCheck::Connections))
+}
+
+/// Runs the health checks selected with `--checks` (by default, all checks whose
+/// arguments were provided) and reports the outcome of each.
+pub fn health_check_all(
+    client: APIClient,
+    health_check_client: &HealthCheckClient,
+    command_args: &ArgMatches,
+) -> CommandResult<HealthCheckReport> {
+    let port = command_args.get_one::<u16>("port").copied();
+    let protocol = command_args
+        .get_one::<SupportedProtocol>("protocol")
+        .cloned();
+    let within = command_args.get_one::<u32>("within").copied();
+    let unit = command_args.str_arg("unit");
+    let max_queue_depth = command_args.get_one::<u64>("max_queue_depth").copied();
+    let min_consumers = command_args.get_one::<u64>("min_consumers").copied();
+    let max_unacked = command_args.get_one::<u64>("max_unacked").copied();
+    let max_connections_per_user = command_args
+        .get_one::<u64>("max_connections_per_user")
+        .copied();
+    let name_pattern = optional_regex_arg(command_args, "name_pattern")?;
+    let continue_on_failure = command_args.get_flag("continue_on_failure");
+
+    let checks: Vec<HealthCheck> = match command_args.get_many::<String>("checks") {
+        Some(names) => names
+            .map(|name| name.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|message| CommandRunError::InvalidArgumentValue {
+                name: "checks".to_string(),
+                message,
+            })?,
+        None => HealthCheck::ALL
+            .into_iter()
+            .filter(|check| match check {
+                HealthCheck::PortListener => port.is_some(),
+                HealthCheck::ProtocolListener => protocol.is_some(),
+                HealthCheck::CertificateExpiration => within.is_some(),
+                HealthCheck::QueueDepth => max_queue_depth.is_some(),
+                HealthCheck::Consumers => min_consumers.is_some(),
+                HealthCheck::Unacked => max_unacked.is_some(),
+                HealthCheck::Connections => max_connections_per_user.is_some(),
+                _ => true,
+            })
+            .collect(),
+    };
+    if checks.contains(&HealthCheck::PortListener) && port.is_none() {
+        return Err(CommandRunError::MissingOptions {
+            message: "the port_listener check requires --port".to_string(),
+        });
+    }
+    if checks.contains(&HealthCheck::ProtocolListener) && protocol.is_none() {
+        return Err(CommandRunError::MissingOptions {
+            message: "the protocol_listener check requires --protocol".to_string(),
+        });
+    }
+    if checks.contains(&HealthCheck::CertificateExpiration) && within.is_none() {
+        return Err(CommandRunError::MissingOptions {
+            message: "the certificate_expiration check requires --within".to_string(),
+        });
+    }
+    if checks.contains(&HealthCheck::QueueDepth) && max_queue_depth.is_none() {
+        return Err(CommandRunError::MissingOptions {
+            message: "the queue_depth check requires --max-queue-depth".to_string(),
+        });
+    }
+    if checks.contains(&HealthCheck::Unacked) && max_unacked.is_none() {
+        return Err(CommandRunError::MissingOptions {
+            message: "the unacked check requires --max-unacked".to_string(),
+        });
+    }
+    if checks.contains(&HealthCheck::Connections) && max_connections_per_user.is_none() {
+        return Err(CommandRunError::MissingOptions {
+            message: "the connections check requires --max-connections-per-user".to_string(),
+        });
+    }
+
+    Ok(health_checks::run_checks(
+        &checks,
+        continue_on_failure,
+        |check| {
+            let result = match check {
+                HealthCheck::LocalAlarms => client
+                    .health_check_local_alarms()
+                    .map_err(CommandRunError::from),
+                HealthCheck::ClusterWideAlarms => client
+                    .health_check_cluster_wide_alarms()
+                    .map_err(CommandRunError::from),
+                HealthCheck::NodeIsQuorumCritical => client
+                    .health_check_if_node_is_quorum_critical()
+                    .map_err(CommandRunError::from),
+                HealthCheck::DeprecatedFeaturesInUse => check_deprecated_features_in_use(&client),
+                HealthCheck::PortListener => client
+                    .health_check_port_listener(port.unwrap())
+                    .map_err(CommandRunError::from),
+                HealthCheck::ProtocolListener => client
+                    .health_check_protocol_listener(protocol.clone().unwrap())
+                    .map_err(CommandRunError::from),
+                HealthCheck::CertificateExpiration => {
+                    health_check_client.certificate_expiration(within.unwrap(), unit)
+                }
+                HealthCheck::VirtualHosts => health_check_client.virtual_hosts(),
+                HealthCheck::ReadyToServeClients => health_check_client.ready_to_serve_clients(),
+                HealthCheck::IsInService => health_check_client.is_in_service(),
+                HealthCheck::QueueDepth => check_queue_threshold(&client, check, |queues| {
+                    health_checks::check_queue_depth(
+                        queues,
+                        max_queue_depth.unwrap(),
+                        name_pattern.as_ref(),
+                    )
+                }),
+                // like `health_check consumers`, requires at least one consumer by default
+                HealthCheck::Consumers => check_queue_threshold(&client, check, |queues| {
+                    health_checks::check_consumers(
+                        queues,
+                        min_consumers.unwrap_or(1),
+                        name_pattern.as_ref(),
+                    )
+                }),
+                HealthCheck::Unacked => check_queue_threshold(&client, check, |queues| {
+                    health_checks::check_unacknowledged_messages(
+                        queues,
+                        max_unacked.unwrap(),
+                        name_pattern.as_ref(),
+                    )
+                }),
+                HealthCheck::Connections => {
+                    check_connections_threshold(&client, max_connections_per_user.unwrap())
+                }
+            };
+            result.map_err(|err| match err {
+                CommandRunError::HealthCheckFailed(info) => info.details.reason(),
+                other => other.to_string(),
+            })
+        },
+    ))
 }
 
-pub fn health_check_protocol_listener(
-    client: APIClient,
-    command_args: &ArgMatches,
-) -> CommandResult<()> {
-    let proto = command_args
-        .get_one::<SupportedProtocol>("protocol")
-        .cloned()
-        .unwrap();
-    Ok(client.health_check_protocol_listener(proto)?)
+fn check_deprecated_features_in_use(client: &APIClient) -> CommandResult<()> {
+    let in_use = client.list_deprecated_features_in_use()?;
+    if in_use.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<&str> = in_use.0.iter().map(|f| f.name.as_str()).collect();
+    Err(CommandRunError::FailureDuringExecution {
+        message: format!("deprecated features in use: {}", names.join(", ")),
+    })
 }
 
 pub fn close_connection(client: APIClient, command_args: &ArgMatches) -> CommandResult<()> {
@@ -2022,13 +3767,30 @@ pub fn export_cluster_wide_definitions(
     let transformations = command_args
         .get_many::<String>("transformations")
         .unwrap_or_default();
+    let rules = load_transformation_rules(command_args)?;
 
-    if transformations.len() == 0 {
+    let filter = definition_filter(command_args)?;
+
+    let canonical = command_args.get_flag("canonical");
+    let split_into = split_into_dir(command_args);
+
+    if transformations.len() == 0
+        && rules.is_none()
+        && filter.is_empty()
+        && !canonical
+        && split_into.is_none()
+    {
         export_cluster_wide_definitions_without_transformations(client, command_args)
     } else {
         let transformations = transformations.map(String::from).collect();
 
-        export_and_transform_cluster_wide_definitions(client, command_args, transformations)
+        export_and_transform_cluster_wide_definitions(
+            client,
+            command_args,
+            transformations,
+            rules,
+            filter,
+        )
     }
 }
 
@@ -2036,27 +3798,172 @@ fn export_and_transform_cluster_wide_definitions(
     client: APIClient,
     command_args: &ArgMatches,
     transformations: Vec<String>,
+    rules: Option<TransformationRules>,
+    filter: DefinitionFilter,
 ) -> Result<(), CommandRunError> {
-    match client.export_cluster_wide_definitions_as_data() {
-        Ok(mut defs0) => {
-            let chain = TransformationChain::from(transformations);
-            let defs1 = chain.apply(&mut defs0);
-            let json = serde_json::to_string_pretty(&defs1).unwrap();
+    let mut defs = export_and_transform_cluster_wide_definitions_as_value(
+        &client,
+        transformations,
+        rules,
+        filter,
+    )?;
+    if command_args.get_flag("canonical") {
+        definitions::canonicalize(&mut defs, command_args.get_flag("drop_volatile_fields"));
+    }
 
-            let path = command_args.str_arg("file");
-            match path.as_str() {
-                "-" => {
-                    println!("{}", json);
-                    Ok(())
-                }
-                file => {
-                    fs::write(file, &json)?;
-                    Ok(())
-                }
-            }
+    match split_into_dir(command_args) {
+        Some(dir) => {
+            let prune = command_args.optional_typed_or::<bool>("prune", false);
+            write_split_definitions(&defs, &dir, prune)
         }
-        Err(err) => Err(err.into()),
+        None => write_transformed_definitions(&defs, command_args),
+    }
+}
+
+fn export_and_transform_cluster_wide_definitions_as_value(
+    client: &APIClient,
+    transformations: Vec<String>,
+    rules: Option<TransformationRules>,
+    filter: DefinitionFilter,
+) -> Result<Value, CommandRunError> {
+    let mut transformations = transformations;
+    let redact_uri_credentials = take_redact_uri_credentials(&mut transformations);
+
+    let mut defs = if transformations.is_empty() {
+        parse_exported_definitions(&client.export_cluster_wide_definitions()?)?
+    } else {
+        let mut defs0 = client.export_cluster_wide_definitions_as_data()?;
+        let chain = TransformationChain::from(transformations);
+        let defs1 = chain.apply(&mut defs0);
+        serde_json::to_value(defs1).unwrap()
+    };
+
+    if let Some(rules) = rules {
+        rules.apply(&mut defs);
+    }
+    if redact_uri_credentials {
+        transformations::redact_uri_credentials(&mut defs);
+    }
+    filter.apply(&mut defs);
+
+    Ok(defs)
+}
+
+/// Definitions exported from one cluster, ready to be imported into another,
+/// plus the changes the import would make.
+pub struct DefinitionsCopyPlan {
+    pub definitions: Value,
+    pub changes: Vec<DefinitionChange>,
+}
+
+pub fn plan_definitions_copy(
+    source: &APIClient,
+    destination: &APIClient,
+    command_args: &ArgMatches,
+) -> Result<DefinitionsCopyPlan, CommandRunError> {
+    let transformations = command_args
+        .get_many::<String>("transformations")
+        .unwrap_or_default()
+        .map(String::from)
+        .collect();
+    let rules = load_transformation_rules(command_args)?;
+    let filter = definition_filter(command_args)?;
+
+    let definitions = export_and_transform_cluster_wide_definitions_as_value(
+        source,
+        transformations,
+        rules,
+        filter,
+    )?;
+    let existing = parse_exported_definitions(&destination.export_cluster_wide_definitions()?)?;
+    let changes = definitions::plan_import(&definitions, &existing);
+
+    Ok(DefinitionsCopyPlan {
+        definitions,
+        changes,
+    })
+}
+
+pub fn apply_definitions_copy(
+    destination: &APIClient,
+    plan: DefinitionsCopyPlan,
+    command_args: &ArgMatches,
+) -> Result<(), CommandRunError> {
+    let approve = command_args.optional_typed_or::<bool>("approve", false);
+    let non_interactive_cli = command_args
+        .optional_typed::<bool>("non_interactive")
+        .unwrap_or_else(|| pre_flight::InteractivityMode::from_env().is_non_interactive());
+    if !approve && !pre_flight::is_non_interactive() && !non_interactive_cli {
+        return Err(CommandRunError::FailureDuringExecution {
+            message: "This operation modifies the destination cluster and requires the --approve flag; use --dry-run to only see the plan".to_string(),
+        });
+    }
+
+    if plan.changes.is_empty() {
+        return Ok(());
+    }
+    destination
+        .import_definitions(plan.definitions)
+        .map_err(Into::into)
+}
+
+// `export definitions`, the legacy alias, does not support --split-into
+fn split_into_dir(command_args: &ArgMatches) -> Option<PathBuf> {
+    command_args
+        .try_get_one::<PathBuf>("split_into")
+        .ok()
+        .flatten()
+        .cloned()
+}
+
+fn write_split_definitions(defs: &Value, dir: &Path, prune: bool) -> Result<(), CommandRunError> {
+    let split = definitions::split_by_vhost(defs);
+    if !prune && dir.is_dir() && fs::read_dir(dir)?.next().is_some() {
+        return Err(CommandRunError::FailureDuringExecution {
+            message: format!(
+                "`{}` is not empty; pass --prune to overwrite the definitions files in it and remove those of virtual hosts that no longer exist",
+                dir.display()
+            ),
+        });
+    }
+    fs::create_dir_all(dir)?;
+
+    // Files of virtual hosts that no longer exist would be re-created by `import --from-dir`
+    let mut removed: Vec<PathBuf> = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some(vhost) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(definitions::vhost_from_definitions_file_name)
+            && !split.vhosts.iter().any(|(name, _)| *name == vhost)
+        {
+            fs::remove_file(&path)?;
+            removed.push(path);
+        }
+    }
+    removed.sort();
+    for path in &removed {
+        println!(
+            "Removed {}: its virtual host no longer exists",
+            path.display()
+        );
+    }
+
+    let cluster_json = serde_json::to_string_pretty(&split.cluster).unwrap();
+    fs::write(
+        dir.join(definitions::CLUSTER_DEFINITIONS_FILE_NAME),
+        cluster_json,
+    )?;
+    for (vhost, vhost_defs) in &split.vhosts {
+        let json = serde_json::to_string_pretty(vhost_defs).unwrap();
+        fs::write(
+            dir.join(definitions::vhost_definitions_file_name(vhost)),
+            json,
+        )?;
     }
+
+    Ok(())
 }
 
 fn export_cluster_wide_definitions_without_transformations(
@@ -2103,13 +4010,25 @@ pub fn export_vhost_definitions(
     let transformations = command_args
         .get_many::<String>("transformations")
         .unwrap_or_default();
+    let rules = load_transformation_rules(command_args)?;
+
+    let filter = definition_filter(command_args)?;
 
-    if transformations.len() == 0 {
+    let canonical = command_args.get_flag("canonical");
+
+    if transformations.len() == 0 && rules.is_none() && filter.is_empty() && !canonical {
         export_vhost_definitions_without_transformations(client, vhost, command_args)
     } else {
         let transformations = transformations.map(String::from).collect();
 
-        export_and_transform_vhost_definitions(client, vhost, command_args, transformations)
+        export_and_transform_vhost_definitions(
+            client,
+            vhost,
+            command_args,
+            transformations,
+            rules,
+            filter,
+        )
     }
 }
 
@@ -2118,27 +4037,156 @@ fn export_and_transform_vhost_definitions(
     vhost: &str,
     command_args: &ArgMatches,
     transformations: Vec<String>,
+    rules: Option<TransformationRules>,
+    filter: DefinitionFilter,
 ) -> Result<(), CommandRunError> {
-    match client.export_vhost_definitions_as_data(vhost) {
-        Ok(mut defs0) => {
-            let chain = VirtualHostTransformationChain::from(transformations);
-            chain.apply(&mut defs0);
+    let mut transformations = transformations;
+    let redact_uri_credentials = take_redact_uri_credentials(&mut transformations);
 
-            let json = serde_json::to_string_pretty(&defs0).unwrap();
+    let mut defs = if transformations.is_empty() {
+        parse_exported_definitions(&client.export_vhost_definitions(vhost)?)?
+    } else {
+        let mut defs0 = client.export_vhost_definitions_as_data(vhost)?;
+        let chain = VirtualHostTransformationChain::from(transformations);
+        chain.apply(&mut defs0);
+        serde_json::to_value(&defs0).unwrap()
+    };
 
-            let path = command_args.str_arg("file");
-            match path.as_str() {
-                "-" => {
-                    println!("{}", json);
-                    Ok(())
-                }
-                file => {
-                    fs::write(file, &json)?;
-                    Ok(())
-                }
-            }
+    if let Some(rules) = rules {
+        rules.apply(&mut defs);
+    }
+    if redact_uri_credentials {
+        transformations::redact_uri_credentials(&mut defs);
+    }
+    filter.apply(&mut defs);
+    if command_args.get_flag("canonical") {
+        definitions::canonicalize(&mut defs, command_args.get_flag("drop_volatile_fields"));
+    }
+
+    write_transformed_definitions(&defs, command_args)
+}
+
+// The HTTP API client's transformation chains would treat this transformation as a no-op,
+// so it is removed from the list and applied separately
+fn take_redact_uri_credentials(transformations: &mut Vec<String>) -> bool {
+    let len = transformations.len();
+    transformations.retain(|t| t != transformations::REDACT_URI_CREDENTIALS);
+    transformations.len() != len
+}
+
+fn substitute_uri_credentials(
+    defs: &mut Value,
+    command_args: &ArgMatches,
+) -> Result<(), CommandRunError> {
+    let from_env = command_args
+        .try_get_one::<bool>("uri_credentials_from_env")
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(false);
+    let secrets = command_args
+        .try_get_one::<PathBuf>("uri_credentials_file")
+        .ok()
+        .flatten()
+        .map(|path| transformations::load_uri_credentials(path))
+        .transpose()?;
+    let no_sources = !from_env && secrets.is_none();
+
+    // Placeholders are looked for even when no credentials were provided: importing
+    // them as is would leave shovels and federation links with unusable URIs.
+    // Values from the secrets file take precedence over environment variables
+    let missing = transformations::substitute_uri_credentials(defs, |name| {
+        secrets
+            .as_ref()
+            .and_then(|m| m.get(name).cloned())
+            .or_else(|| from_env.then(|| env::var(name).ok()).flatten())
+    });
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        let hint = if no_sources {
+            "; provide them with --uri-credentials-file or --uri-credentials-from-env"
+        } else {
+            ""
+        };
+        Err(CommandRunError::FailureDuringExecution {
+            message: format!(
+                "no credentials were provided for URI placeholders: {}{}",
+                missing.join(", "),
+                hint
+            ),
+        })
+    }
+}
+
+fn load_transformation_rules(
+    command_args: &ArgMatches,
+) -> Result<Option<TransformationRules>, CommandRunError> {
+    command_args
+        .optional_typed::<PathBuf>("transformation_rules")
+        .map(|path| TransformationRules::load(&path))
+        .transpose()
+}
+
+fn definition_filter(command_args: &ArgMatches) -> Result<DefinitionFilter, CommandRunError> {
+    let kinds = |name: &str| -> Option<Vec<DefinitionKind>> {
+        command_args
+            .try_get_many::<String>(name)
+            .ok()
+            .flatten()
+            .map(|vals| vals.filter_map(|v| v.parse().ok()).collect())
+    };
+
+    Ok(DefinitionFilter {
+        include_vhosts: optional_regex_arg(command_args, "include_vhosts")?,
+        exclude_vhosts: optional_regex_arg(command_args, "exclude_vhosts")?,
+        include_kinds: kinds("include_kinds"),
+        exclude_kinds: kinds("exclude_kinds").unwrap_or_default(),
+        include_names: optional_regex_arg(command_args, "include_names")?,
+        exclude_names: optional_regex_arg(command_args, "exclude_names")?,
+    })
+}
+
+// Not every command that shares this code path defines the filtering options,
+// so a missing argument definition is treated as an absent value
+fn optional_regex_arg(
+    command_args: &ArgMatches,
+    name: &str,
+) -> Result<Option<Regex>, CommandRunError> {
+    command_args
+        .try_get_one::<String>(name)
+        .ok()
+        .flatten()
+        .map(|pattern| {
+            Regex::new(pattern).map_err(|_| CommandRunError::UnsupportedArgumentValue {
+                property: name.to_string(),
+            })
+        })
+        .transpose()
+}
+
+fn parse_exported_definitions(json: &str) -> Result<Value, CommandRunError> {
+    serde_json::from_str(json).map_err(|err| CommandRunError::JsonParseError {
+        message: err.to_string(),
+    })
+}
+
+fn write_transformed_definitions(
+    defs: &Value,
+    command_args: &ArgMatches,
+) -> Result<(), CommandRunError> {
+    let json = serde_json::to_string_pretty(defs).unwrap();
+
+    let path = command_args.str_arg("file");
+    match path.as_str() {
+        "-" => {
+            println!("{}", json);
+            Ok(())
+        }
+        file => {
+            fs::write(file, &json)?;
+            Ok(())
         }
-        Err(err) => Err(err.into()),
     }
 }
 
@@ -2169,30 +4217,234 @@ pub fn import_definitions(
     client: APIClient,
     command_args: &ArgMatches,
 ) -> Result<(), CommandRunError> {
-    let defs_json = read_and_parse_definitions(command_args)?;
+    let mut defs_json = read_and_parse_definitions(command_args)?;
+    if let Some(rules) = load_transformation_rules(command_args)? {
+        rules.apply(&mut defs_json);
+    }
+    substitute_uri_credentials(&mut defs_json, command_args)?;
+    require_administrator(&client, "import definitions")?;
     client.import_definitions(defs_json).map_err(Into::into)
 }
 
+impl BulkPreviewRow for DefinitionObject {
+    fn preview_name(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Imports definitions one object at a time, so that queues and exchanges that already exist
+/// with different properties do not fail the entire import.
+pub fn import_definitions_object_by_object(
+    client: APIClient,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<BulkReport<DefinitionObject>, CommandRunError> {
+    let strategy = command_args
+        .get_one::<String>("on_conflict")
+        .map(|s| ConflictStrategy::from(s.as_str()))
+        .unwrap_or_default();
+    let approve = command_args.optional_typed_or::<bool>("approve", false);
+    let fail_fast = command_args.optional_typed_or::<bool>("fail_fast", false);
+    let non_interactive_cli = command_args
+        .optional_typed::<bool>("non_interactive")
+        .unwrap_or_else(|| pre_flight::InteractivityMode::from_env().is_non_interactive());
+
+    if strategy == ConflictStrategy::Recreate
+        && !approve
+        && !pre_flight::is_non_interactive()
+        && !non_interactive_cli
+    {
+        return Err(CommandRunError::FailureDuringExecution {
+            message: "Recreating conflicting queues and exchanges deletes them, their messages and bindings, and requires the --approve flag".to_string(),
+        });
+    }
+
+    let mut defs_json = read_and_parse_definitions(command_args)?;
+    if let Some(rules) = load_transformation_rules(command_args)? {
+        rules.apply(&mut defs_json);
+    }
+    substitute_uri_credentials(&mut defs_json, command_args)?;
+    require_administrator(&client, "import definitions")?;
+
+    let existing = parse_exported_definitions(&client.export_cluster_wide_definitions()?)?;
+    let conflicts = definitions::find_conflicts(&defs_json, &existing);
+
+    let mode = if fail_fast {
+        BulkMode::FailFast
+    } else {
+        BulkMode::ContinueOnError
+    };
+
+    Ok(bulk::bulk_op(
+        definitions::split_into_objects(&defs_json),
+        mode,
+        |obj| obj.to_string(),
+        |obj| {
+            if conflicts.iter().any(|c| obj.conflicts_with(c)) {
+                match strategy {
+                    ConflictStrategy::Fail => {
+                        return ItemAction::Fail(
+                            "already exists with different properties".to_string(),
+                        );
+                    }
+                    ConflictStrategy::Skip => {
+                        return ItemAction::Skip(SkipReason::Conflict {
+                            reason: "already exists with different properties".to_string(),
+                        });
+                    }
+                    ConflictStrategy::Recreate => {
+                        if let Err(e) = delete_conflicting_object(&client, obj) {
+                            return ItemAction::Fail(e.to_string());
+                        }
+                    }
+                }
+            }
+            match client.import_definitions(obj.to_document()) {
+                Ok(_) => ItemAction::Ok,
+                Err(e) => ItemAction::Fail(CommandRunError::from(e).to_string()),
+            }
+        },
+        prog_rep,
+        "Importing definitions",
+    ))
+}
+
+fn delete_conflicting_object(client: &APIClient, obj: &DefinitionObject) -> CommandResult<()> {
+    let vhost = obj.vhost().unwrap_or(DEFAULT_VHOST);
+    let name = definitions::str_field(&obj.definition, "name").unwrap_or_default();
+    match obj.kind {
+        DefinitionKind::Queues => Ok(client.delete_queue(vhost, name, true)?),
+        DefinitionKind::Exchanges => Ok(client.delete_exchange(vhost, name, true)?),
+        _ => Ok(()),
+    }
+}
+
 pub fn import_vhost_definitions(
     client: APIClient,
     vhost: &str,
     command_args: &ArgMatches,
 ) -> Result<(), CommandRunError> {
-    let defs_json = read_and_parse_definitions(command_args)?;
+    let mut defs_json = read_and_parse_definitions(command_args)?;
+    if let Some(rules) = load_transformation_rules(command_args)? {
+        rules.apply(&mut defs_json);
+    }
+    substitute_uri_credentials(&mut defs_json, command_args)?;
+    require_administrator(&client, "import definitions")?;
     client
         .import_vhost_definitions(vhost, defs_json)
         .map_err(Into::into)
 }
 
+pub fn import_definitions_from_dir(
+    client: APIClient,
+    command_args: &ArgMatches,
+    prog_rep: &mut dyn ProgressReporter,
+) -> Result<(), CommandRunError> {
+    let dir = command_args.get_one::<PathBuf>("from_dir").ok_or_else(|| {
+        CommandRunError::MissingRequiredArgument {
+            name: "from_dir".to_string(),
+        }
+    })?;
+    let rules = load_transformation_rules(command_args)?;
+
+    let cluster_file = dir.join(definitions::CLUSTER_DEFINITIONS_FILE_NAME);
+    if !cluster_file.is_file() {
+        return Err(CommandRunError::FailureDuringExecution {
+            message: format!(
+                "`{}` does not exist or is not a file",
+                cluster_file.display()
+            ),
+        });
+    }
+
+    let mut vhost_files: Vec<(String, PathBuf)> = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some(vhost) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(definitions::vhost_from_definitions_file_name)
+        {
+            vhost_files.push((vhost, path));
+        }
+    }
+    vhost_files.sort();
+    require_administrator(&client, "import definitions")?;
+
+    // The cluster-level file goes first: it declares the virtual hosts
+    // the rest of the files are imported into
+    let mut files = vec![(None, cluster_file)];
+    files.extend(vhost_files.into_iter().map(|(vh, path)| (Some(vh), path)));
+
+    let total = files.len();
+    prog_rep.start_operation(total, "Importing definition files");
+    for (index, (vhost, path)) in files.iter().enumerate() {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        prog_rep.report_progress(index + 1, total, &file_name);
+
+        let result = read_definitions_file(path).and_then(|defs| {
+            let mut defs = match vhost {
+                Some(vh) => definitions::into_cluster_format(vh, &defs),
+                None => defs,
+            };
+            if let Some(rules) = &rules {
+                rules.apply(&mut defs);
+            }
+            substitute_uri_credentials(&mut defs, command_args)?;
+            client.import_definitions(defs).map_err(Into::into)
+        });
+
+        if let Err(err) = result {
+            prog_rep.report_failure(&file_name, &err.to_string());
+            prog_rep.finish_operation(total);
+            return Err(err);
+        }
+        prog_rep.report_success(&file_name);
+    }
+    prog_rep.finish_operation(total);
+
+    Ok(())
+}
+
+fn read_definitions_file(path: &Path) -> Result<Value, CommandRunError> {
+    let contents = fs::read_to_string(path).map_err(|err| {
+        let message = format!(
+            "`{}` does not exist or is not readable: {}",
+            path.display(),
+            err
+        );
+        CommandRunError::FailureDuringExecution { message }
+    })?;
+
+    serde_json::from_str(&contents).map_err(|err| {
+        let message = format!("`{}` is not a valid JSON file: {}", path.display(), err);
+        CommandRunError::FailureDuringExecution { message }
+    })
+}
+
 fn read_and_parse_definitions(command_args: &ArgMatches) -> Result<Value, CommandRunError> {
-    let path = command_args.optional_string("file").map(|s| {
-        s.trim_ascii()
-            .trim_matches('\'')
-            .trim_matches('"')
-            .to_string()
-    });
-    let path_ref = path.as_deref();
+    let paths: Vec<String> = command_args
+        .get_many::<String>("file")
+        .unwrap_or_default()
+        .map(|s| {
+            s.trim_ascii()
+                .trim_matches('\'')
+                .trim_matches('"')
+                .to_string()
+        })
+        .collect();
+    if paths.len() > 1 {
+        return read_and_merge_definitions(&paths);
+    }
+
     let use_stdin = command_args.optional_typed::<bool>("stdin");
+    read_and_parse_definitions_file(paths.first().map(String::as_str), use_stdin)
+}
+
+fn read_and_parse_definitions_file(
+    path_ref: Option<&str>,
+    use_stdin: Option<bool>,
+) -> Result<Value, CommandRunError> {
     let definitions = read_definitions(path_ref, use_stdin).map_err(|err| {
         let message = match path_ref {
             None => format!("could not read from standard input: {}", err),
@@ -2210,6 +4462,38 @@ fn read_and_parse_definitions(command_args: &ArgMatches) -> Result<Value, Comman
     })
 }
 
+fn read_and_merge_definitions(paths: &[String]) -> Result<Value, CommandRunError> {
+    let fragments = paths
+        .iter()
+        .map(|path| {
+            Ok(DefinitionsFragment {
+                source: path.clone(),
+                definitions: read_and_parse_definitions_file(Some(path), None)?,
+            })
+        })
+        .collect::<Result<Vec<_>, CommandRunError>>()?;
+
+    definitions::merge(&fragments).map_err(|err| CommandRunError::FailureDuringExecution {
+        message: err.to_string(),
+    })
+}
+
+pub fn merge_definitions(command_args: &ArgMatches) -> Result<(), CommandRunError> {
+    let paths: Vec<String> = command_args
+        .get_many::<String>("file")
+        .unwrap_or_default()
+        .cloned()
+        .collect();
+    let merged = read_and_merge_definitions(&paths)?;
+    let json = serde_json::to_string_pretty(&merged).unwrap();
+
+    match command_args.str_arg("output_file").as_str() {
+        "-" => println!("{}", json),
+        path => fs::write(path, json)?,
+    }
+    Ok(())
+}
+
 const POLICY_LENGTH_LIMIT: usize = 255;
 const OVERRIDE_POLICY_PREFIX: &str = "overrides.";
 
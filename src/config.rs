@@ -213,6 +213,68 @@ impl SharedSettings {
         }
     }
 
+    /// Settings of a node alias that is not the target of the global options, such as
+    /// `definitions copy --from-node` and `--to-node`. The connection settings come from
+    /// the configuration file section only, so that `--host`, `--username` and similar
+    /// options cannot redirect the alias to another node. Output-related options still apply.
+    pub fn from_node_alias_section(
+        general_args: &ArgMatches,
+        section: &Self,
+    ) -> Result<Self, CommandRunError> {
+        let mut settings = section.clone();
+        let (hostname, port) = match &section.base_uri {
+            Some(s) => {
+                let url = Url::parse(s).map_err(|e| CommandRunError::InvalidBaseUri {
+                    uri: s.clone(),
+                    message: e.to_string(),
+                })?;
+                settings.tls = settings.tls || url.scheme() == "https";
+                (url.host_str().map(String::from), url.port())
+            }
+            None => (section.hostname.clone(), section.port),
+        };
+        if settings.tls {
+            settings.scheme = Scheme::Https;
+        }
+        let default_port = if settings.tls {
+            DEFAULT_HTTPS_PORT
+        } else {
+            DEFAULT_HTTP_PORT
+        };
+
+        settings.hostname = Some(hostname.unwrap_or(DEFAULT_HOST.to_string()));
+        settings.port = Some(port.unwrap_or(default_port));
+        settings.username = Some(
+            section
+                .username
+                .clone()
+                .unwrap_or(DEFAULT_USERNAME.to_string()),
+        );
+        settings.password = Some(
+            section
+                .password
+                .clone()
+                .unwrap_or(DEFAULT_PASSWORD.to_string()),
+        );
+        settings.virtual_host = Some(
+            section
+                .virtual_host
+                .clone()
+                .unwrap_or(DEFAULT_VHOST.to_owned()),
+        );
+
+        settings.non_interactive |= general_args.get_flag("non_interactive");
+        settings.quiet |= general_args.get_flag("quiet");
+        settings.verbose |= general_args.get_flag("verbose");
+        settings.table_style = general_args
+            .get_one::<TableStyle>("table_style")
+            .cloned()
+            .or(section.table_style)
+            .or(Some(TableStyle::default()));
+
+        Ok(settings)
+    }
+
     pub fn from_args(general_args: &ArgMatches) -> Result<Self, CommandRunError> {
         let base_uri = general_args.get_one::<String>("base_uri").cloned();
 
@@ -21,6 +21,9 @@ pub const DEFAULT_VHOST: &str = "/";
 pub const DEFAULT_USERNAME: &str = "guest";
 pub const DEFAULT_PASSWORD: &str = "guest";
 
+pub const ADMINISTRATOR_TAG: &str = "administrator";
+pub const MONITORING_TAG: &str = "monitoring";
+
 // default node section in the configuration file
 pub const DEFAULT_NODE_ALIAS: &str = "default";
 
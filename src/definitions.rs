@@ -0,0 +1,939 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers that operate on definition files as JSON documents.
+//!
+//! The typed definition sets in `rabbitmq_http_client::responses` do not carry
+//! every key a definitions file can contain (e.g. topic permissions or global
+//! runtime parameters), so anything that must round-trip a file back to
+//! the import endpoint works on [`serde_json::Value`] instead.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use tabled::Tabled;
+
+/// A kind of object (a top-level collection) found in a definitions file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DefinitionKind {
+    Users,
+    #[serde(alias = "virtual_hosts")]
+    Vhosts,
+    Permissions,
+    TopicPermissions,
+    Parameters,
+    GlobalParameters,
+    Policies,
+    Queues,
+    Exchanges,
+    Bindings,
+}
+
+impl DefinitionKind {
+    pub const ALL: [DefinitionKind; 10] = [
+        DefinitionKind::Users,
+        DefinitionKind::Vhosts,
+        DefinitionKind::Permissions,
+        DefinitionKind::TopicPermissions,
+        DefinitionKind::Parameters,
+        DefinitionKind::GlobalParameters,
+        DefinitionKind::Policies,
+        DefinitionKind::Queues,
+        DefinitionKind::Exchanges,
+        DefinitionKind::Bindings,
+    ];
+
+    /// The top-level key this collection is stored under.
+    pub fn key(&self) -> &'static str {
+        match self {
+            DefinitionKind::Users => "users",
+            DefinitionKind::Vhosts => "vhosts",
+            DefinitionKind::Permissions => "permissions",
+            DefinitionKind::TopicPermissions => "topic_permissions",
+            DefinitionKind::Parameters => "parameters",
+            DefinitionKind::GlobalParameters => "global_parameters",
+            DefinitionKind::Policies => "policies",
+            DefinitionKind::Queues => "queues",
+            DefinitionKind::Exchanges => "exchanges",
+            DefinitionKind::Bindings => "bindings",
+        }
+    }
+
+    /// Returns true if the object's name matches the pattern.
+    ///
+    /// Permissions are matched by username, bindings by either their source
+    /// or their destination.
+    pub fn name_matches(&self, obj: &Value, pattern: &Regex) -> bool {
+        let field_matches = |field: &str| {
+            obj.get(field)
+                .and_then(Value::as_str)
+                .is_some_and(|s| pattern.is_match(s))
+        };
+
+        match self {
+            DefinitionKind::Permissions | DefinitionKind::TopicPermissions => field_matches("user"),
+            DefinitionKind::Bindings => field_matches("source") || field_matches("destination"),
+            _ => field_matches("name"),
+        }
+    }
+
+    /// The fields that identify an object of this kind, in sorting order.
+    pub fn natural_key_fields(&self) -> &'static [&'static str] {
+        match self {
+            DefinitionKind::Users | DefinitionKind::Vhosts | DefinitionKind::GlobalParameters => {
+                &["name"]
+            }
+            DefinitionKind::Permissions => &["vhost", "user"],
+            DefinitionKind::TopicPermissions => &["vhost", "user", "exchange"],
+            DefinitionKind::Parameters => &["vhost", "component", "name"],
+            DefinitionKind::Policies | DefinitionKind::Queues | DefinitionKind::Exchanges => {
+                &["vhost", "name"]
+            }
+            DefinitionKind::Bindings => &[
+                "vhost",
+                "source",
+                "destination_type",
+                "destination",
+                "routing_key",
+                "arguments",
+            ],
+        }
+    }
+
+    /// The fields that define an object of this kind, as opposed to server-populated
+    /// or cosmetic ones. `None` means that every field does.
+    pub fn defining_fields(&self) -> Option<&'static [&'static str]> {
+        match self {
+            DefinitionKind::Queues => Some(&["type", "durable", "auto_delete", "arguments"]),
+            DefinitionKind::Exchanges => {
+                Some(&["type", "durable", "auto_delete", "internal", "arguments"])
+            }
+            DefinitionKind::Policies => Some(&["pattern", "definition", "priority", "apply-to"]),
+            _ => None,
+        }
+    }
+
+    /// Returns true if two objects (in canonical form) with the same natural key differ
+    /// in any of the [defining fields](Self::defining_fields). A field that only one of the objects
+    /// has, such as queue `type` in definitions exported by older versions, is not compared.
+    pub fn differs(&self, a: &Value, b: &Value) -> bool {
+        match self.defining_fields() {
+            None => a != b,
+            Some(fields) => fields
+                .iter()
+                .any(|field| match (a.get(*field), b.get(*field)) {
+                    (Some(x), Some(y)) => x != y,
+                    _ => false,
+                }),
+        }
+    }
+
+    /// A short, human-readable identifier of an object, not including its virtual host.
+    pub fn display_name(&self, obj: &Value) -> String {
+        let field = |name: &str| str_field(obj, name).unwrap_or_default();
+        match self {
+            DefinitionKind::Permissions => field("user").to_owned(),
+            DefinitionKind::TopicPermissions => {
+                format!("{} ({})", field("user"), field("exchange"))
+            }
+            DefinitionKind::Parameters => format!("{}/{}", field("component"), field("name")),
+            DefinitionKind::Bindings => format!(
+                "{} -> {} {} (routing key: '{}')",
+                field("source"),
+                field("destination_type"),
+                field("destination"),
+                field("routing_key")
+            ),
+            _ => field("name").to_owned(),
+        }
+    }
+
+    /// Returns the natural key of an object. Missing fields are treated as empty strings,
+    /// so this works for virtual host-specific definitions, too.
+    pub fn natural_key(&self, obj: &Value) -> Vec<String> {
+        self.natural_key_fields()
+            .iter()
+            .map(|field| match obj.get(*field) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for DefinitionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "virtual_hosts" => Ok(DefinitionKind::Vhosts),
+            _ => DefinitionKind::ALL
+                .into_iter()
+                .find(|k| k.key() == s)
+                .ok_or_else(|| format!("unknown definition kind: {}", s)),
+        }
+    }
+}
+
+/// Returns a mutable reference to a top-level collection, if it is present.
+///
+/// Definitions produced by serializing a typed definition set use `virtual_hosts`
+/// instead of `vhosts`, so both keys are accepted.
+pub fn collection_mut(defs: &mut Value, kind: DefinitionKind) -> Option<&mut Vec<Value>> {
+    let key = if kind == DefinitionKind::Vhosts
+        && defs.get("vhosts").is_none()
+        && defs.get("virtual_hosts").is_some()
+    {
+        "virtual_hosts"
+    } else {
+        kind.key()
+    };
+
+    defs.get_mut(key).and_then(Value::as_array_mut)
+}
+
+/// Returns the virtual host an object belongs to. Objects in virtual host-specific
+/// definition files have no `vhost` key.
+pub fn vhost_of(obj: &Value) -> Option<&str> {
+    obj.get("vhost").and_then(Value::as_str)
+}
+
+/// Returns a string field of an object.
+pub fn str_field<'a>(obj: &'a Value, field: &str) -> Option<&'a str> {
+    obj.get(field).and_then(Value::as_str)
+}
+
+/// Top-level keys whose values differ between exports of the same topology.
+pub const VOLATILE_KEYS: [&str; 5] = [
+    "rabbit_version",
+    "rabbitmq_version",
+    "product_name",
+    "product_version",
+    "server_version",
+];
+
+/// Optional arguments and policy definition keys (without the `x-` prefix)
+/// that have integer values.
+const INTEGER_VALUED_KEYS: [&str; 13] = [
+    "consumer-timeout",
+    "delivery-limit",
+    "expires",
+    "initial-cluster-size",
+    "max-in-memory-bytes",
+    "max-in-memory-length",
+    "max-length",
+    "max-length-bytes",
+    "max-priority",
+    "message-ttl",
+    "quorum-initial-group-size",
+    "quorum-target-group-size",
+    "stream-max-segment-size-bytes",
+];
+
+/// Optional arguments and policy definition keys (without the `x-` prefix)
+/// that have boolean values.
+const BOOLEAN_VALUED_KEYS: [&str; 1] = ["single-active-consumer"];
+
+/// Rewrites a definitions document into a stable, diff-friendly form:
+///
+///  * every collection is sorted by the natural key of its objects
+///  * object keys are sorted
+///  * optional arguments and policy definition values that were provided as strings
+///    (or floats) but are integers or booleans are converted to their actual type
+///  * optionally, fields that change between exports (e.g. `rabbitmq_version`) are removed
+pub fn canonicalize(defs: &mut Value, drop_volatile_fields: bool) {
+    if drop_volatile_fields && let Some(map) = defs.as_object_mut() {
+        for key in VOLATILE_KEYS {
+            map.remove(key);
+        }
+    }
+
+    for kind in DefinitionKind::ALL {
+        let Some(items) = collection_mut(defs, kind) else {
+            continue;
+        };
+
+        for obj in items.iter_mut() {
+            normalize_object(kind, obj);
+        }
+        items.sort_by_cached_key(|obj| kind.natural_key(obj));
+    }
+
+    sort_keys(defs);
+}
+
+fn normalize_object(kind: DefinitionKind, obj: &mut Value) {
+    let map_key = match kind {
+        DefinitionKind::Queues | DefinitionKind::Exchanges | DefinitionKind::Bindings => {
+            "arguments"
+        }
+        DefinitionKind::Policies => "definition",
+        DefinitionKind::Users | DefinitionKind::Vhosts => {
+            if let Some(Value::Array(tags)) = obj.get_mut("tags") {
+                tags.sort_by_key(|t| t.to_string());
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    if let Some(Value::Object(args)) = obj.get_mut(map_key) {
+        for (key, value) in args.iter_mut() {
+            normalize_argument_value(key, value);
+        }
+    }
+}
+
+fn normalize_argument_value(key: &str, value: &mut Value) {
+    let key = key.strip_prefix("x-").unwrap_or(key);
+
+    if INTEGER_VALUED_KEYS.contains(&key) {
+        let n = match value {
+            Value::String(s) => s.trim().parse::<i64>().ok(),
+            Value::Number(n) if n.is_f64() => {
+                n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64)
+            }
+            _ => None,
+        };
+        if let Some(n) = n {
+            *value = Value::from(n);
+        }
+    } else if BOOLEAN_VALUED_KEYS.contains(&key)
+        && let Value::String(s) = value
+        && let Ok(b) = s.trim().parse::<bool>()
+    {
+        *value = Value::Bool(b);
+    }
+}
+
+/// Recursively rebuilds every object with its keys in sorted order.
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = std::mem::take(map).into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, mut v) in entries {
+                sort_keys(&mut v);
+                map.insert(key, v);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+/// Collections that belong to a specific virtual host and are included into
+/// virtual host-specific definition files.
+pub const VHOST_SCOPED_KINDS: [DefinitionKind; 5] = [
+    DefinitionKind::Parameters,
+    DefinitionKind::Policies,
+    DefinitionKind::Queues,
+    DefinitionKind::Exchanges,
+    DefinitionKind::Bindings,
+];
+
+/// The name of the cluster-level file in a directory produced by [`split_by_vhost`].
+pub const CLUSTER_DEFINITIONS_FILE_NAME: &str = "cluster.json";
+
+const VHOST_DEFINITIONS_FILE_NAME_PREFIX: &str = "vhost.";
+const DEFINITIONS_FILE_NAME_SUFFIX: &str = ".json";
+
+/// Cluster-wide definitions split into a cluster-level part and
+/// virtual host-specific parts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitDefinitions {
+    /// Users, virtual hosts, permissions, topic permissions and global runtime parameters
+    pub cluster: Value,
+    /// Virtual host-specific definitions in the format accepted by `import_into_vhost`,
+    /// ordered by virtual host name
+    pub vhosts: Vec<(String, Value)>,
+}
+
+/// Splits cluster-wide definitions into a cluster-level part and one
+/// virtual host-specific part per virtual host.
+pub fn split_by_vhost(defs: &Value) -> SplitDefinitions {
+    let mut cluster = defs.clone();
+    if let Some(map) = cluster.as_object_mut() {
+        for kind in VHOST_SCOPED_KINDS {
+            map.remove(kind.key());
+        }
+    }
+
+    let mut vhost_names: Vec<String> = defs
+        .get("vhosts")
+        .or_else(|| defs.get("virtual_hosts"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|vh| str_field(vh, "name").map(String::from))
+        .collect();
+    // objects can reference virtual hosts missing from the list
+    for kind in VHOST_SCOPED_KINDS {
+        for obj in defs
+            .get(kind.key())
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(vh) = vhost_of(obj)
+                && !vhost_names.iter().any(|n| n == vh)
+            {
+                vhost_names.push(vh.to_owned());
+            }
+        }
+    }
+    vhost_names.sort();
+
+    let vhosts = vhost_names
+        .into_iter()
+        .map(|vhost| {
+            let mut scoped = serde_json::Map::new();
+            for key in VOLATILE_KEYS {
+                if let Some(v) = defs.get(key) {
+                    scoped.insert(key.to_owned(), v.clone());
+                }
+            }
+            for kind in VHOST_SCOPED_KINDS {
+                let items: Vec<Value> = defs
+                    .get(kind.key())
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter(|obj| vhost_of(obj) == Some(vhost.as_str()))
+                    .map(|obj| {
+                        let mut obj = obj.clone();
+                        if let Some(map) = obj.as_object_mut() {
+                            map.remove("vhost");
+                        }
+                        obj
+                    })
+                    .collect();
+                scoped.insert(kind.key().to_owned(), Value::Array(items));
+            }
+            (vhost, Value::Object(scoped))
+        })
+        .collect();
+
+    SplitDefinitions { cluster, vhosts }
+}
+
+/// Converts virtual host-specific definitions into the cluster-wide format
+/// by adding the virtual host to every object.
+pub fn into_cluster_format(vhost: &str, defs: &Value) -> Value {
+    let mut defs = defs.clone();
+    for kind in VHOST_SCOPED_KINDS {
+        if let Some(items) = collection_mut(&mut defs, kind) {
+            for obj in items.iter_mut() {
+                if let Some(map) = obj.as_object_mut() {
+                    map.insert("vhost".to_owned(), Value::String(vhost.to_owned()));
+                }
+            }
+        }
+    }
+    defs
+}
+
+/// Returns the file name used for a virtual host's definitions. Virtual host names
+/// can contain characters that are not safe to use in file names (e.g. the slash in `/`),
+/// so everything except ASCII alphanumerics, `-`, `_` and `.` is percent-encoded.
+pub fn vhost_definitions_file_name(vhost: &str) -> String {
+    format!(
+        "{}{}{}",
+        VHOST_DEFINITIONS_FILE_NAME_PREFIX,
+        percent_encode(vhost),
+        DEFINITIONS_FILE_NAME_SUFFIX
+    )
+}
+
+/// The inverse of [`vhost_definitions_file_name`]. Returns `None` for file names
+/// that do not follow the naming scheme.
+pub fn vhost_from_definitions_file_name(file_name: &str) -> Option<String> {
+    let encoded = file_name
+        .strip_prefix(VHOST_DEFINITIONS_FILE_NAME_PREFIX)?
+        .strip_suffix(DEFINITIONS_FILE_NAME_SUFFIX)?;
+    percent_decode(encoded)
+}
+
+/// Percent-encodes everything except ASCII alphanumerics, `-`, `_` and `.`.
+pub(crate) fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+pub(crate) fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Narrows down exported definitions to a subset of virtual hosts, kinds of
+/// virtual host-scoped objects, and object names.
+#[derive(Debug, Default)]
+pub struct DefinitionFilter {
+    /// Only keep virtual hosts (and objects in them) whose names match
+    pub include_vhosts: Option<Regex>,
+    /// Drop virtual hosts (and objects in them) whose names match
+    pub exclude_vhosts: Option<Regex>,
+    /// Only keep these virtual host-scoped kinds
+    pub include_kinds: Option<Vec<DefinitionKind>>,
+    /// Drop these virtual host-scoped kinds
+    pub exclude_kinds: Vec<DefinitionKind>,
+    /// Only keep virtual host-scoped objects whose names match
+    pub include_names: Option<Regex>,
+    /// Drop virtual host-scoped objects whose names match
+    pub exclude_names: Option<Regex>,
+}
+
+impl DefinitionFilter {
+    pub fn is_empty(&self) -> bool {
+        self.include_vhosts.is_none()
+            && self.exclude_vhosts.is_none()
+            && self.include_kinds.is_none()
+            && self.exclude_kinds.is_empty()
+            && self.include_names.is_none()
+            && self.exclude_names.is_none()
+    }
+
+    pub fn apply(&self, defs: &mut Value) {
+        let vhost_is_kept = |vhost: &str| {
+            self.include_vhosts
+                .as_ref()
+                .is_none_or(|re| re.is_match(vhost))
+                && !self
+                    .exclude_vhosts
+                    .as_ref()
+                    .is_some_and(|re| re.is_match(vhost))
+        };
+
+        if let Some(vhosts) = collection_mut(defs, DefinitionKind::Vhosts) {
+            vhosts.retain(|vh| str_field(vh, "name").is_none_or(vhost_is_kept));
+        }
+        for kind in DefinitionKind::ALL {
+            if let Some(items) = collection_mut(defs, kind) {
+                items.retain(|obj| vhost_of(obj).is_none_or(vhost_is_kept));
+            }
+        }
+
+        for kind in VHOST_SCOPED_KINDS {
+            let Some(items) = collection_mut(defs, kind) else {
+                continue;
+            };
+
+            let kind_is_kept = self
+                .include_kinds
+                .as_ref()
+                .is_none_or(|kinds| kinds.contains(&kind))
+                && !self.exclude_kinds.contains(&kind);
+            if !kind_is_kept {
+                items.clear();
+                continue;
+            }
+
+            items.retain(|obj| {
+                self.include_names
+                    .as_ref()
+                    .is_none_or(|re| kind.name_matches(obj, re))
+                    && !self
+                        .exclude_names
+                        .as_ref()
+                        .is_some_and(|re| kind.name_matches(obj, re))
+            });
+        }
+    }
+}
+
+/// What importing a definitions document would do to an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    /// The object does not exist and would be created
+    Create,
+    /// An object with the same natural key exists and would be updated
+    Update,
+}
+
+impl fmt::Display for ChangeAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeAction::Create => write!(f, "create"),
+            ChangeAction::Update => write!(f, "update"),
+        }
+    }
+}
+
+/// A single entry of an import plan produced by [`plan_import`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Tabled)]
+pub struct DefinitionChange {
+    pub action: ChangeAction,
+    pub kind: String,
+    pub vhost: String,
+    pub name: String,
+}
+
+/// Compares a definitions document with the definitions of the cluster it would be
+/// imported into. Objects are matched by their natural key and compared in canonical form,
+/// by their defining fields only (see [`DefinitionKind::differs`]).
+///
+/// Importing definitions never deletes anything, so objects that only exist
+/// in `existing` are not part of the plan, and neither are identical objects.
+pub fn plan_import(incoming: &Value, existing: &Value) -> Vec<DefinitionChange> {
+    let mut incoming = incoming.clone();
+    let mut existing = existing.clone();
+    canonicalize(&mut incoming, true);
+    canonicalize(&mut existing, true);
+
+    let mut changes = Vec::new();
+    for kind in DefinitionKind::ALL {
+        let Some(items) = collection_mut(&mut incoming, kind) else {
+            continue;
+        };
+        let current: HashMap<Vec<String>, &Value> = collection(&existing, kind)
+            .iter()
+            .map(|obj| (kind.natural_key(obj), obj))
+            .collect();
+
+        for obj in items.iter() {
+            let action = match current.get(&kind.natural_key(obj)) {
+                None => ChangeAction::Create,
+                Some(other) if kind.differs(other, obj) => ChangeAction::Update,
+                Some(_) => continue,
+            };
+            changes.push(DefinitionChange {
+                action,
+                kind: kind.key().to_owned(),
+                vhost: vhost_of(obj).unwrap_or_default().to_owned(),
+                name: kind.display_name(obj),
+            });
+        }
+    }
+    changes
+}
+
+/// Returns the queues and exchanges in `incoming` that already exist with different properties.
+/// Unlike other objects, these cannot be updated by an import.
+pub fn find_conflicts(incoming: &Value, existing: &Value) -> Vec<DefinitionChange> {
+    plan_import(incoming, existing)
+        .into_iter()
+        .filter(|change| {
+            change.action == ChangeAction::Update
+                && [DefinitionKind::Queues, DefinitionKind::Exchanges]
+                    .iter()
+                    .any(|kind| kind.key() == change.kind)
+        })
+        .collect()
+}
+
+/// How an object-by-object import treats conflicting queues and exchanges (see [`find_conflicts`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Report the object as failed
+    #[default]
+    Fail,
+    /// Leave the existing object alone
+    Skip,
+    /// Delete the existing object and import the new one
+    Recreate,
+}
+
+impl From<&str> for ConflictStrategy {
+    fn from(value: &str) -> Self {
+        match value {
+            "skip" => ConflictStrategy::Skip,
+            "recreate" => ConflictStrategy::Recreate,
+            _ => ConflictStrategy::Fail,
+        }
+    }
+}
+
+/// A single object from a definitions document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefinitionObject {
+    pub kind: DefinitionKind,
+    pub definition: Value,
+}
+
+impl DefinitionObject {
+    pub fn vhost(&self) -> Option<&str> {
+        vhost_of(&self.definition)
+    }
+
+    pub fn name(&self) -> String {
+        self.kind.display_name(&self.definition)
+    }
+
+    /// Returns a definitions document that only contains this object.
+    pub fn to_document(&self) -> Value {
+        let mut map = Map::new();
+        map.insert(
+            self.kind.key().to_owned(),
+            Value::Array(vec![self.definition.clone()]),
+        );
+        Value::Object(map)
+    }
+
+    pub fn conflicts_with(&self, change: &DefinitionChange) -> bool {
+        self.kind.key() == change.kind
+            && self.vhost().unwrap_or_default() == change.vhost
+            && self.name() == change.name
+    }
+}
+
+impl fmt::Display for DefinitionObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} '{}'", self.kind.key(), self.name())?;
+        match self.vhost() {
+            Some(vhost) => write!(f, " in virtual host '{}'", vhost),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Splits a definitions document into individual objects, ordered so that
+/// every object comes after the objects it depends on (e.g. bindings after queues and exchanges).
+pub fn split_into_objects(defs: &Value) -> Vec<DefinitionObject> {
+    DefinitionKind::ALL
+        .into_iter()
+        .flat_map(|kind| {
+            collection(defs, kind)
+                .iter()
+                .map(move |obj| DefinitionObject {
+                    kind,
+                    definition: obj.clone(),
+                })
+        })
+        .collect()
+}
+
+/// Definitions files come in two formats: cluster-wide and virtual host-specific.
+/// Objects in the latter have no `vhost` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionsFormat {
+    ClusterWide,
+    VirtualHost,
+}
+
+impl fmt::Display for DefinitionsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefinitionsFormat::ClusterWide => write!(f, "cluster-wide"),
+            DefinitionsFormat::VirtualHost => write!(f, "virtual host-specific"),
+        }
+    }
+}
+
+/// Returns the format of a definitions document, or `None` if it does not contain
+/// anything that tells the two formats apart (e.g. it only has empty collections).
+pub fn detect_format(defs: &Value) -> Option<DefinitionsFormat> {
+    if DefinitionKind::ALL
+        .into_iter()
+        .filter(|kind| !VHOST_SCOPED_KINDS.contains(kind))
+        .any(|kind| !collection(defs, kind).is_empty())
+    {
+        return Some(DefinitionsFormat::ClusterWide);
+    }
+
+    VHOST_SCOPED_KINDS
+        .into_iter()
+        .flat_map(|kind| collection(defs, kind).first())
+        .next()
+        .map(|obj| match vhost_of(obj) {
+            Some(_) => DefinitionsFormat::ClusterWide,
+            None => DefinitionsFormat::VirtualHost,
+        })
+}
+
+/// A definitions document and where it came from, e.g. a file path.
+#[derive(Debug, Clone)]
+pub struct DefinitionsFragment {
+    pub source: String,
+    pub definitions: Value,
+}
+
+/// Two fragments define the same object (the same natural key) differently.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub object: DefinitionObject,
+    pub first_source: String,
+    pub second_source: String,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is defined differently in {} and {}",
+            self.object, self.first_source, self.second_source
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeError {
+    MixedFormats {
+        first_source: String,
+        first_format: DefinitionsFormat,
+        second_source: String,
+        second_format: DefinitionsFormat,
+    },
+    Conflicts(Vec<MergeConflict>),
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::MixedFormats {
+                first_source,
+                first_format,
+                second_source,
+                second_format,
+            } => write!(
+                f,
+                "cannot merge {} definitions ({}) with {} definitions ({})",
+                first_format, first_source, second_format, second_source
+            ),
+            MergeError::Conflicts(conflicts) => {
+                write!(f, "found {} conflicting object(s):", conflicts.len())?;
+                for conflict in conflicts {
+                    write!(f, "\n * {}", conflict)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Merges definitions fragments of the same format.
+///
+/// Collections are unioned. Objects with the same natural key must be identical
+/// (in canonical form) and are only included once. Other top-level keys, such as
+/// `rabbitmq_version`, are taken from the first fragment that has them.
+pub fn merge(fragments: &[DefinitionsFragment]) -> Result<Value, MergeError> {
+    let mut format: Option<(&str, DefinitionsFormat)> = None;
+    for fragment in fragments {
+        let Some(this) = detect_format(&fragment.definitions) else {
+            continue;
+        };
+        match format {
+            Some((first_source, first_format)) if first_format != this => {
+                return Err(MergeError::MixedFormats {
+                    first_source: first_source.to_owned(),
+                    first_format,
+                    second_source: fragment.source.clone(),
+                    second_format: this,
+                });
+            }
+            Some(_) => {}
+            None => format = Some((&fragment.source, this)),
+        }
+    }
+
+    let mut merged = Map::new();
+    for fragment in fragments {
+        if let Some(map) = fragment.definitions.as_object() {
+            for (key, value) in map {
+                let is_collection = key == "virtual_hosts" || key.parse::<DefinitionKind>().is_ok();
+                if !is_collection && !merged.contains_key(key) {
+                    merged.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    for kind in DefinitionKind::ALL {
+        let mut items: Vec<Value> = Vec::new();
+        // natural key => (canonical form, source)
+        let mut seen: HashMap<Vec<String>, (Value, &str)> = HashMap::new();
+        let mut present = false;
+
+        for fragment in fragments {
+            let collection_present = fragment.definitions.get(kind.key()).is_some()
+                || (kind == DefinitionKind::Vhosts
+                    && fragment.definitions.get("virtual_hosts").is_some());
+            present |= collection_present;
+
+            for obj in collection(&fragment.definitions, kind) {
+                let canonical = canonical_object(kind, obj);
+                match seen.get(&kind.natural_key(obj)) {
+                    None => {
+                        seen.insert(kind.natural_key(obj), (canonical, &fragment.source));
+                        items.push(obj.clone());
+                    }
+                    Some((other, _)) if *other == canonical => {}
+                    Some((_, first_source)) => conflicts.push(MergeConflict {
+                        object: DefinitionObject {
+                            kind,
+                            definition: obj.clone(),
+                        },
+                        first_source: (*first_source).to_owned(),
+                        second_source: fragment.source.clone(),
+                    }),
+                }
+            }
+        }
+
+        if present {
+            merged.insert(kind.key().to_owned(), Value::Array(items));
+        }
+    }
+
+    if conflicts.is_empty() {
+        Ok(Value::Object(merged))
+    } else {
+        Err(MergeError::Conflicts(conflicts))
+    }
+}
+
+fn canonical_object(kind: DefinitionKind, obj: &Value) -> Value {
+    let mut doc = DefinitionObject {
+        kind,
+        definition: obj.clone(),
+    }
+    .to_document();
+    canonicalize(&mut doc, true);
+    collection(&doc, kind).first().cloned().unwrap_or_default()
+}
+
+/// Returns a top-level collection, or an empty slice if it is not present.
+pub fn collection(defs: &Value, kind: DefinitionKind) -> &[Value] {
+    let items = match defs.get(kind.key()) {
+        None if kind == DefinitionKind::Vhosts => defs.get("virtual_hosts"),
+        other => other,
+    };
+    items
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
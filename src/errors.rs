@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::health_checks::{HealthCheckFailure, ThresholdCheckFailure};
 use rabbitmq_http_client::blocking_api::EndpointValidationError;
 use rabbitmq_http_client::error::{ConversionError, Error as ApiClientError, ErrorDetails};
 use rabbitmq_http_client::{blocking_api::HttpClientError, responses::HealthCheckFailureDetails};
@@ -34,10 +35,30 @@ pub struct HttpErrorInfo {
 #[allow(dead_code)]
 pub struct HealthCheckInfo {
     pub health_check_path: String,
-    pub details: HealthCheckFailureDetails,
+    pub details: HealthCheckDetails,
     pub status_code: StatusCode,
 }
 
+/// Failure details of a health check: those of a check the HTTP API client
+/// supports, of one performed by [`HealthCheckClient`](crate::health_checks::HealthCheckClient),
+/// or of a threshold-based one computed locally.
+#[derive(Debug, Clone)]
+pub enum HealthCheckDetails {
+    Client(HealthCheckFailureDetails),
+    Extended(HealthCheckFailure),
+    Threshold(ThresholdCheckFailure),
+}
+
+impl HealthCheckDetails {
+    pub fn reason(&self) -> String {
+        match self {
+            HealthCheckDetails::Client(details) => details.reason(),
+            HealthCheckDetails::Extended(details) => details.reason.clone(),
+            HealthCheckDetails::Threshold(details) => details.reason.clone(),
+        }
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum CommandRunError {
     #[error("Asked to run an unknown command '{command} {subcommand}'")]
@@ -84,6 +105,8 @@ pub enum CommandRunError {
     ConflictingOptions { message: String },
     #[error("{message}")]
     MissingOptions { message: String },
+    #[error("{message}")]
+    InsufficientPrivileges { message: String },
     #[error("Missing argument value for property (field) {property}")]
     MissingArgumentValue { property: String },
     #[error("Unsupported argument value for property (field) {property}")]
@@ -123,7 +146,11 @@ impl From<HttpClientError> for CommandRunError {
                 Self::ServerError(Box::new(HttpErrorInfo { status_code, url, body, error_details, headers }))
             }
             ApiClientError::HealthCheckFailed { path, details, status_code } => {
-                Self::HealthCheckFailed(Box::new(HealthCheckInfo { health_check_path: path, details, status_code }))
+                Self::HealthCheckFailed(Box::new(HealthCheckInfo {
+                    health_check_path: path,
+                    details: HealthCheckDetails::Client(details),
+                    status_code,
+                }))
             }
             ApiClientError::NotFound => Self::NotFound,
             ApiClientError::MultipleMatchingBindings => Self::ConflictingOptions {
@@ -0,0 +1,500 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `health_check all`: runs a number of health checks in one process
+//! and aggregates their outcomes into a single report.
+//!
+//! Also performs the health checks the HTTP API client does not support,
+//! and the threshold-based ones computed from the queues and connections it lists.
+
+use crate::errors::{CommandRunError, HealthCheckDetails, HealthCheckInfo, HttpErrorInfo};
+use rabbitmq_http_client::error::ErrorDetails;
+use rabbitmq_http_client::responses::{Connection, QueueInfo};
+use regex::Regex;
+use reqwest::StatusCode;
+use reqwest::blocking::Client as HTTPClient;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tabled::Tabled;
+
+/// A health check that `health_check all` can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthCheck {
+    LocalAlarms,
+    ClusterWideAlarms,
+    NodeIsQuorumCritical,
+    DeprecatedFeaturesInUse,
+    PortListener,
+    ProtocolListener,
+    CertificateExpiration,
+    VirtualHosts,
+    ReadyToServeClients,
+    IsInService,
+    QueueDepth,
+    Consumers,
+    Unacked,
+    Connections,
+}
+
+impl HealthCheck {
+    /// All checks, in the order they are run by default.
+    pub const ALL: [HealthCheck; 14] = [
+        HealthCheck::LocalAlarms,
+        HealthCheck::ClusterWideAlarms,
+        HealthCheck::NodeIsQuorumCritical,
+        HealthCheck::DeprecatedFeaturesInUse,
+        HealthCheck::PortListener,
+        HealthCheck::ProtocolListener,
+        HealthCheck::CertificateExpiration,
+        HealthCheck::VirtualHosts,
+        HealthCheck::ReadyToServeClients,
+        HealthCheck::IsInService,
+        HealthCheck::QueueDepth,
+        HealthCheck::Consumers,
+        HealthCheck::Unacked,
+        HealthCheck::Connections,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HealthCheck::LocalAlarms => "local_alarms",
+            HealthCheck::ClusterWideAlarms => "cluster_wide_alarms",
+            HealthCheck::NodeIsQuorumCritical => "node_is_quorum_critical",
+            HealthCheck::DeprecatedFeaturesInUse => "deprecated_features_in_use",
+            HealthCheck::PortListener => "port_listener",
+            HealthCheck::ProtocolListener => "protocol_listener",
+            HealthCheck::CertificateExpiration => "certificate_expiration",
+            HealthCheck::VirtualHosts => "virtual_hosts",
+            HealthCheck::ReadyToServeClients => "ready_to_serve_clients",
+            HealthCheck::IsInService => "is_in_service",
+            HealthCheck::QueueDepth => "queue_depth",
+            HealthCheck::Consumers => "consumers",
+            HealthCheck::Unacked => "unacked",
+            HealthCheck::Connections => "connections",
+        }
+    }
+
+    pub fn names() -> Vec<&'static str> {
+        Self::ALL.iter().map(HealthCheck::name).collect()
+    }
+}
+
+impl fmt::Display for HealthCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for HealthCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|check| check.name() == s.trim())
+            .ok_or_else(|| {
+                format!(
+                    "unknown health check '{}', supported checks are: {}",
+                    s.trim(),
+                    Self::names().join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    /// Not run because an earlier check failed
+    NotRun,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Passed => write!(f, "passed"),
+            CheckStatus::Failed => write!(f, "failed"),
+            CheckStatus::NotRun => write!(f, "not run"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Tabled)]
+pub struct CheckResult {
+    pub check: String,
+    pub status: CheckStatus,
+    #[tabled(rename = "duration (ms)")]
+    pub duration_ms: u64,
+    pub details: String,
+}
+
+/// The outcome of every selected check. The report is healthy
+/// when no check failed.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheckReport {
+    pub healthy: bool,
+    pub results: Vec<CheckResult>,
+}
+
+/// Runs `checks` in order using `run`, which returns the failure details of a failed check.
+/// Unless `continue_on_failure` is set, the checks after the first failed one are not run,
+/// the same way `--fail-fast` stops bulk operations.
+pub fn run_checks<F>(
+    checks: &[HealthCheck],
+    continue_on_failure: bool,
+    mut run: F,
+) -> HealthCheckReport
+where
+    F: FnMut(HealthCheck) -> Result<(), String>,
+{
+    let mut results = Vec::with_capacity(checks.len());
+    let mut stopped = false;
+
+    for check in checks {
+        if stopped {
+            results.push(CheckResult {
+                check: check.to_string(),
+                status: CheckStatus::NotRun,
+                duration_ms: 0,
+                details: "an earlier check failed".to_string(),
+            });
+            continue;
+        }
+
+        let started = Instant::now();
+        let outcome = run(*check);
+        let duration_ms = started.elapsed().as_millis() as u64;
+        let (status, details) = match outcome {
+            Ok(()) => (CheckStatus::Passed, String::new()),
+            Err(details) => {
+                stopped = !continue_on_failure;
+                (CheckStatus::Failed, details)
+            }
+        };
+        results.push(CheckResult {
+            check: check.to_string(),
+            status,
+            duration_ms,
+            details,
+        });
+    }
+
+    HealthCheckReport {
+        healthy: results.iter().all(|r| r.status != CheckStatus::Failed),
+        results,
+    }
+}
+
+//
+// Health checks not supported by the HTTP API client
+//
+
+/// Health checks performed by [`HealthCheckClient`]. `health_check all` runs them, too.
+pub const EXTENDED_HEALTH_CHECKS: [&str; 4] = [
+    "certificate_expiration",
+    "virtual_hosts",
+    "ready_to_serve_clients",
+    "is_in_service",
+];
+
+/// Units `health_check certificate_expiration --unit` accepts.
+pub const CERTIFICATE_EXPIRATION_UNITS: [&str; 4] = ["days", "weeks", "months", "years"];
+
+/// Failure details of the health checks performed by [`HealthCheckClient`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct HealthCheckFailure {
+    pub reason: String,
+    /// Listeners with certificates that expire within the given period (`certificate_expiration`)
+    pub expired: Vec<ExpiringListener>,
+    /// Virtual hosts that are down (`virtual_hosts`)
+    #[serde(alias = "virtual-hosts")]
+    pub virtual_hosts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ExpiringListener {
+    pub node: String,
+    pub protocol: String,
+    pub interface: String,
+    pub port: u32,
+    pub certfile: String,
+    pub expiry_date: String,
+}
+
+/// Performs health checks using the same HTTP client, endpoint and credentials
+/// as the HTTP API client, for the endpoints that client does not support.
+pub struct HealthCheckClient {
+    http_client: HTTPClient,
+    endpoint: String,
+    username: String,
+    password: String,
+    timeout: Duration,
+}
+
+impl HealthCheckClient {
+    pub fn new(
+        http_client: HTTPClient,
+        endpoint: String,
+        username: String,
+        password: String,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            http_client,
+            endpoint,
+            username,
+            password,
+            timeout,
+        }
+    }
+
+    /// Fails if any of the TLS-enabled listeners of the target node use a certificate
+    /// that expires within the given period.
+    pub fn certificate_expiration(&self, within: u32, unit: &str) -> Result<(), CommandRunError> {
+        self.health_check(&format!(
+            "health/checks/certificate-expiration/{}/{}",
+            within, unit
+        ))
+    }
+
+    /// Fails if any of the virtual hosts are down on the target node.
+    pub fn virtual_hosts(&self) -> Result<(), CommandRunError> {
+        self.health_check("health/checks/virtual-hosts")
+    }
+
+    /// Fails if the target node is not ready to serve clients: it is booting, in maintenance
+    /// mode, or its listeners are not running.
+    pub fn ready_to_serve_clients(&self) -> Result<(), CommandRunError> {
+        self.health_check("health/checks/ready-to-serve-clients")
+    }
+
+    /// Fails if the target node is not in service, e.g. because it is in maintenance mode.
+    pub fn is_in_service(&self) -> Result<(), CommandRunError> {
+        self.health_check("health/checks/is-in-service")
+    }
+
+    fn health_check(&self, path: &str) -> Result<(), CommandRunError> {
+        let url = format!("{}/{}", self.endpoint.trim_end_matches('/'), path);
+        let response = self
+            .http_client
+            .get(&url)
+            .basic_auth(&self.username, Some(&self.password))
+            .timeout(self.timeout)
+            .send()
+            .map_err(|error| CommandRunError::RequestError { error })?;
+
+        let status_code = response.status();
+        if status_code.is_success() {
+            return Ok(());
+        }
+        // Health checks respond with a 503 Service Unavailable when they fail,
+        // other responses are reported like any other API error
+        if status_code == StatusCode::SERVICE_UNAVAILABLE {
+            let details: HealthCheckFailure = response
+                .json()
+                .map_err(|error| CommandRunError::RequestError { error })?;
+            return Err(CommandRunError::HealthCheckFailed(Box::new(
+                HealthCheckInfo {
+                    health_check_path: path.to_owned(),
+                    details: HealthCheckDetails::Extended(details),
+                    status_code,
+                },
+            )));
+        }
+        if status_code == StatusCode::NOT_FOUND {
+            return Err(CommandRunError::NotFound);
+        }
+
+        let headers = response.headers().clone();
+        let body = response.text().ok();
+        let info = Box::new(HttpErrorInfo {
+            status_code,
+            url: url.parse().ok(),
+            error_details: body.as_deref().and_then(ErrorDetails::from_json),
+            body,
+            headers: Some(headers),
+        });
+        if status_code.is_client_error() {
+            Err(CommandRunError::ClientError(info))
+        } else {
+            Err(CommandRunError::ServerError(info))
+        }
+    }
+}
+
+//
+// Threshold-based health checks
+//
+
+/// Failure details of the threshold-based health checks. Unlike other health checks,
+/// these are computed locally from the queues or connections the HTTP API lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdCheckFailure {
+    pub reason: String,
+    /// What the value of each violation counts, e.g. "messages" or "connections"
+    pub metric: &'static str,
+    pub violations: Vec<ThresholdViolation>,
+}
+
+/// An object whose metric is above the maximum (or below the minimum) of a check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdViolation {
+    /// A description of the object, e.g. "queue 'orders' in virtual host '/'" or "user 'app'"
+    pub object: String,
+    pub value: u64,
+}
+
+impl ThresholdCheckFailure {
+    /// Converts the failure into the error the other health checks fail with.
+    /// There is no health check endpoint to report, so the name of the check is used instead.
+    pub fn into_error(self, check: HealthCheck) -> CommandRunError {
+        CommandRunError::HealthCheckFailed(Box::new(HealthCheckInfo {
+            health_check_path: check.name().to_owned(),
+            details: HealthCheckDetails::Threshold(self),
+            status_code: StatusCode::SERVICE_UNAVAILABLE,
+        }))
+    }
+}
+
+/// Fails if any of the queues matching `name_pattern` (all queues, if `None`)
+/// have more than `max` messages.
+pub fn check_queue_depth(
+    queues: &[QueueInfo],
+    max: u64,
+    name_pattern: Option<&Regex>,
+) -> Result<(), ThresholdCheckFailure> {
+    check_queues(
+        queues,
+        name_pattern,
+        |q| q.message_count,
+        |n| n > max,
+        "messages",
+        |count| format!("{} queue(s) have more than {} messages", count, max),
+    )
+}
+
+/// Fails if any of the queues matching `name_pattern` (all queues, if `None`)
+/// have fewer than `min` consumers.
+pub fn check_consumers(
+    queues: &[QueueInfo],
+    min: u64,
+    name_pattern: Option<&Regex>,
+) -> Result<(), ThresholdCheckFailure> {
+    check_queues(
+        queues,
+        name_pattern,
+        |q| q.consumer_count as u64,
+        |n| n < min,
+        "consumers",
+        |count| format!("{} queue(s) have fewer than {} consumers", count, min),
+    )
+}
+
+/// Fails if any of the queues matching `name_pattern` (all queues, if `None`)
+/// have more than `max` unacknowledged messages.
+pub fn check_unacknowledged_messages(
+    queues: &[QueueInfo],
+    max: u64,
+    name_pattern: Option<&Regex>,
+) -> Result<(), ThresholdCheckFailure> {
+    check_queues(
+        queues,
+        name_pattern,
+        |q| q.unacknowledged_message_count,
+        |n| n > max,
+        "unacknowledged messages",
+        |count| {
+            format!(
+                "{} queue(s) have more than {} unacknowledged messages",
+                count, max
+            )
+        },
+    )
+}
+
+/// Fails if any user has more than `max_per_user` connections open.
+pub fn check_connections_per_user(
+    connections: &[Connection],
+    max_per_user: u64,
+) -> Result<(), ThresholdCheckFailure> {
+    let mut per_user: BTreeMap<&str, u64> = BTreeMap::new();
+    for conn in connections {
+        *per_user.entry(conn.username.as_str()).or_default() += 1;
+    }
+
+    let violations: Vec<ThresholdViolation> = per_user
+        .into_iter()
+        .filter(|(_, count)| *count > max_per_user)
+        .map(|(user, count)| ThresholdViolation {
+            object: format!("user '{}'", user),
+            value: count,
+        })
+        .collect();
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    Err(ThresholdCheckFailure {
+        reason: format!(
+            "{} user(s) have more than {} connections",
+            violations.len(),
+            max_per_user
+        ),
+        metric: "connections",
+        violations,
+    })
+}
+
+fn check_queues<V, P, R>(
+    queues: &[QueueInfo],
+    name_pattern: Option<&Regex>,
+    value_of: V,
+    violates: P,
+    metric: &'static str,
+    reason: R,
+) -> Result<(), ThresholdCheckFailure>
+where
+    V: Fn(&QueueInfo) -> u64,
+    P: Fn(u64) -> bool,
+    R: FnOnce(usize) -> String,
+{
+    let mut offending: Vec<&QueueInfo> = queues
+        .iter()
+        .filter(|q| name_pattern.is_none_or(|re| re.is_match(&q.name)))
+        .filter(|q| violates(value_of(q)))
+        .collect();
+    if offending.is_empty() {
+        return Ok(());
+    }
+    offending.sort_by(|a, b| (&a.vhost, &a.name).cmp(&(&b.vhost, &b.name)));
+
+    Err(ThresholdCheckFailure {
+        reason: reason(offending.len()),
+        metric,
+        violations: offending
+            .into_iter()
+            .map(|q| ThresholdViolation {
+                object: format!("queue '{}' in virtual host '{}'", q.name, q.vhost),
+                value: value_of(q),
+            })
+            .collect(),
+    })
+}
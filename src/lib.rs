@@ -13,12 +13,22 @@
 // limitations under the License.
 
 pub mod arg_helpers;
+pub mod audit;
 pub mod bulk;
 pub mod columns;
 pub mod config;
 pub mod constants;
+pub mod definitions;
 pub mod errors;
 pub mod exit_code;
+pub mod health_checks;
 pub mod output;
+pub mod passwords;
+pub mod permission_check;
 pub mod pre_flight;
+pub mod shovel_migration;
 pub mod tables;
+pub mod tenants;
+pub mod transformations;
+pub mod users;
+pub mod vhost_cloning;
@@ -28,27 +28,38 @@ use crate::exit_code::Outcome;
 use rustls::pki_types::pem::PemObject;
 
 mod arg_helpers;
+mod audit;
 mod bulk;
 mod cli;
 mod columns;
 mod commands;
 mod config;
 mod constants;
+mod definitions;
 mod errors;
 mod exit_code;
+mod health_checks;
 mod output;
+mod passwords;
+mod permission_check;
 pub mod pre_flight;
+mod shovel_migration;
 mod static_urls;
 mod tables;
 mod tanzu_cli;
 mod tanzu_commands;
+mod tenants;
+mod transformations;
+mod users;
+mod vhost_cloning;
 
 use crate::cli::CompletionShell;
 use crate::config::{PreFlightSettings, SharedSettings};
 use crate::constants::{
-    DEFAULT_CONFIG_FILE_PATH, DEFAULT_HTTPS_PORT, DEFAULT_NODE_ALIAS, DEFAULT_VHOST,
+    DEFAULT_CONFIG_FILE_PATH, DEFAULT_HOST, DEFAULT_HTTPS_PORT, DEFAULT_NODE_ALIAS, DEFAULT_VHOST,
     TANZU_COMMAND_PREFIX,
 };
+use crate::health_checks::HealthCheckClient;
 use crate::output::*;
 use rabbitmq_http_client::blocking_api::{
     Client as GenericAPIClient, ClientBuilder, EndpointValidationError,
@@ -87,6 +98,13 @@ fn run() -> Outcome {
         return Outcome::from(dispatch_shell_command(shell_args, pre_flight_settings));
     }
 
+    // `definitions copy` connects to two nodes from the configuration file instead of --node
+    if let Some(("definitions", definitions_args)) = cli.subcommand()
+        && let Some(("copy", copy_args)) = definitions_args.subcommand()
+    {
+        return dispatch_definitions_copy_command(&cli, copy_args);
+    }
+
     let (common_settings, endpoint) = match resolve_run_configuration(&cli) {
         Ok(result) => result,
         Err(code) => return Outcome::from(code),
@@ -99,7 +117,35 @@ fn run() -> Outcome {
     }
 
     match configure_http_api_client(&cli, &common_settings, &endpoint.clone()) {
-        Ok(client) => dispatch_command(&cli, client, &common_settings),
+        // `shovels migrate_cluster` also connects to the target cluster's node from the configuration file
+        Ok(client) => match cli.subcommand() {
+            Some(("shovels", shovels_args)) => match shovels_args.subcommand() {
+                Some(("migrate_cluster", migrate_args)) => {
+                    dispatch_shovel_migration_command(&cli, migrate_args, client, &common_settings)
+                }
+                _ => dispatch_command(&cli, client, &common_settings),
+            },
+            // `users rotate_password` may also update the configuration file
+            Some(("users", users_args)) => match users_args.subcommand() {
+                Some(("rotate_password", rotate_args)) => {
+                    dispatch_password_rotation_command(&cli, rotate_args, client, &common_settings)
+                }
+                _ => dispatch_command(&cli, client, &common_settings),
+            },
+            // some health checks use endpoints the HTTP API client does not support
+            Some(("health_check", health_check_args)) => match health_check_args.subcommand() {
+                Some(("all", all_args)) => {
+                    dispatch_health_check_all_command(&cli, all_args, client, &common_settings)
+                }
+                Some((name, check_args))
+                    if health_checks::EXTENDED_HEALTH_CHECKS.contains(&name) =>
+                {
+                    dispatch_extended_health_check_command(&cli, name, check_args, &common_settings)
+                }
+                _ => dispatch_command(&cli, client, &common_settings),
+            },
+            _ => dispatch_command(&cli, client, &common_settings),
+        },
         Err(err) => {
             let mut res_handler = ResultHandler::new(&common_settings, &cli);
             res_handler.report_pre_command_run_error(&err);
@@ -178,6 +224,25 @@ fn configure_http_api_client<'a>(
     Ok(client)
 }
 
+fn configure_health_check_client(
+    cli: &ArgMatches,
+    merged_settings: &SharedSettings,
+) -> Result<HealthCheckClient, CommandRunError> {
+    let httpc = build_http_client(cli, merged_settings)?;
+    // Due to how SharedSettings are computed, these should be safe to unwrap()
+    let username = merged_settings.username.clone().unwrap();
+    let password = merged_settings.password.clone().unwrap();
+    let timeout_secs = cli.get_one::<u64>("timeout").copied().unwrap_or(60);
+
+    Ok(HealthCheckClient::new(
+        httpc,
+        merged_settings.endpoint(),
+        username,
+        password,
+        Duration::from_secs(timeout_secs),
+    ))
+}
+
 fn dispatch_config_file_command(cli: &ArgMatches, config_file_args: &ArgMatches) -> ExitCode {
     let config_file_path = cli
         .get_one::<PathBuf>("config_file_path")
@@ -220,6 +285,267 @@ fn dispatch_config_file_command(cli: &ArgMatches, config_file_args: &ArgMatches)
     res_handler.exit_code.unwrap_or(ExitCode::Usage)
 }
 
+fn dispatch_definitions_copy_command(cli: &ArgMatches, copy_args: &ArgMatches) -> Outcome {
+    let settings_of = |arg: &str| {
+        let alias = copy_args
+            .get_one::<String>(arg)
+            .cloned()
+            .unwrap_or_default();
+        resolve_node_alias_configuration(cli, &alias)
+    };
+    let (source_settings, destination_settings) =
+        match (settings_of("from_node"), settings_of("to_node")) {
+            (Ok(source), Ok(destination)) => (source, destination),
+            (Err(err), _) | (_, Err(err)) => {
+                let common_settings = SharedSettings::default();
+                let mut res_handler = ResultHandler::new(&common_settings, copy_args);
+                res_handler.report_pre_command_run_error(&err);
+                return res_handler.final_outcome_or(ExitCode::DataErr);
+            }
+        };
+
+    let mut res_handler = ResultHandler::new(&destination_settings, copy_args);
+    let clients = configure_http_api_client(cli, &source_settings, &source_settings.endpoint())
+        .and_then(|source| {
+            let destination = configure_http_api_client(
+                cli,
+                &destination_settings,
+                &destination_settings.endpoint(),
+            )?;
+            Ok((source, destination))
+        });
+    let (source, destination) = match clients {
+        Ok(pair) => pair,
+        Err(err) => {
+            res_handler.report_pre_command_run_error(&err);
+            return res_handler.final_outcome_or(ExitCode::DataErr);
+        }
+    };
+
+    // The plan is displayed before anything is imported
+    match commands::plan_definitions_copy(&source, &destination, copy_args) {
+        Ok(plan) => {
+            res_handler.tabular_result(Ok(plan.changes.clone()));
+            if !copy_args.get_flag("dry_run") {
+                let result = commands::apply_definitions_copy(&destination, plan, copy_args);
+                res_handler.no_output_on_success(result);
+            }
+        }
+        Err(err) => res_handler.no_output_on_success::<()>(Err(err)),
+    }
+    res_handler.final_outcome_or(ExitCode::Usage)
+}
+
+fn dispatch_shovel_migration_command(
+    cli: &ArgMatches,
+    migrate_args: &ArgMatches,
+    client: APIClient,
+    common_settings: &SharedSettings,
+) -> Outcome {
+    let mut res_handler = ResultHandler::new(common_settings, migrate_args);
+    if migrate_args.get_flag("status") {
+        let result = commands::shovel_migration_status(client, migrate_args);
+        res_handler.tabular_result(result);
+        return res_handler.final_outcome_or(ExitCode::Usage);
+    }
+
+    let alias = migrate_args
+        .get_one::<String>("to_node")
+        .cloned()
+        .unwrap_or_default();
+    let target = resolve_node_alias_configuration(cli, &alias).and_then(|settings| {
+        let target_client = configure_http_api_client(cli, &settings, &settings.endpoint())?;
+        let base_uri = match migrate_args.get_one::<String>("destination_uri") {
+            Some(uri) => uri.clone(),
+            None => shovel_migration::amqp_base_uri(
+                settings.hostname.as_deref().unwrap_or(DEFAULT_HOST),
+                settings.username.as_deref().unwrap_or_default(),
+                settings.password.as_deref().unwrap_or_default(),
+                settings.tls,
+            )
+            .map_err(|e| CommandRunError::FailureDuringExecution {
+                message: format!(
+                    "Could not build an AMQP 0-9-1 URI for node alias '{}': {}",
+                    alias, e
+                ),
+            })?
+            .to_string(),
+        };
+        Ok((target_client, base_uri))
+    });
+    let (target_client, base_uri) = match target {
+        Ok(pair) => pair,
+        Err(err) => {
+            res_handler.report_pre_command_run_error(&err);
+            return res_handler.final_outcome_or(ExitCode::DataErr);
+        }
+    };
+
+    let output = migrate_args.get_one::<String>("output").cloned();
+    let opts = BulkReportOpts {
+        strict: migrate_args.get_flag("strict"),
+        detailed_exit_codes: migrate_args.get_flag("detailed_exit_codes"),
+        output_format: BulkOutputFormat::parse(output.as_deref()),
+    };
+    let mut prog_rep = res_handler.instantiate_progress_reporter();
+    let result = commands::migrate_cluster_with_shovels(
+        &client,
+        &target_client,
+        &base_uri,
+        migrate_args,
+        &mut *prog_rep,
+    );
+    drop(prog_rep);
+    match result {
+        Ok(report) => res_handler.render_bulk_report(report, opts),
+        Err(err) => res_handler.no_output_on_success::<()>(Err(err)),
+    }
+    res_handler.final_outcome_or(ExitCode::Usage)
+}
+
+fn dispatch_extended_health_check_command(
+    cli: &ArgMatches,
+    name: &str,
+    check_args: &ArgMatches,
+    common_settings: &SharedSettings,
+) -> Outcome {
+    let mut res_handler = ResultHandler::new(common_settings, check_args);
+    let result =
+        configure_health_check_client(cli, common_settings).and_then(|client| match name {
+            "certificate_expiration" => {
+                commands::health_check_certificate_expiration(&client, check_args)
+            }
+            "virtual_hosts" => commands::health_check_virtual_hosts(&client),
+            "ready_to_serve_clients" => commands::health_check_ready_to_serve_clients(&client),
+            _ => commands::health_check_is_in_service(&client),
+        });
+    res_handler.health_check_result(result);
+    res_handler.final_outcome_or(ExitCode::Usage)
+}
+
+fn dispatch_health_check_all_command(
+    cli: &ArgMatches,
+    all_args: &ArgMatches,
+    client: APIClient,
+    common_settings: &SharedSettings,
+) -> Outcome {
+    let mut res_handler = ResultHandler::new(common_settings, all_args);
+    let json = all_args.get_one::<String>("output").map(String::as_str) == Some("json");
+    let result =
+        configure_health_check_client(cli, common_settings).and_then(|health_check_client| {
+            commands::health_check_all(client, &health_check_client, all_args)
+        });
+    res_handler.health_check_report_result(result, json);
+    res_handler.final_outcome_or(ExitCode::Usage)
+}
+
+fn dispatch_password_rotation_command(
+    cli: &ArgMatches,
+    rotate_args: &ArgMatches,
+    client: APIClient,
+    common_settings: &SharedSettings,
+) -> Outcome {
+    let mut res_handler = ResultHandler::new(common_settings, rotate_args);
+    let name = rotate_args
+        .get_one::<String>("name")
+        .cloned()
+        .unwrap_or_default();
+
+    // Validate the configuration file target before the password is changed
+    let config_target = if rotate_args.get_flag("update_config_file") {
+        let node_alias = cli
+            .get_one::<String>("node_alias")
+            .cloned()
+            .unwrap_or(DEFAULT_NODE_ALIAS.to_string());
+        let target = if common_settings.username.as_deref() != Some(name.as_str()) {
+            Err(CommandRunError::ConflictingOptions {
+                message: format!(
+                    "--update-config-file can only be used to rotate the password of the user rabbitmqadmin is authenticated as, '{}'",
+                    common_settings.username.as_deref().unwrap_or_default()
+                ),
+            })
+        } else {
+            resolve_node_alias_configuration(cli, &node_alias).map(|_| {
+                let config_file_path = cli
+                    .get_one::<PathBuf>("config_file_path")
+                    .cloned()
+                    .unwrap_or(PathBuf::from(DEFAULT_CONFIG_FILE_PATH));
+                (config_file_path, node_alias)
+            })
+        };
+        match target {
+            Ok(pair) => Some(pair),
+            Err(err) => {
+                res_handler.report_pre_command_run_error(&err);
+                return res_handler.final_outcome_or(ExitCode::Usage);
+            }
+        }
+    } else {
+        None
+    };
+
+    let password = match commands::rotate_user_password(&client, rotate_args) {
+        Ok(password) => password,
+        Err(err) => {
+            res_handler.report_pre_command_run_error(&err);
+            return res_handler.final_outcome_or(ExitCode::DataErr);
+        }
+    };
+
+    // The new password is emitted before the configuration file is updated,
+    // so that it is not lost if the update fails
+    match rotate_args.get_one::<PathBuf>("output_file") {
+        Some(path) => {
+            let result = commands::write_rotated_password(path, &password).map_err(|e| {
+                CommandRunError::FailureDuringExecution {
+                    message: format!(
+                        "The password was rotated but could not be written to '{}', it is printed below instead: {}",
+                        path.display(),
+                        e
+                    ),
+                }
+            });
+            if let Err(err) = result {
+                res_handler.report_pre_command_run_error(&err);
+                println!("{}", password);
+                return res_handler.final_outcome_or(ExitCode::DataErr);
+            }
+            res_handler.no_output_on_success(Ok(()));
+        }
+        None => res_handler.single_value_output_with_result(Ok(password.clone())),
+    }
+
+    if let Some((config_file_path, node_alias)) = config_target {
+        let result =
+            commands::update_password_in_config_file(&config_file_path, &node_alias, &password);
+        res_handler.no_output_on_success(result);
+    }
+    res_handler.final_outcome_or(ExitCode::Usage)
+}
+
+/// Unlike [`resolve_run_configuration`], requires the configuration file section to exist,
+/// and ignores the global connection options: see [`SharedSettings::from_node_alias_section`].
+fn resolve_node_alias_configuration(
+    cli: &ArgMatches,
+    node_alias: &str,
+) -> Result<SharedSettings, CommandRunError> {
+    let config_file_path = cli
+        .get_one::<PathBuf>("config_file_path")
+        .cloned()
+        .unwrap_or(PathBuf::from(DEFAULT_CONFIG_FILE_PATH));
+
+    let section = SharedSettings::from_config_file(&config_file_path, Some(node_alias.to_owned()))
+        .map_err(|e| CommandRunError::FailureDuringExecution {
+            message: format!(
+                "Could not load configuration for node alias '{}' from configuration file '{}': {}",
+                node_alias,
+                config_file_path.display(),
+                e
+            ),
+        })?;
+    SharedSettings::from_node_alias_section(cli, &section)
+}
+
 fn dispatch_shell_command(
     shell_args: &ArgMatches,
     pre_flight_settings: PreFlightSettings,
@@ -16,13 +16,15 @@ use crate::columns;
 use crate::config::SharedSettings;
 use crate::errors::CommandRunError;
 use crate::exit_code::Outcome;
+use crate::health_checks::HealthCheckReport;
 use crate::tables;
 use bel7_cli::Padding;
 use clap::ArgMatches;
 use indicatif::{ProgressBar, ProgressStyle};
 use rabbitmq_http_client::password_hashing::HashingError;
 use rabbitmq_http_client::responses::{
-    NodeMemoryBreakdown, Overview, SchemaDefinitionSyncStatus, WarmStandbyReplicationStatus,
+    CurrentUser, NodeMemoryBreakdown, Overview, SchemaDefinitionSyncStatus,
+    WarmStandbyReplicationStatus,
 };
 use serde::Serialize;
 use std::fmt;
@@ -246,6 +248,10 @@ impl<'a> ResultHandler<'a> {
         self.handle_table_result(result, tables::churn_overview);
     }
 
+    pub fn show_whoami(&mut self, result: CommandResult<CurrentUser>) {
+        self.handle_table_result(result, tables::whoami);
+    }
+
     pub fn show_salted_and_hashed_value(&mut self, result: Result<String, HashingError>) {
         match result {
             Ok(value) => {
@@ -264,6 +270,25 @@ impl<'a> ResultHandler<'a> {
         self.handle_table_result(result, Table::new);
     }
 
+    /// Like [`Self::tabular_result`] but exits with `DataErr` if any of the rows
+    /// could not be evaluated, e.g. because a permission pattern cannot be evaluated locally.
+    pub fn permission_check_result<T>(
+        &mut self,
+        result: CommandResult<Vec<T>>,
+        is_evaluated: impl Fn(&T) -> bool,
+    ) where
+        T: fmt::Debug + Tabled,
+    {
+        let evaluated = match &result {
+            Ok(rows) => rows.iter().all(is_evaluated),
+            Err(_) => true,
+        };
+        self.tabular_result(result);
+        if !evaluated {
+            self.exit_code = Some(ExitCode::DataErr);
+        }
+    }
+
     pub fn tabular_result_with_columns<T>(
         &mut self,
         result: CommandResult<Vec<T>>,
@@ -427,6 +452,31 @@ impl<'a> ResultHandler<'a> {
         }
     }
 
+    /// Renders the report of `health_check all` as a table or JSON. Exits with the same
+    /// code as individual health checks if any of the checks failed.
+    pub fn health_check_report_result(
+        &mut self,
+        result: CommandResult<HealthCheckReport>,
+        json: bool,
+    ) {
+        match result {
+            Ok(report) => {
+                self.exit_code = Some(if report.healthy {
+                    ExitCode::Ok
+                } else {
+                    ExitCode::Unavailable
+                });
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+                } else {
+                    let mut table = Table::new(&report.results);
+                    self.print_styled_table(&mut table);
+                }
+            }
+            Err(error) => self.report_pre_command_run_error(&error),
+        }
+    }
+
     /// Options controlling how a [`BulkReport`] is rendered and how
     /// its outcome is mapped to a process exit code. Passed in from
     /// the dispatch layer once per bulk-delete subcommand.
@@ -559,6 +609,7 @@ impl<'a> ResultHandler<'a> {
             CommandRunError::CertificateKeyMismatch { .. } => ExitCode::DataErr,
             CommandRunError::IoError { .. } => ExitCode::DataErr,
             CommandRunError::FailureDuringExecution { .. } => ExitCode::DataErr,
+            CommandRunError::InsufficientPrivileges { .. } => ExitCode::NoPerm,
             CommandRunError::HttpClientBuildError { .. } => ExitCode::DataErr,
             CommandRunError::ClientError { .. } => ExitCode::DataErr,
             CommandRunError::ServerError { .. } => ExitCode::DataErr,
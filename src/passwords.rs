@@ -0,0 +1,144 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Random password generation for credential rotation and provisioning.
+
+use rabbitmq_http_client::password_hashing::{self, HashingAlgorithm};
+use rand::RngExt;
+use serde::Serialize;
+use std::fmt;
+use tabled::Tabled;
+
+pub const DEFAULT_PASSWORD_LENGTH: u16 = 32;
+pub const MIN_PASSWORD_LENGTH: u16 = 12;
+pub const MAX_PASSWORD_LENGTH: u16 = 1024;
+
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+// Symbols that do not need to be escaped in TOML strings, shells or URI user info
+const SYMBOLS: &[u8] = b"-_.~";
+const HEX: &[u8] = b"0123456789abcdef";
+// RabbitMQ password hashes are Base64-encoded 32-bit salts followed by a digest
+const SALT_LENGTH: usize = 4;
+
+/// The characters a generated password is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PasswordCharset {
+    #[default]
+    Alphanumeric,
+    AlphanumericWithSymbols,
+    Hex,
+}
+
+impl PasswordCharset {
+    fn characters(&self) -> Vec<u8> {
+        match self {
+            PasswordCharset::Alphanumeric => ALPHANUMERIC.to_vec(),
+            PasswordCharset::AlphanumericWithSymbols => [ALPHANUMERIC, SYMBOLS].concat(),
+            PasswordCharset::Hex => HEX.to_vec(),
+        }
+    }
+}
+
+impl From<&str> for PasswordCharset {
+    fn from(value: &str) -> Self {
+        match value {
+            "alphanumeric_with_symbols" => PasswordCharset::AlphanumericWithSymbols,
+            "hex" => PasswordCharset::Hex,
+            _ => PasswordCharset::Alphanumeric,
+        }
+    }
+}
+
+impl fmt::Display for PasswordCharset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordCharset::Alphanumeric => write!(f, "alphanumeric"),
+            PasswordCharset::AlphanumericWithSymbols => write!(f, "alphanumeric_with_symbols"),
+            PasswordCharset::Hex => write!(f, "hex"),
+        }
+    }
+}
+
+/// Generates a password of `length` characters picked uniformly at random
+/// from `charset`, using a cryptographically secure generator.
+pub fn generate(length: usize, charset: PasswordCharset) -> String {
+    let characters = charset.characters();
+    let mut rng = rand::rng();
+    (0..length)
+        .map(|_| characters[rng.random_range(0..characters.len())] as char)
+        .collect()
+}
+
+/// A generated password together with its salted hash, ready to be used
+/// as a `password_hash` in definitions or with `users declare`.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct GeneratedPassword {
+    pub password: String,
+    pub password_hash: String,
+    pub hashing_algorithm: String,
+}
+
+/// Generates a password like [`generate`] does and salts and hashes it locally.
+pub fn generate_with_hash(
+    length: usize,
+    charset: PasswordCharset,
+    algorithm: &HashingAlgorithm,
+) -> GeneratedPassword {
+    let password = generate(length, charset);
+    let password_hash = password_hashing::base64_encoded_salted_password_hash(
+        &password_hashing::salt(),
+        &password,
+        algorithm,
+    );
+    GeneratedPassword {
+        password,
+        password_hash,
+        hashing_algorithm: algorithm.to_string(),
+    }
+}
+
+/// Checks a cleartext password against a Base64-encoded, salted RabbitMQ password hash
+/// produced with `algorithm`. Returns an error if the value is not a valid hash for
+/// that algorithm.
+pub fn verify(
+    password_hash: &str,
+    password: &str,
+    algorithm: &HashingAlgorithm,
+) -> Result<bool, String> {
+    let decoded = rbase64::decode(password_hash.trim())
+        .map_err(|_| "the password hash is not a valid Base64-encoded value".to_string())?;
+    let expected_length = SALT_LENGTH + digest_length(algorithm);
+    if decoded.len() != expected_length {
+        return Err(format!(
+            "the password hash is {} bytes long but a salted {} hash is {} bytes long",
+            decoded.len(),
+            algorithm,
+            expected_length
+        ));
+    }
+
+    let salt = &decoded[..SALT_LENGTH];
+    let computed = match algorithm {
+        HashingAlgorithm::SHA256 => password_hashing::salted_password_hash_sha256(salt, password),
+        HashingAlgorithm::SHA512 => password_hashing::salted_password_hash_sha512(salt, password),
+    };
+    Ok(computed == decoded)
+}
+
+fn digest_length(algorithm: &HashingAlgorithm) -> usize {
+    match algorithm {
+        HashingAlgorithm::SHA256 => 32,
+        HashingAlgorithm::SHA512 => 64,
+    }
+}
@@ -0,0 +1,330 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local evaluation of user permissions for `permissions check`.
+//!
+//! Mirrors the checks performed by the internal authorization backend:
+//! permission patterns are unanchored regular expressions, and an empty pattern
+//! is treated as `^$`, that is, it only matches the default exchange (whose name is empty).
+
+use rabbitmq_http_client::responses;
+use regex::Regex;
+use serde::Serialize;
+use std::fmt;
+use tabled::Tabled;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceType {
+    Queue,
+    Exchange,
+    /// A routing key published or bound to a topic exchange
+    Topic,
+}
+
+impl From<&str> for ResourceType {
+    fn from(value: &str) -> Self {
+        match value {
+            "exchange" => ResourceType::Exchange,
+            "topic" => ResourceType::Topic,
+            _ => ResourceType::Queue,
+        }
+    }
+}
+
+impl fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceType::Queue => write!(f, "queue"),
+            ResourceType::Exchange => write!(f, "exchange"),
+            ResourceType::Topic => write!(f, "topic"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Configure,
+    Write,
+    Read,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 3] = [Operation::Configure, Operation::Write, Operation::Read];
+
+    fn pattern_of<'a>(&self, permissions: &'a responses::Permissions) -> &'a str {
+        match self {
+            Operation::Configure => &permissions.configure,
+            Operation::Write => &permissions.write,
+            Operation::Read => &permissions.read,
+        }
+    }
+}
+
+impl From<&str> for Operation {
+    fn from(value: &str) -> Self {
+        match value {
+            "configure" => Operation::Configure,
+            "read" => Operation::Read,
+            _ => Operation::Write,
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Configure => write!(f, "configure"),
+            Operation::Write => write!(f, "write"),
+            Operation::Read => write!(f, "read"),
+        }
+    }
+}
+
+/// The resource an operation is performed on. For topics, `name` is the exchange.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resource {
+    pub resource_type: ResourceType,
+    pub name: String,
+    pub routing_key: Option<String>,
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.resource_type, &self.routing_key) {
+            (ResourceType::Topic, Some(rk)) => {
+                write!(f, "routing key '{}' on exchange '{}'", rk, self.name)
+            }
+            (rt, _) => write!(f, "{} '{}'", rt, self.name),
+        }
+    }
+}
+
+/// The permissions of one user in one virtual host.
+#[derive(Debug, Clone, Default)]
+pub struct UserAccess<'a> {
+    pub permissions: Option<&'a responses::Permissions>,
+    pub topic_permissions: Vec<&'a responses::TopicPermission>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    Allowed,
+    Denied,
+    /// Topic permissions have no configure operation
+    NotApplicable,
+    /// A pattern uses a feature (e.g. a lookaround or a backreference) the broker's
+    /// regular expression engine supports but this one does not
+    CannotEvaluate,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decision::Allowed => write!(f, "allowed"),
+            Decision::Denied => write!(f, "denied"),
+            Decision::NotApplicable => write!(f, "n/a"),
+            Decision::CannotEvaluate => write!(f, "cannot evaluate"),
+        }
+    }
+}
+
+/// The outcome of a check plus the rule that decided it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    pub decision: Decision,
+    pub rule: String,
+}
+
+impl Verdict {
+    fn allowed(rule: String) -> Self {
+        Verdict {
+            decision: Decision::Allowed,
+            rule,
+        }
+    }
+
+    fn denied(rule: String) -> Self {
+        Verdict {
+            decision: Decision::Denied,
+            rule,
+        }
+    }
+
+    fn cannot_evaluate(rule: String) -> Self {
+        Verdict {
+            decision: Decision::CannotEvaluate,
+            rule,
+        }
+    }
+
+    pub fn is_allowed(&self) -> bool {
+        self.decision == Decision::Allowed
+    }
+}
+
+#[derive(Debug, Clone, Tabled, Serialize)]
+pub struct PermissionCheck {
+    pub user: String,
+    pub vhost: String,
+    pub resource: String,
+    pub operation: String,
+    pub result: String,
+    pub rule: String,
+}
+
+impl PermissionCheck {
+    pub fn is_evaluated(&self) -> bool {
+        self.result != Decision::CannotEvaluate.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Tabled, Serialize)]
+pub struct PermissionMatrixRow {
+    pub user: String,
+    pub vhost: String,
+    pub configure: String,
+    pub write: String,
+    pub read: String,
+}
+
+impl PermissionMatrixRow {
+    pub fn is_evaluated(&self) -> bool {
+        let cannot_evaluate = Decision::CannotEvaluate.to_string();
+        [&self.configure, &self.write, &self.read]
+            .iter()
+            .all(|decision| **decision != cannot_evaluate)
+    }
+}
+
+/// Evaluates whether `user` may perform `operation` on `resource` in `vhost`.
+pub fn check(
+    user: &str,
+    vhost: &str,
+    access: &UserAccess,
+    resource: &Resource,
+    operation: Operation,
+) -> Verdict {
+    let Some(permissions) = access.permissions else {
+        return Verdict::denied(format!(
+            "user '{}' has no permissions in virtual host '{}'",
+            user, vhost
+        ));
+    };
+
+    if resource.resource_type == ResourceType::Topic && operation == Operation::Configure {
+        return Verdict {
+            decision: Decision::NotApplicable,
+            rule: "topic permissions only cover the write and read operations".to_owned(),
+        };
+    }
+
+    // Topic authorisation happens in addition to the regular exchange permission check
+    let kind = match resource.resource_type {
+        ResourceType::Queue => "queue",
+        _ => "exchange",
+    };
+    let pattern = operation.pattern_of(permissions);
+    let verdict = match_pattern(
+        &format!("{} permission pattern", operation),
+        pattern,
+        &resource.name,
+        &format!("{} '{}'", kind, resource.name),
+    );
+    if !verdict.is_allowed() || resource.resource_type != ResourceType::Topic {
+        return verdict;
+    }
+
+    let routing_key = resource.routing_key.as_deref().unwrap_or_default();
+    let Some(topic) = access
+        .topic_permissions
+        .iter()
+        .find(|tp| tp.exchange == resource.name)
+    else {
+        return Verdict::allowed(format!(
+            "{}, and there are no topic permissions for exchange '{}', so all routing keys are allowed",
+            verdict.rule, resource.name
+        ));
+    };
+
+    let topic_pattern = match operation {
+        Operation::Read => &topic.read,
+        _ => &topic.write,
+    };
+    let expanded = topic_pattern
+        .replace("{username}", user)
+        .replace("{vhost}", vhost);
+    match_pattern(
+        &format!("topic {} permission pattern", operation),
+        &expanded,
+        routing_key,
+        &format!("routing key '{}'", routing_key),
+    )
+}
+
+/// Evaluates every operation on `resource` for every user × virtual host pair.
+pub fn matrix(
+    users: &[String],
+    vhosts: &[String],
+    permissions: &[responses::Permissions],
+    topic_permissions: &[responses::TopicPermission],
+    resource: &Resource,
+) -> Vec<PermissionMatrixRow> {
+    let mut rows = Vec::with_capacity(users.len() * vhosts.len());
+    for user in users {
+        for vhost in vhosts {
+            let access = UserAccess {
+                permissions: permissions
+                    .iter()
+                    .find(|p| &p.user == user && &p.vhost == vhost),
+                topic_permissions: topic_permissions
+                    .iter()
+                    .filter(|tp| &tp.user == user && &tp.vhost == vhost)
+                    .collect(),
+            };
+            let [configure, write, read] = Operation::ALL.map(|op| {
+                check(user, vhost, &access, resource, op)
+                    .decision
+                    .to_string()
+            });
+            rows.push(PermissionMatrixRow {
+                user: user.clone(),
+                vhost: vhost.clone(),
+                configure,
+                write,
+                read,
+            });
+        }
+    }
+    rows
+}
+
+fn match_pattern(label: &str, pattern: &str, subject: &str, subject_description: &str) -> Verdict {
+    // The broker treats an empty pattern as "^$"
+    let (effective, shown) = if pattern.is_empty() {
+        ("^$", format!("{} '' (treated as '^$')", label))
+    } else {
+        (pattern, format!("{} '{}'", label, pattern))
+    };
+
+    match Regex::new(effective) {
+        Ok(re) if re.is_match(subject) => {
+            Verdict::allowed(format!("{} matches {}", shown, subject_description))
+        }
+        Ok(_) => Verdict::denied(format!("{} does not match {}", shown, subject_description)),
+        Err(err) => Verdict::cannot_evaluate(format!(
+            "{} cannot be evaluated locally, the broker may still accept it: {}",
+            shown, err
+        )),
+    }
+}
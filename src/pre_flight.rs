@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::constants::{ADMINISTRATOR_TAG, MONITORING_TAG};
+use crate::errors::CommandRunError;
+use rabbitmq_http_client::responses::CurrentUser;
 use std::env;
 
 /// Represents the two modes of operation for the `rabbitmqadmin` CLI:
@@ -55,3 +58,33 @@ fn is_enabled_in_env(key: &str) -> bool {
         Err(_) => false,
     }
 }
+
+/// Whether the user has the administrator tag.
+pub fn is_administrator(user: &CurrentUser) -> bool {
+    user.tags.contains(ADMINISTRATOR_TAG)
+}
+
+/// Whether the user can access monitoring endpoints. The administrator tag
+/// grants everything the monitoring one does.
+pub fn has_monitoring_access(user: &CurrentUser) -> bool {
+    is_administrator(user) || user.tags.contains(MONITORING_TAG)
+}
+
+/// Checks that the current user has the tag `action` requires, so that commands
+/// can fail early instead of with a 401 or 403 response midway through.
+pub fn require_tag(user: &CurrentUser, tag: &str, action: &str) -> Result<(), CommandRunError> {
+    if user.tags.contains(tag) {
+        return Ok(());
+    }
+    let tags = if user.tags.is_empty() {
+        "none".to_string()
+    } else {
+        user.tags.to_string()
+    };
+    Err(CommandRunError::InsufficientPrivileges {
+        message: format!(
+            "User '{}' (tags: {}) cannot {}: this requires the '{}' tag",
+            user.name, tags, action, tag
+        ),
+    })
+}
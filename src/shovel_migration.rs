@@ -0,0 +1,220 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Planning for `shovels migrate_cluster`.
+//!
+//! A blue/green migration moves the messages of every (matching) queue on the current cluster
+//! to a queue with the same name on the target cluster. Each queue gets a dynamic AMQP 0-9-1 shovel
+//! that runs on the current cluster and deletes itself after transferring the messages that
+//! were in the queue when it started (`src-delete-after: queue-length`).
+//!
+//! Since the shovels delete themselves, the queues they were declared for are recorded
+//! in a global runtime parameter, see [`MigrationRecord`].
+
+use crate::definitions::{DefinitionKind, percent_encode};
+use rabbitmq_http_client::responses::ShovelState;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use tabled::Tabled;
+use url::Url;
+
+/// Migration shovels are named after the queue they drain, with this prefix.
+pub const MIGRATION_SHOVEL_NAME_PREFIX: &str = "migrate_cluster.";
+
+/// The global runtime parameter [`MigrationRecord`] is stored in.
+pub const MIGRATION_RECORD_PARAMETER_NAME: &str = "rabbitmqadmin.migrate_cluster";
+
+const AMQP_PORT: u16 = 5672;
+const AMQPS_PORT: u16 = 5671;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueMigration {
+    pub vhost: String,
+    pub queue: String,
+}
+
+impl QueueMigration {
+    pub fn shovel_name(&self) -> String {
+        migration_shovel_name(&self.queue)
+    }
+
+    /// The shovel runs on the cluster the queue is migrated from, so its source is local.
+    pub fn source_uri(&self) -> String {
+        format!("amqp:///{}", percent_encode(&self.vhost))
+    }
+
+    /// `base_uri` with the path replaced by the (percent-encoded) virtual host of the queue.
+    pub fn destination_uri(&self, base_uri: &Url) -> String {
+        let mut uri = base_uri.clone();
+        uri.set_path(&format!("/{}", percent_encode(&self.vhost)));
+        uri.to_string()
+    }
+}
+
+impl fmt::Display for QueueMigration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "queue '{}' in virtual host '{}'", self.queue, self.vhost)
+    }
+}
+
+pub fn migration_shovel_name(queue: &str) -> String {
+    format!("{}{}", MIGRATION_SHOVEL_NAME_PREFIX, queue)
+}
+
+/// Narrows cluster-wide definitions down to virtual hosts and queues: the topology
+/// that has to exist on the target before the shovels can be declared.
+pub fn retain_vhosts_and_queues(defs: &mut Value) {
+    if let Some(map) = defs.as_object_mut() {
+        for kind in DefinitionKind::ALL {
+            if !matches!(kind, DefinitionKind::Vhosts | DefinitionKind::Queues) {
+                map.remove(kind.key());
+            }
+        }
+    }
+}
+
+/// One migration per queue in the definitions, ordered by virtual host and queue name.
+pub fn plan_queue_migrations(defs: &Value) -> Vec<QueueMigration> {
+    let mut migrations: Vec<QueueMigration> = defs
+        .get(DefinitionKind::Queues.key())
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|q| {
+            Some(QueueMigration {
+                vhost: q.get("vhost")?.as_str()?.to_owned(),
+                queue: q.get("name")?.as_str()?.to_owned(),
+            })
+        })
+        .collect();
+    migrations.sort_by(|a, b| (&a.vhost, &a.queue).cmp(&(&b.vhost, &b.queue)));
+    migrations
+}
+
+/// Builds the AMQP 0-9-1 URI of a node from its HTTP API settings, assuming
+/// the default AMQP (or AMQPS) port.
+///
+/// The URI includes the given credentials. Shovel URIs are stored in runtime parameters
+/// on the cluster the shovels run on, where they are visible to users that can list them.
+pub fn amqp_base_uri(
+    hostname: &str,
+    username: &str,
+    password: &str,
+    tls: bool,
+) -> Result<Url, url::ParseError> {
+    let (scheme, port) = if tls {
+        ("amqps", AMQPS_PORT)
+    } else {
+        ("amqp", AMQP_PORT)
+    };
+    let mut uri = Url::parse(&format!("{}://localhost", scheme))?;
+    // IPv6 addresses must be bracketed, e.g. when provided with --host
+    if hostname.contains(':') && !hostname.starts_with('[') {
+        uri.set_host(Some(&format!("[{}]", hostname)))?;
+    } else {
+        uri.set_host(Some(hostname))?;
+    }
+    // these only fail for URIs without a host
+    let _ = uri.set_port(Some(port));
+    let _ = uri.set_username(username);
+    let _ = uri.set_password(Some(password));
+    Ok(uri)
+}
+
+/// The queues `shovels migrate_cluster` has declared migration shovels for, stored as
+/// the value of a global runtime parameter. Migration shovels delete themselves when done,
+/// so this is what tells a finished migration apart from a queue that was never migrated.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationRecord {
+    pub migrations: Vec<QueueMigration>,
+}
+
+impl MigrationRecord {
+    /// Appends migrations that are not yet recorded.
+    pub fn extend<'a>(&mut self, migrations: impl IntoIterator<Item = &'a QueueMigration>) {
+        for migration in migrations {
+            if !self.migrations.contains(migration) {
+                self.migrations.push(migration.clone());
+            }
+        }
+    }
+
+    pub fn contains(&self, vhost: &str, queue: &str) -> bool {
+        self.migrations
+            .iter()
+            .any(|m| m.vhost == vhost && m.queue == queue)
+    }
+
+    pub fn to_parameter_value(&self) -> Map<String, Value> {
+        match serde_json::to_value(self) {
+            Ok(Value::Object(map)) => map,
+            _ => Map::new(),
+        }
+    }
+
+    pub fn from_parameter_value(value: Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    /// There is no migration shovel and the queue still has messages
+    Pending,
+    /// The migration shovel is starting or transferring messages
+    InProgress,
+    /// The migration shovel has stopped without finishing, e.g. it cannot connect to the target
+    Terminated,
+    /// The migration shovel has transferred its messages and deleted itself
+    Finished,
+    /// No migration shovel was declared for the queue and it has no messages
+    NothingToMigrate,
+}
+
+impl MigrationState {
+    /// Migration shovels delete themselves when done, so an absent shovel means either
+    /// a finished migration or one that has not been started: `recorded` tells them apart.
+    pub fn of(shovel_state: Option<&ShovelState>, recorded: bool, message_count: u64) -> Self {
+        match shovel_state {
+            Some(ShovelState::Terminated) => MigrationState::Terminated,
+            Some(_) => MigrationState::InProgress,
+            None if message_count > 0 => MigrationState::Pending,
+            None if recorded => MigrationState::Finished,
+            None => MigrationState::NothingToMigrate,
+        }
+    }
+}
+
+impl fmt::Display for MigrationState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationState::Pending => write!(f, "pending"),
+            MigrationState::InProgress => write!(f, "in progress"),
+            MigrationState::Terminated => write!(f, "terminated"),
+            MigrationState::Finished => write!(f, "finished"),
+            MigrationState::NothingToMigrate => write!(f, "nothing to migrate"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Tabled, Serialize)]
+pub struct QueueMigrationStatus {
+    pub vhost: String,
+    pub queue: String,
+    pub shovel: String,
+    pub state: MigrationState,
+    pub messages: u64,
+}
@@ -35,17 +35,22 @@ pub(crate) const UPGRADE_GUIDE_URL: &str = "https://rabbitmq.com/docs/upgrade";
 pub(crate) const BLUE_GREEN_UPGRADE_GUIDE_URL: &str =
     "https://rabbitmq.com/docs/blue-green-upgrade";
 pub(crate) const MONITORING_GUIDE_URL: &str = "https://rabbitmq.com/docs/monitoring";
+pub(crate) const PRODUCTION_CHECKLIST_GUIDE_URL: &str =
+    "https://rabbitmq.com/docs/production-checklist";
 pub(crate) const HEALTH_CHECK_GUIDE_URL: &str =
     "https://rabbitmq.com/docs/monitoring#health-checks";
 pub(crate) const FEATURE_FLAG_GUIDE_URL: &str = "https://rabbitmq.com/docs/feature-flags";
 pub(crate) const DEPRECATED_FEATURE_GUIDE_URL: &str =
     "https://rabbitmq.com/docs/deprecated-features";
 pub(crate) const ACCESS_CONTROL_GUIDE_URL: &str = "https://rabbitmq.com/docs/access-control";
+pub(crate) const TOPIC_PERMISSIONS_GUIDE_URL: &str =
+    "https://rabbitmq.com/docs/access-control#topic-authorisation";
 pub(crate) const HTTP_API_ACCESS_PERMISSIONS_GUIDE_URL: &str =
     "https://rabbitmq.com/docs/management#permissions";
 pub(crate) const MEMORY_FOOTPRINT_GUIDE_URL: &str = "https://rabbitmq.com/docs/memory-use";
 pub(crate) const DEFINITION_GUIDE_URL: &str = "https://rabbitmq.com/docs/definitions";
 pub(crate) const CONSUMER_GUIDE_URL: &str = "https://rabbitmq.com/docs/consumers";
+pub(crate) const CONFIRMS_GUIDE_URL: &str = "https://rabbitmq.com/docs/confirms";
 pub(crate) const POLLING_CONSUMER_GUIDE_URL: &str = "https://rabbitmq.com/docs/consumers#polling";
 pub(crate) const PUBLISHER_GUIDE_URL: &str = "https://rabbitmq.com/docs/publishers";
 pub(crate) const CLUSTERING_GUIDE_URL: &str = "https://rabbitmq.com/docs/clustering";
@@ -11,11 +11,13 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use crate::errors::HealthCheckDetails;
+use crate::pre_flight;
 use rabbitmq_http_client::formatting::*;
 use rabbitmq_http_client::password_hashing::HashingError;
 use rabbitmq_http_client::responses::{
-    ClusterAlarmCheckDetails, HealthCheckFailureDetails, NodeMemoryBreakdown, Overview,
-    QuorumCriticalityCheckDetails, SchemaDefinitionSyncStatus,
+    ClusterAlarmCheckDetails, CurrentUser, HealthCheckFailureDetails, NodeMemoryBreakdown,
+    Overview, QuorumCriticalityCheckDetails, SchemaDefinitionSyncStatus,
 };
 use reqwest::StatusCode;
 use std::fmt;
@@ -257,6 +259,32 @@ pub fn schema_definition_sync_status(status: SchemaDefinitionSyncStatus) -> Tabl
     build_table_with_header(data, "Schema Definition Sync Status")
 }
 
+pub fn whoami(user: CurrentUser) -> Table {
+    let tags_s = user.tags.to_string();
+    let administrator_s = pre_flight::is_administrator(&user).to_string();
+    let monitoring_s = pre_flight::has_monitoring_access(&user).to_string();
+    let data = vec![
+        RowOfTwo {
+            key: "user",
+            value: &user.name,
+        },
+        RowOfTwo {
+            key: "tags",
+            value: &tags_s,
+        },
+        RowOfTwo {
+            key: "administrator access",
+            value: &administrator_s,
+        },
+        RowOfTwo {
+            key: "monitoring access",
+            value: &monitoring_s,
+        },
+    ];
+
+    build_simple_table(data)
+}
+
 pub fn hashing_error_details(error: &HashingError) -> Table {
     build_simple_table(vec![
         RowOfTwo {
@@ -273,15 +301,9 @@ pub fn hashing_error_details(error: &HashingError) -> Table {
 pub fn health_check_failure(
     path: &str,
     status_code: StatusCode,
-    details: HealthCheckFailureDetails,
+    details: HealthCheckDetails,
 ) -> Table {
-    let reason = match details {
-        HealthCheckFailureDetails::AlarmCheck(ref details) => details.reason.clone(),
-        HealthCheckFailureDetails::NodeIsQuorumCritical(ref details) => details.reason.clone(),
-        HealthCheckFailureDetails::NoActivePortListener(ref details) => details.reason.clone(),
-        HealthCheckFailureDetails::NoActiveProtocolListener(ref details) => details.reason.clone(),
-        HealthCheckFailureDetails::NoActiveProtocolListeners(ref details) => details.reason.clone(),
-    };
+    let reason = details.reason();
     let code_str = format!("{}", status_code);
 
     let vec = vec![
@@ -304,36 +326,64 @@ pub fn health_check_failure(
     ];
     let mut tb = Table::builder(vec);
     match details {
-        HealthCheckFailureDetails::AlarmCheck(ClusterAlarmCheckDetails { reason: _, alarms }) => {
-            for alarm in alarms {
-                let key = format!("alarm in effect on node {}", alarm.node);
-                let value = alarm.resource;
+        HealthCheckDetails::Client(details) => match details {
+            HealthCheckFailureDetails::AlarmCheck(ClusterAlarmCheckDetails {
+                reason: _,
+                alarms,
+            }) => {
+                for alarm in alarms {
+                    let key = format!("alarm in effect on node {}", alarm.node);
+                    let value = alarm.resource;
+                    tb.push_record([key.as_str(), value.as_str()]);
+                }
+            }
+            HealthCheckFailureDetails::NodeIsQuorumCritical(QuorumCriticalityCheckDetails {
+                reason: _,
+                queues,
+            }) => {
+                for q in queues {
+                    let key = "affected queue, stream or internal component";
+                    let value = q.readable_name;
+                    tb.push_record([key, value.as_str()]);
+                }
+            }
+            HealthCheckFailureDetails::NoActivePortListener(details) => {
+                tb.push_record(["inactive port", details.inactive_port.to_string().as_str()]);
+            }
+            HealthCheckFailureDetails::NoActiveProtocolListener(details) => {
+                tb.push_record([
+                    "inactive protocol",
+                    details.inactive_protocol.to_string().as_str(),
+                ]);
+            }
+            HealthCheckFailureDetails::NoActiveProtocolListeners(details) => tb.push_record([
+                "inactive protocols",
+                details.inactive_protocols.join(", ").as_str(),
+            ]),
+        },
+        HealthCheckDetails::Extended(details) => {
+            for listener in details.expired {
+                let key = format!("expiring certificate on node {}", listener.node);
+                let value = format!(
+                    "{} listener on {}:{}, {}, expires on {}",
+                    listener.protocol,
+                    listener.interface,
+                    listener.port,
+                    listener.certfile,
+                    listener.expiry_date
+                );
                 tb.push_record([key.as_str(), value.as_str()]);
             }
-        }
-        HealthCheckFailureDetails::NodeIsQuorumCritical(QuorumCriticalityCheckDetails {
-            reason: _,
-            queues,
-        }) => {
-            for q in queues {
-                let key = "affected queue, stream or internal component";
-                let value = q.readable_name;
-                tb.push_record([key, value.as_str()]);
+            for vhost in details.virtual_hosts {
+                tb.push_record(["virtual host that is down", vhost.as_str()]);
             }
         }
-        HealthCheckFailureDetails::NoActivePortListener(details) => {
-            tb.push_record(["inactive port", details.inactive_port.to_string().as_str()]);
-        }
-        HealthCheckFailureDetails::NoActiveProtocolListener(details) => {
-            tb.push_record([
-                "inactive protocol",
-                details.inactive_protocol.to_string().as_str(),
-            ]);
+        HealthCheckDetails::Threshold(details) => {
+            for violation in details.violations {
+                let value = format!("{} {}", violation.value, details.metric);
+                tb.push_record([violation.object.as_str(), value.as_str()]);
+            }
         }
-        HealthCheckFailureDetails::NoActiveProtocolListeners(details) => tb.push_record([
-            "inactive protocols",
-            details.inactive_protocols.join(", ").as_str(),
-        ]),
     };
 
     tb.build()
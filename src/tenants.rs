@@ -0,0 +1,601 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tenant specs for `tenants provision` and `tenants deprovision`.
+//!
+//! A tenant is a virtual host plus the users, permissions, limits, policies and
+//! base topology a team needs. Provisioning a spec produces a list of steps.
+//! The objects those steps created are recorded in a global runtime parameter,
+//! so that they can be rolled back on failure, or removed by tenant name later.
+
+use crate::users;
+use rabbitmq_http_client::password_hashing::{self, HashingAlgorithm};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+/// Global runtime parameters with this prefix hold tenant provisioning records.
+pub const TENANT_RECORD_PARAMETER_PREFIX: &str = "rabbitmqadmin.tenant.";
+
+const DEFAULT_QUEUE_TYPE: &str = "classic";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantSpec {
+    pub name: String,
+    #[serde(default)]
+    pub vhost: VirtualHostSpec,
+    /// Virtual host limits, e.g. `max-connections = 100`
+    #[serde(default)]
+    pub limits: BTreeMap<String, i64>,
+    #[serde(default)]
+    pub users: Vec<UserSpec>,
+    #[serde(default)]
+    pub permissions: Vec<PermissionsSpec>,
+    #[serde(default)]
+    pub topic_permissions: Vec<TopicPermissionsSpec>,
+    #[serde(default)]
+    pub policies: Vec<PolicySpec>,
+    #[serde(default)]
+    pub exchanges: Vec<ExchangeSpec>,
+    #[serde(default)]
+    pub queues: Vec<QueueSpec>,
+    #[serde(default)]
+    pub bindings: Vec<BindingSpec>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VirtualHostSpec {
+    /// Defaults to the tenant name
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub default_queue_type: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserSpec {
+    pub name: String,
+    pub password: Option<String>,
+    pub password_hash: Option<String>,
+    pub hashing_algorithm: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl UserSpec {
+    /// Hashing algorithm module name, e.g. `rabbit_password_hashing_sha256`
+    pub fn hashing_algorithm_module(&self) -> Result<&'static str, String> {
+        let name = self.hashing_algorithm.as_deref().unwrap_or_default();
+        users::hashing_algorithm_module(name).ok_or_else(|| {
+            format!(
+                "user '{}' has an unsupported hashing_algorithm '{}', use SHA256, SHA512 or (for a password_hash only) MD5",
+                self.name, name
+            )
+        })
+    }
+
+    /// Definitions with this user. A plaintext password is salted and hashed locally
+    /// using the spec's hashing algorithm, which is passed along with the hash.
+    pub fn to_definitions(&self) -> Result<Value, String> {
+        let module = self.hashing_algorithm_module()?;
+        let password_hash = match (&self.password_hash, &self.password) {
+            (Some(hash), _) => hash.clone(),
+            (None, password) => HashingAlgorithm::from(users::hashing_algorithm_name(module))
+                .salt_and_hash(
+                    &password_hashing::salt(),
+                    password.as_deref().unwrap_or_default(),
+                )
+                .map_err(|e| format!("password hashing failed: {}", e))?,
+        };
+        Ok(json!({
+            "users": [users::user_definition(&self.name, &password_hash, module, &self.tags)]
+        }))
+    }
+}
+
+// Keeps passwords out of debug output
+impl fmt::Debug for UserSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserSpec")
+            .field("name", &self.name)
+            .field("tags", &self.tags)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PermissionsSpec {
+    pub user: String,
+    pub configure: String,
+    pub write: String,
+    pub read: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopicPermissionsSpec {
+    pub user: String,
+    pub exchange: String,
+    pub write: String,
+    pub read: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicySpec {
+    pub name: String,
+    pub pattern: String,
+    #[serde(
+        default = "default_policy_target",
+        rename = "apply-to",
+        alias = "apply_to"
+    )]
+    pub apply_to: String,
+    #[serde(default)]
+    pub priority: i32,
+    pub definition: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExchangeSpec {
+    pub name: String,
+    #[serde(rename = "type", default = "default_exchange_type")]
+    pub exchange_type: String,
+    #[serde(default = "default_true")]
+    pub durable: bool,
+    #[serde(default)]
+    pub auto_delete: bool,
+    #[serde(default)]
+    pub arguments: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QueueSpec {
+    pub name: String,
+    /// Defaults to the default queue type of the virtual host, or `classic`
+    #[serde(rename = "type")]
+    pub queue_type: Option<String>,
+    #[serde(default = "default_true")]
+    pub durable: bool,
+    #[serde(default)]
+    pub auto_delete: bool,
+    #[serde(default)]
+    pub arguments: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BindingSpec {
+    pub source: String,
+    pub destination: String,
+    #[serde(default = "default_destination_type")]
+    pub destination_type: String,
+    #[serde(default)]
+    pub routing_key: String,
+    #[serde(default)]
+    pub arguments: Map<String, Value>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_policy_target() -> String {
+    "all".to_owned()
+}
+
+fn default_exchange_type() -> String {
+    "direct".to_owned()
+}
+
+fn default_destination_type() -> String {
+    "queue".to_owned()
+}
+
+impl TenantSpec {
+    pub fn vhost_name(&self) -> &str {
+        self.vhost.name.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Parses and validates a TOML tenant spec.
+pub fn parse_spec(contents: &str) -> Result<TenantSpec, String> {
+    let spec: TenantSpec = toml::from_str(contents).map_err(|err| err.to_string())?;
+
+    if spec.name.is_empty() {
+        return Err("tenant name must not be empty".to_owned());
+    }
+    for user in &spec.users {
+        if user.password.is_some() == user.password_hash.is_some() {
+            return Err(format!(
+                "user '{}' must have either a password or a password_hash, but not both",
+                user.name
+            ));
+        }
+        let module = user.hashing_algorithm_module()?;
+        if user.password.is_some() && module == users::MD5_MODULE {
+            return Err(format!(
+                "user '{}' uses MD5, which is only supported for a password_hash",
+                user.name
+            ));
+        }
+    }
+    for binding in &spec.bindings {
+        if !matches!(binding.destination_type.as_str(), "queue" | "exchange") {
+            return Err(format!(
+                "binding destination type must be 'queue' or 'exchange', got '{}'",
+                binding.destination_type
+            ));
+        }
+    }
+    Ok(spec)
+}
+
+/// Something created for a tenant, identified well enough to be removed again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TenantObject {
+    VirtualHost {
+        name: String,
+    },
+    User {
+        name: String,
+    },
+    Permissions {
+        vhost: String,
+        user: String,
+    },
+    /// Removing topic permissions clears all of the user's topic permissions in the virtual host
+    TopicPermissions {
+        vhost: String,
+        user: String,
+    },
+    Limit {
+        vhost: String,
+        kind: String,
+    },
+    Policy {
+        vhost: String,
+        name: String,
+    },
+    Exchange {
+        vhost: String,
+        name: String,
+    },
+    Queue {
+        vhost: String,
+        name: String,
+    },
+    Binding {
+        vhost: String,
+        source: String,
+        destination: String,
+        destination_type: String,
+        routing_key: String,
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        arguments: Map<String, Value>,
+    },
+}
+
+impl TenantObject {
+    /// The virtual host this object belongs to. Virtual hosts and users are cluster-wide.
+    pub fn vhost(&self) -> Option<&str> {
+        match self {
+            TenantObject::VirtualHost { .. } | TenantObject::User { .. } => None,
+            TenantObject::Permissions { vhost, .. }
+            | TenantObject::TopicPermissions { vhost, .. }
+            | TenantObject::Limit { vhost, .. }
+            | TenantObject::Policy { vhost, .. }
+            | TenantObject::Exchange { vhost, .. }
+            | TenantObject::Queue { vhost, .. }
+            | TenantObject::Binding { vhost, .. } => Some(vhost),
+        }
+    }
+}
+
+impl fmt::Display for TenantObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TenantObject::VirtualHost { name } => write!(f, "virtual host '{}'", name),
+            TenantObject::User { name } => write!(f, "user '{}'", name),
+            TenantObject::Permissions { vhost, user } => {
+                write!(f, "permissions of user '{}' in '{}'", user, vhost)
+            }
+            TenantObject::TopicPermissions { vhost, user } => {
+                write!(f, "topic permissions of user '{}' in '{}'", user, vhost)
+            }
+            TenantObject::Limit { vhost, kind } => write!(f, "limit '{}' on '{}'", kind, vhost),
+            TenantObject::Policy { vhost, name } => write!(f, "policy '{}' in '{}'", name, vhost),
+            TenantObject::Exchange { vhost, name } => {
+                write!(f, "exchange '{}' in '{}'", name, vhost)
+            }
+            TenantObject::Queue { vhost, name } => write!(f, "queue '{}' in '{}'", name, vhost),
+            TenantObject::Binding {
+                vhost,
+                source,
+                destination,
+                destination_type,
+                routing_key,
+                ..
+            } => write!(
+                f,
+                "binding of {} '{}' to exchange '{}' with routing key '{}' in '{}'",
+                destination_type, destination, source, routing_key, vhost
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TenantStep {
+    CreateVirtualHost {
+        name: String,
+        spec: VirtualHostSpec,
+    },
+    CreateUser(UserSpec),
+    GrantPermissions {
+        vhost: String,
+        spec: PermissionsSpec,
+    },
+    GrantTopicPermissions {
+        vhost: String,
+        spec: TopicPermissionsSpec,
+    },
+    DeclarePolicy {
+        vhost: String,
+        spec: PolicySpec,
+    },
+    DeclareExchange {
+        vhost: String,
+        spec: ExchangeSpec,
+    },
+    DeclareQueue {
+        vhost: String,
+        spec: QueueSpec,
+    },
+    DeclareBinding {
+        vhost: String,
+        spec: BindingSpec,
+    },
+    SetLimit {
+        vhost: String,
+        kind: String,
+        value: i64,
+    },
+    /// Stores the objects created for the tenant in a global runtime parameter
+    SaveRecord {
+        tenant: String,
+    },
+}
+
+impl TenantStep {
+    /// The object this step creates, if any.
+    pub fn object(&self) -> Option<TenantObject> {
+        let object = match self {
+            TenantStep::CreateVirtualHost { name, .. } => {
+                TenantObject::VirtualHost { name: name.clone() }
+            }
+            TenantStep::CreateUser(spec) => TenantObject::User {
+                name: spec.name.clone(),
+            },
+            TenantStep::GrantPermissions { vhost, spec } => TenantObject::Permissions {
+                vhost: vhost.clone(),
+                user: spec.user.clone(),
+            },
+            TenantStep::GrantTopicPermissions { vhost, spec } => TenantObject::TopicPermissions {
+                vhost: vhost.clone(),
+                user: spec.user.clone(),
+            },
+            TenantStep::DeclarePolicy { vhost, spec } => TenantObject::Policy {
+                vhost: vhost.clone(),
+                name: spec.name.clone(),
+            },
+            TenantStep::DeclareExchange { vhost, spec } => TenantObject::Exchange {
+                vhost: vhost.clone(),
+                name: spec.name.clone(),
+            },
+            TenantStep::DeclareQueue { vhost, spec } => TenantObject::Queue {
+                vhost: vhost.clone(),
+                name: spec.name.clone(),
+            },
+            TenantStep::DeclareBinding { vhost, spec } => TenantObject::Binding {
+                vhost: vhost.clone(),
+                source: spec.source.clone(),
+                destination: spec.destination.clone(),
+                destination_type: spec.destination_type.clone(),
+                routing_key: spec.routing_key.clone(),
+                arguments: spec.arguments.clone(),
+            },
+            TenantStep::SetLimit { vhost, kind, .. } => TenantObject::Limit {
+                vhost: vhost.clone(),
+                kind: kind.clone(),
+            },
+            TenantStep::SaveRecord { .. } => return None,
+        };
+        Some(object)
+    }
+}
+
+impl fmt::Display for TenantStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TenantStep::CreateVirtualHost { .. }
+            | TenantStep::CreateUser(_)
+            | TenantStep::DeclarePolicy { .. }
+            | TenantStep::DeclareExchange { .. }
+            | TenantStep::DeclareQueue { .. } => {
+                write!(f, "declare {}", self.object().unwrap())
+            }
+            TenantStep::GrantPermissions { .. } => write!(f, "grant {}", self.object().unwrap()),
+            TenantStep::GrantTopicPermissions { vhost, spec } => write!(
+                f,
+                "grant topic permissions for exchange '{}' to user '{}' in '{}'",
+                spec.exchange, spec.user, vhost
+            ),
+            TenantStep::DeclareBinding { .. } => write!(f, "declare {}", self.object().unwrap()),
+            TenantStep::SetLimit { vhost, kind, value } => {
+                write!(f, "set limit '{}' to {} on '{}'", kind, value, vhost)
+            }
+            TenantStep::SaveRecord { tenant } => {
+                write!(f, "save the provisioning record of tenant '{}'", tenant)
+            }
+        }
+    }
+}
+
+/// Produces the provisioning steps for `spec`.
+///
+/// Limits are set after the topology is declared so that a `max-queues` limit
+/// cannot fail the provisioning, and the record is saved last.
+pub fn plan_provisioning(spec: &TenantSpec) -> Vec<TenantStep> {
+    let vhost = spec.vhost_name().to_owned();
+    let default_queue_type = spec
+        .vhost
+        .default_queue_type
+        .clone()
+        .unwrap_or_else(|| DEFAULT_QUEUE_TYPE.to_owned());
+
+    let mut steps = vec![TenantStep::CreateVirtualHost {
+        name: vhost.clone(),
+        spec: spec.vhost.clone(),
+    }];
+    steps.extend(spec.users.iter().cloned().map(TenantStep::CreateUser));
+    steps.extend(
+        spec.permissions
+            .iter()
+            .map(|p| TenantStep::GrantPermissions {
+                vhost: vhost.clone(),
+                spec: p.clone(),
+            }),
+    );
+    steps.extend(
+        spec.topic_permissions
+            .iter()
+            .map(|p| TenantStep::GrantTopicPermissions {
+                vhost: vhost.clone(),
+                spec: p.clone(),
+            }),
+    );
+    steps.extend(spec.policies.iter().map(|p| TenantStep::DeclarePolicy {
+        vhost: vhost.clone(),
+        spec: p.clone(),
+    }));
+    steps.extend(spec.exchanges.iter().map(|x| TenantStep::DeclareExchange {
+        vhost: vhost.clone(),
+        spec: x.clone(),
+    }));
+    steps.extend(spec.queues.iter().map(|q| {
+        let mut q = q.clone();
+        q.queue_type
+            .get_or_insert_with(|| default_queue_type.clone());
+        TenantStep::DeclareQueue {
+            vhost: vhost.clone(),
+            spec: q,
+        }
+    }));
+    steps.extend(spec.bindings.iter().map(|b| TenantStep::DeclareBinding {
+        vhost: vhost.clone(),
+        spec: b.clone(),
+    }));
+    steps.extend(
+        spec.limits
+            .iter()
+            .map(|(kind, value)| TenantStep::SetLimit {
+                vhost: vhost.clone(),
+                kind: kind.clone(),
+                value: *value,
+            }),
+    );
+    steps.push(TenantStep::SaveRecord {
+        tenant: spec.name.clone(),
+    });
+
+    steps
+}
+
+/// The objects created for a tenant, stored as the value of a global runtime parameter.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TenantRecord {
+    pub tenant: String,
+    pub objects: Vec<TenantObject>,
+}
+
+impl TenantRecord {
+    pub fn new(tenant: &str) -> Self {
+        Self {
+            tenant: tenant.to_owned(),
+            objects: Vec::new(),
+        }
+    }
+
+    pub fn parameter_name(&self) -> String {
+        record_parameter_name(&self.tenant)
+    }
+
+    /// Appends objects that are not yet recorded, preserving creation order.
+    pub fn extend(&mut self, objects: impl IntoIterator<Item = TenantObject>) {
+        for object in objects {
+            if !self.objects.contains(&object) {
+                self.objects.push(object);
+            }
+        }
+    }
+
+    pub fn to_parameter_value(&self) -> Map<String, Value> {
+        match serde_json::to_value(self) {
+            Ok(Value::Object(map)) => map,
+            _ => Map::new(),
+        }
+    }
+
+    pub fn from_parameter_value(value: Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+pub fn record_parameter_name(tenant: &str) -> String {
+    format!("{}{}", TENANT_RECORD_PARAMETER_PREFIX, tenant)
+}
+
+/// Returns the objects to remove, in reverse creation order.
+///
+/// Objects that belong to a virtual host that is itself removed are left out,
+/// since deleting the virtual host deletes them as well.
+pub fn plan_removal(objects: &[TenantObject]) -> Vec<TenantObject> {
+    let removed_vhosts: HashSet<&str> = objects
+        .iter()
+        .filter_map(|o| match o {
+            TenantObject::VirtualHost { name } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    let mut removal: Vec<TenantObject> = Vec::new();
+
+    for object in objects.iter().rev() {
+        if object.vhost().is_some_and(|vh| removed_vhosts.contains(vh)) || removal.contains(object)
+        {
+            continue;
+        }
+        removal.push(object.clone());
+    }
+    removal
+}
@@ -0,0 +1,555 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! User-defined definition transformations, loaded from a TOML rules file.
+//!
+//! A rules file is a list of `[[rule]]` tables applied in order:
+//!
+//! ```toml
+//! [[rule]]
+//! action = "drop"
+//! kind = "queues"
+//! name_pattern = "^tmp\\."
+//!
+//! [[rule]]
+//! action = "rename_vhost"
+//! from = "staging"
+//! to = "production"
+//!
+//! [[rule]]
+//! action = "set_queue_argument"
+//! name_pattern = "^orders\\."
+//! key = "x-queue-type"
+//! value = "quorum"
+//!
+//! [[rule]]
+//! action = "replace_uri_host"
+//! from = "old-upstream.eng.example.com"
+//! to = "upstream.eng.example.com"
+//! ```
+//!
+//! This module also implements `redact_uri_credentials`, a transformation that
+//! replaces the credentials in shovel and federation upstream URIs with placeholders,
+//! and its import-time counterpart that fills the placeholders back in.
+
+use crate::constants::DEFAULT_VHOST;
+use crate::definitions::{self, DefinitionKind};
+use crate::errors::CommandRunError;
+use rabbitmq_http_client::uris::UriBuilder;
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// Names of the built-in transformations supported by `definitions export`.
+pub const CLUSTER_WIDE_TRANSFORMATIONS: [&str; 10] = [
+    "prepare_for_quorum_queue_migration",
+    "strip_cmq_keys_from_policies",
+    "drop_empty_policies",
+    "obfuscate_usernames",
+    "exclude_users",
+    "exclude_permissions",
+    "exclude_runtime_parameters",
+    "exclude_policies",
+    REDACT_URI_CREDENTIALS,
+    "no_op",
+];
+
+/// Names of the built-in transformations supported by `definitions export_from_vhost`.
+pub const VHOST_TRANSFORMATIONS: [&str; 5] = [
+    "prepare_for_quorum_queue_migration",
+    "strip_cmq_keys_from_policies",
+    "drop_empty_policies",
+    REDACT_URI_CREDENTIALS,
+    "no_op",
+];
+
+/// Unlike the rest of the built-in transformations, this one is implemented
+/// by this tool and not by the HTTP API client's transformation chains.
+pub const REDACT_URI_CREDENTIALS: &str = "redact_uri_credentials";
+
+/// Credential placeholders are stored in the URI username position,
+/// e.g. `amqp://$SHOVEL_STAGING_ORDERS_SRC_URI@remote.example.com:5672`.
+const PLACEHOLDER_PREFIX: char = '$';
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TransformationRules {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<TransformationRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TransformationRule {
+    /// Drops objects of a kind whose names match a pattern.
+    /// Dropping virtual hosts also drops all objects in them,
+    /// dropping queues or exchanges also drops their bindings.
+    Drop {
+        kind: DefinitionKind,
+        #[serde(deserialize_with = "deserialize_regex")]
+        name_pattern: Regex,
+    },
+    /// Renames a virtual host, including all references to it, such as
+    /// the virtual host in local (host-less) shovel and federation upstream URIs.
+    /// Has no effect on virtual host-specific definitions other than the URIs.
+    RenameVhost { from: String, to: String },
+    /// Sets an optional argument on queues, optionally only those whose names match.
+    SetQueueArgument {
+        #[serde(default, deserialize_with = "deserialize_optional_regex")]
+        name_pattern: Option<Regex>,
+        key: String,
+        value: Value,
+    },
+    /// Removes an optional argument from queues, optionally only those whose names match.
+    RemoveQueueArgument {
+        #[serde(default, deserialize_with = "deserialize_optional_regex")]
+        name_pattern: Option<Regex>,
+        key: String,
+    },
+    /// Rewrites policy patterns using a regular expression replacement
+    /// (`$1`-style group references are supported).
+    RewritePolicyPattern {
+        #[serde(default, deserialize_with = "deserialize_optional_regex")]
+        name_pattern: Option<Regex>,
+        #[serde(deserialize_with = "deserialize_regex")]
+        from: Regex,
+        to: String,
+    },
+    /// Replaces the host in shovel `src-uri`/`dest-uri` and federation upstream `uri` values.
+    ReplaceUriHost { from: String, to: String },
+}
+
+impl TransformationRules {
+    pub fn from_toml(input: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(input)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, CommandRunError> {
+        let contents = fs::read_to_string(path).map_err(|err| {
+            let message = format!(
+                "`{}` does not exist or is not readable: {}",
+                path.display(),
+                err
+            );
+            CommandRunError::FailureDuringExecution { message }
+        })?;
+
+        Self::from_toml(&contents).map_err(|err| {
+            let message = format!(
+                "`{}` is not a valid transformation rules file: {}",
+                path.display(),
+                err
+            );
+            CommandRunError::FailureDuringExecution { message }
+        })
+    }
+
+    /// Applies all rules, in order. Works with both cluster-wide and
+    /// virtual host-specific definitions.
+    pub fn apply(&self, defs: &mut Value) {
+        for rule in &self.rules {
+            rule.apply(defs);
+        }
+    }
+}
+
+impl TransformationRule {
+    pub fn apply(&self, defs: &mut Value) {
+        match self {
+            TransformationRule::Drop { kind, name_pattern } => {
+                drop_matching(defs, *kind, name_pattern)
+            }
+            TransformationRule::RenameVhost { from, to } => rename_vhost(defs, from, to),
+            TransformationRule::SetQueueArgument {
+                name_pattern,
+                key,
+                value,
+            } => {
+                for args in queue_arguments_mut(defs, name_pattern.as_ref()) {
+                    args.insert(key.clone(), value.clone());
+                }
+            }
+            TransformationRule::RemoveQueueArgument { name_pattern, key } => {
+                for args in queue_arguments_mut(defs, name_pattern.as_ref()) {
+                    args.remove(key);
+                }
+            }
+            TransformationRule::RewritePolicyPattern {
+                name_pattern,
+                from,
+                to,
+            } => rewrite_policy_patterns(defs, name_pattern.as_ref(), from, to),
+            TransformationRule::ReplaceUriHost { from, to } => {
+                update_parameter_uris(defs, |uri| replace_uri_host(uri, from, to))
+            }
+        }
+    }
+}
+
+/// Names of the URI-valued keys of runtime parameters, by component.
+fn uri_keys_of(component: &str) -> &'static [&'static str] {
+    match component {
+        "shovel" => &["src-uri", "dest-uri"],
+        "federation-upstream" => &["uri"],
+        _ => &[],
+    }
+}
+
+/// Identifies a URI-valued key of a shovel or federation upstream runtime parameter.
+#[derive(Debug, Clone, Copy)]
+pub struct ParameterUri<'a> {
+    pub component: &'a str,
+    /// `None` for virtual host-specific definitions
+    pub vhost: Option<&'a str>,
+    pub name: &'a str,
+    pub key: &'a str,
+    /// Position of the URI for keys that list multiple URIs
+    pub index: Option<usize>,
+}
+
+impl ParameterUri<'_> {
+    /// The name of the credential placeholder for this URI, for example,
+    /// `SHOVEL_STAGING_ORDERS_SRC_URI` or `FEDERATION_UPSTREAM_ORIGIN_URI_2`.
+    /// This is also the name of the environment variable the credentials are
+    /// looked up in on import.
+    pub fn placeholder_name(&self) -> String {
+        let index = self.index.map(|i| (i + 1).to_string());
+        let parts = [
+            Some(self.component),
+            self.vhost,
+            Some(self.name),
+            Some(self.key),
+            index.as_deref(),
+        ];
+
+        let mut name = String::new();
+        for part in parts.into_iter().flatten() {
+            for c in part.chars() {
+                let c = if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                };
+                if c != '_' || !(name.is_empty() || name.ends_with('_')) {
+                    name.push(c);
+                }
+            }
+            if !name.is_empty() && !name.ends_with('_') {
+                name.push('_');
+            }
+        }
+        name.trim_end_matches('_').to_owned()
+    }
+}
+
+/// Applies `f` to every shovel and federation upstream URI. `f` returns `None`
+/// to leave a URI unchanged.
+pub fn update_parameter_uris<F>(defs: &mut Value, mut f: F)
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    update_parameter_uris_with_location(defs, |_, uri| f(uri))
+}
+
+/// Like [`update_parameter_uris`] but also passes the location of every URI to `f`.
+pub fn update_parameter_uris_with_location<F>(defs: &mut Value, mut f: F)
+where
+    F: FnMut(&ParameterUri, &str) -> Option<String>,
+{
+    let Some(params) = definitions::collection_mut(defs, DefinitionKind::Parameters) else {
+        return;
+    };
+
+    for param in params.iter_mut() {
+        let Some(obj) = param.as_object_mut() else {
+            continue;
+        };
+        let component = obj
+            .get("component")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        let vhost = obj.get("vhost").and_then(Value::as_str).map(String::from);
+        let name = obj
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        let Some(value) = obj.get_mut("value").and_then(Value::as_object_mut) else {
+            continue;
+        };
+
+        for key in uri_keys_of(&component) {
+            let mut location = ParameterUri {
+                component: &component,
+                vhost: vhost.as_deref(),
+                name: &name,
+                key,
+                index: None,
+            };
+            match value.get_mut(*key) {
+                Some(Value::String(uri)) => {
+                    if let Some(updated) = f(&location, uri) {
+                        *uri = updated;
+                    }
+                }
+                // federation upstreams and shovels can list multiple URIs
+                Some(Value::Array(uris)) => {
+                    for (i, item) in uris.iter_mut().enumerate() {
+                        location.index = Some(i);
+                        if let Value::String(uri) = item
+                            && let Some(updated) = f(&location, uri)
+                        {
+                            *uri = updated;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Replaces the credentials (the userinfo part) of every shovel and federation upstream URI
+/// with a placeholder named after the runtime parameter, see [`ParameterUri::placeholder_name`].
+/// URIs without credentials are left unchanged.
+pub fn redact_uri_credentials(defs: &mut Value) {
+    update_parameter_uris_with_location(defs, |location, uri| {
+        redact_credentials(uri, &location.placeholder_name())
+    })
+}
+
+/// Substitutes the placeholders left by [`redact_uri_credentials`]. `lookup` returns
+/// a `username:password` value for a placeholder name.
+///
+/// Returns the names of the placeholders `lookup` had no value for, such URIs are left unchanged.
+pub fn substitute_uri_credentials<F>(defs: &mut Value, lookup: F) -> Vec<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut missing = Vec::new();
+    update_parameter_uris(defs, |uri| {
+        let mut url = Url::parse(uri).ok()?;
+        let placeholder = url
+            .username()
+            .strip_prefix(PLACEHOLDER_PREFIX)
+            .filter(|_| url.password().is_none())?
+            .to_owned();
+        let Some(credentials) = lookup(&placeholder) else {
+            if !missing.contains(&placeholder) {
+                missing.push(placeholder);
+            }
+            return None;
+        };
+
+        let (username, password) = match credentials.split_once(':') {
+            Some((u, p)) => (u, Some(p)),
+            None => (credentials.as_str(), None),
+        };
+        url.set_username(username).ok()?;
+        url.set_password(password).ok()?;
+        Some(url.to_string())
+    });
+    missing
+}
+
+/// Loads a TOML file that maps credential placeholder names to `username:password` values.
+pub fn load_uri_credentials(path: &Path) -> Result<HashMap<String, String>, CommandRunError> {
+    let contents = fs::read_to_string(path).map_err(|err| {
+        let message = format!(
+            "`{}` does not exist or is not readable: {}",
+            path.display(),
+            err
+        );
+        CommandRunError::FailureDuringExecution { message }
+    })?;
+
+    toml::from_str(&contents).map_err(|err| {
+        let message = format!(
+            "`{}` is not a valid URI credentials file: {}",
+            path.display(),
+            err
+        );
+        CommandRunError::FailureDuringExecution { message }
+    })
+}
+
+fn redact_credentials(uri: &str, placeholder: &str) -> Option<String> {
+    let mut ub = UriBuilder::new(uri).ok()?;
+    let url = ub.as_url();
+    if url.username().is_empty() && url.password().is_none() {
+        return None;
+    }
+
+    let mut url = url.clone();
+    url.set_username(&format!("{}{}", PLACEHOLDER_PREFIX, placeholder))
+        .ok()?;
+    url.set_password(None).ok()?;
+    Some(url.to_string())
+}
+
+fn replace_uri_host(uri: &str, from: &str, to: &str) -> Option<String> {
+    let mut url = Url::parse(uri).ok()?;
+    if url.host_str() != Some(from) {
+        return None;
+    }
+    url.set_host(Some(to)).ok()?;
+    Some(url.to_string())
+}
+
+fn drop_matching(defs: &mut Value, kind: DefinitionKind, pattern: &Regex) {
+    let mut dropped: Vec<(Option<String>, String)> = Vec::new();
+    if let Some(items) = definitions::collection_mut(defs, kind) {
+        items.retain(|obj| {
+            if kind.name_matches(obj, pattern) {
+                let name = definitions::str_field(obj, "name").unwrap_or_default();
+                dropped.push((
+                    definitions::vhost_of(obj).map(String::from),
+                    name.to_owned(),
+                ));
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    match kind {
+        DefinitionKind::Vhosts => {
+            for other in DefinitionKind::ALL {
+                if let Some(items) = definitions::collection_mut(defs, other) {
+                    items.retain(|obj| {
+                        !definitions::vhost_of(obj)
+                            .is_some_and(|vh| dropped.iter().any(|(_, name)| name == vh))
+                    });
+                }
+            }
+        }
+        DefinitionKind::Queues | DefinitionKind::Exchanges => {
+            let is_dropped = |obj: &Value, field: &str| {
+                let vhost = definitions::vhost_of(obj);
+                let name = definitions::str_field(obj, field);
+                dropped
+                    .iter()
+                    .any(|(vh, n)| vh.as_deref() == vhost && Some(n.as_str()) == name)
+            };
+            let destination_type = match kind {
+                DefinitionKind::Queues => "queue",
+                _ => "exchange",
+            };
+
+            if let Some(bindings) = definitions::collection_mut(defs, DefinitionKind::Bindings) {
+                bindings.retain(|b| {
+                    let source_dropped =
+                        kind == DefinitionKind::Exchanges && is_dropped(b, "source");
+                    let destination_dropped = definitions::str_field(b, "destination_type")
+                        == Some(destination_type)
+                        && is_dropped(b, "destination");
+                    !(source_dropped || destination_dropped)
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn rename_vhost(defs: &mut Value, from: &str, to: &str) {
+    if let Some(vhosts) = definitions::collection_mut(defs, DefinitionKind::Vhosts) {
+        for vh in vhosts.iter_mut() {
+            if definitions::str_field(vh, "name") == Some(from) {
+                vh["name"] = Value::String(to.to_owned());
+            }
+        }
+    }
+
+    for kind in DefinitionKind::ALL {
+        if let Some(items) = definitions::collection_mut(defs, kind) {
+            for obj in items.iter_mut() {
+                if definitions::vhost_of(obj) == Some(from) {
+                    obj["vhost"] = Value::String(to.to_owned());
+                }
+            }
+        }
+    }
+
+    update_parameter_uris(defs, |uri| rename_uri_vhost(uri, from, to));
+}
+
+// URIs with a host may refer to a virtual host with the same name in a different cluster,
+// so only local URIs such as `amqp:///staging` are updated
+fn rename_uri_vhost(uri: &str, from: &str, to: &str) -> Option<String> {
+    let mut url = Url::parse(uri).ok()?;
+    if url.host_str().is_some_and(|host| !host.is_empty()) {
+        return None;
+    }
+    let vhost = match url.path() {
+        "" => DEFAULT_VHOST.to_owned(),
+        path => definitions::percent_decode(path.strip_prefix('/').unwrap_or(path))?,
+    };
+    if vhost != from {
+        return None;
+    }
+    url.set_path(&format!("/{}", definitions::percent_encode(to)));
+    Some(url.to_string())
+}
+
+fn queue_arguments_mut<'a>(
+    defs: &'a mut Value,
+    name_pattern: Option<&'a Regex>,
+) -> impl Iterator<Item = &'a mut Map<String, Value>> {
+    definitions::collection_mut(defs, DefinitionKind::Queues)
+        .into_iter()
+        .flatten()
+        .filter(move |q| name_pattern.is_none_or(|re| DefinitionKind::Queues.name_matches(q, re)))
+        .filter_map(|q| {
+            let q = q.as_object_mut()?;
+            let args = q
+                .entry("arguments")
+                .or_insert_with(|| Value::Object(Map::new()));
+            args.as_object_mut()
+        })
+}
+
+fn rewrite_policy_patterns(defs: &mut Value, name_pattern: Option<&Regex>, from: &Regex, to: &str) {
+    let Some(policies) = definitions::collection_mut(defs, DefinitionKind::Policies) else {
+        return;
+    };
+
+    for policy in policies.iter_mut() {
+        if name_pattern.is_some_and(|re| !DefinitionKind::Policies.name_matches(policy, re)) {
+            continue;
+        }
+        if let Some(Value::String(pattern)) = policy.get_mut("pattern") {
+            *pattern = from.replace_all(pattern, to).into_owned();
+        }
+    }
+}
+
+fn deserialize_regex<'de, D>(deserializer: D) -> Result<Regex, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Regex::new(&s).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_optional_regex<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_regex(deserializer).map(Some)
+}
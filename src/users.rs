@@ -0,0 +1,517 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for user objects in definitions: definitions are the only
+//! representation that carries the password hashing algorithm, so updating
+//! a user through them never touches its credentials.
+
+use crate::definitions::{DefinitionKind, collection, str_field};
+use rabbitmq_http_client::password_hashing::{self, HashingAlgorithm};
+use serde_json::{Value, json};
+use std::fmt;
+
+/// Parses a comma-separated list of tags.
+pub fn parse_tags(s: &str) -> Vec<String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Tags of a user object. Definitions exported by older RabbitMQ versions
+/// use a comma-separated string instead of a list.
+pub fn tags_of(user: &Value) -> Vec<String> {
+    match user.get("tags") {
+        Some(Value::Array(tags)) => tags
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_owned)
+            .collect(),
+        Some(Value::String(tags)) => parse_tags(tags),
+        _ => Vec::new(),
+    }
+}
+
+pub fn has_tag(user: &Value, tag: &str) -> bool {
+    tags_of(user).iter().any(|t| t == tag)
+}
+
+/// Returns a copy of the user object with its tags replaced, in the same
+/// shape (list or string) the original used.
+pub fn with_tags(user: &Value, tags: &[String]) -> Value {
+    let mut updated = user.clone();
+    let value = match user.get("tags") {
+        Some(Value::String(_)) => Value::from(tags.join(",")),
+        _ => Value::from(tags.to_vec()),
+    };
+    if let Some(obj) = updated.as_object_mut() {
+        obj.insert("tags".to_owned(), value);
+    }
+    updated
+}
+
+/// A user object for a definitions document. Importing definitions is the only way to pass
+/// the hashing algorithm (a module name, e.g. `rabbit_password_hashing_sha512`) of a password hash.
+pub fn user_definition(
+    name: &str,
+    password_hash: &str,
+    hashing_algorithm: &str,
+    tags: &[String],
+) -> Value {
+    json!({
+        "name": name,
+        "password_hash": password_hash,
+        "hashing_algorithm": hashing_algorithm,
+        "tags": tags,
+    })
+}
+
+/// Returns a copy of the user object with its password hash and hashing algorithm
+/// (a module name, e.g. `rabbit_password_hashing_sha512`) replaced. Tags and limits are kept.
+pub fn with_password_hash(user: &Value, password_hash: &str, hashing_algorithm: &str) -> Value {
+    let mut updated = user.clone();
+    if let Some(obj) = updated.as_object_mut() {
+        obj.insert("password_hash".to_owned(), Value::from(password_hash));
+        obj.insert(
+            "hashing_algorithm".to_owned(),
+            Value::from(hashing_algorithm),
+        );
+    }
+    updated
+}
+
+pub fn find_user<'a>(defs: &'a Value, name: &str) -> Option<&'a Value> {
+    collection(defs, DefinitionKind::Users)
+        .iter()
+        .find(|u| str_field(u, "name") == Some(name))
+}
+
+/// Changes to a user's tags: either a complete replacement, or tags to add and remove.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagChanges {
+    pub replace: Option<Vec<String>>,
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl TagChanges {
+    /// Applies the changes, preserving the order of existing tags.
+    pub fn apply(&self, current: &[String]) -> Vec<String> {
+        let mut tags: Vec<String> = self.replace.as_deref().unwrap_or(current).to_vec();
+        for tag in &self.add {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.retain(|t| !self.remove.contains(t));
+        tags
+    }
+}
+
+//
+// CSV import and export
+//
+
+pub const CSV_COLUMNS: [&str; 9] = [
+    "name",
+    "password",
+    "password_hash",
+    "hashing_algorithm",
+    "tags",
+    "vhost",
+    "configure",
+    "write",
+    "read",
+];
+
+const SHA256_MODULE: &str = "rabbit_password_hashing_sha256";
+const SHA512_MODULE: &str = "rabbit_password_hashing_sha512";
+/// The legacy MD5 module, only supported for existing password hashes
+pub const MD5_MODULE: &str = "rabbit_password_hashing_md5";
+
+/// Maps a hashing algorithm name as used by `--hashing-algorithm` (or the
+/// RabbitMQ module name) to the module name definitions use. MD5 is only
+/// accepted so that users with legacy password hashes can be re-imported.
+pub fn hashing_algorithm_module(name: &str) -> Option<&'static str> {
+    match name.to_uppercase().as_str() {
+        "" | "SHA256" | "SHA-256" => Some(SHA256_MODULE),
+        "SHA512" | "SHA-512" => Some(SHA512_MODULE),
+        "MD5" => Some(MD5_MODULE),
+        _ if name == SHA256_MODULE => Some(SHA256_MODULE),
+        _ if name == SHA512_MODULE => Some(SHA512_MODULE),
+        _ if name == MD5_MODULE => Some(MD5_MODULE),
+        _ => None,
+    }
+}
+
+/// The module name definitions use for an algorithm passwords can be hashed with locally.
+pub fn module_of(algorithm: &HashingAlgorithm) -> &'static str {
+    match algorithm {
+        HashingAlgorithm::SHA256 => SHA256_MODULE,
+        HashingAlgorithm::SHA512 => SHA512_MODULE,
+    }
+}
+
+/// The reverse of [`hashing_algorithm_module`]. Other modules are returned as is.
+pub fn hashing_algorithm_name(module: &str) -> &str {
+    match module {
+        SHA256_MODULE => "SHA256",
+        SHA512_MODULE => "SHA512",
+        MD5_MODULE => "MD5",
+        other => other,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionRecord {
+    pub vhost: String,
+    pub configure: String,
+    pub write: String,
+    pub read: String,
+}
+
+/// A user to import, assembled from one or more CSV rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserRecord {
+    pub name: String,
+    /// The first row of the user in the file
+    pub row: usize,
+    pub password: Option<String>,
+    pub password_hash: Option<String>,
+    /// Hashing algorithm module name, e.g. `rabbit_password_hashing_sha256`
+    pub hashing_algorithm: &'static str,
+    pub tags: Vec<String>,
+    pub permissions: Vec<PermissionRecord>,
+    /// Validation errors of the user's rows. A user with errors is not imported.
+    pub errors: Vec<String>,
+}
+
+impl UserRecord {
+    fn new(name: &str, row: usize) -> Self {
+        UserRecord {
+            name: name.to_string(),
+            row,
+            password: None,
+            password_hash: None,
+            hashing_algorithm: SHA256_MODULE,
+            tags: Vec::new(),
+            permissions: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// The user name, or the row for rows without one.
+    pub fn item_name(&self) -> String {
+        if self.name.is_empty() {
+            format!("row {}", self.row)
+        } else {
+            self.name.clone()
+        }
+    }
+
+    /// Definitions with this user and its permissions. A plaintext password is
+    /// salted and hashed locally and never leaves this process.
+    pub fn to_definitions(&self) -> Result<Value, String> {
+        let password_hash = match (&self.password, &self.password_hash) {
+            (_, Some(hash)) => hash.clone(),
+            (Some(password), None) => {
+                let algorithm =
+                    HashingAlgorithm::from(hashing_algorithm_name(self.hashing_algorithm));
+                algorithm
+                    .salt_and_hash(&password_hashing::salt(), password)
+                    .map_err(|e| format!("password hashing failed: {}", e))?
+            }
+            (None, None) => String::new(),
+        };
+        let permissions: Vec<Value> = self
+            .permissions
+            .iter()
+            .map(|p| {
+                json!({
+                    "user": self.name,
+                    "vhost": p.vhost,
+                    "configure": p.configure,
+                    "write": p.write,
+                    "read": p.read,
+                })
+            })
+            .collect();
+        Ok(json!({
+            "users": [user_definition(
+                &self.name,
+                &password_hash,
+                self.hashing_algorithm,
+                &self.tags,
+            )],
+            "permissions": permissions,
+        }))
+    }
+
+    /// Merges a CSV row into the user.
+    fn merge_row(&mut self, cell: impl Fn(&str) -> String) -> Result<(), String> {
+        let name = self.name.clone();
+        if name.is_empty() {
+            return Err("the name is empty".to_string());
+        }
+        let algorithm = hashing_algorithm_module(&cell("hashing_algorithm")).ok_or_else(|| {
+            format!(
+                "unsupported hashing algorithm '{}', use SHA256, SHA512 or (for existing password hashes only) MD5",
+                cell("hashing_algorithm")
+            )
+        })?;
+
+        merge_cell(&mut self.password, &cell("password"))
+            .map_err(|_| format!("conflicting passwords for user '{}'", name))?;
+        merge_cell(&mut self.password_hash, &cell("password_hash"))
+            .map_err(|_| format!("conflicting password hashes for user '{}'", name))?;
+        if !cell("hashing_algorithm").is_empty() {
+            self.hashing_algorithm = algorithm;
+        }
+        let tags = parse_tags(&cell("tags"));
+        if !tags.is_empty() {
+            if !self.tags.is_empty() && self.tags != tags {
+                return Err(format!("conflicting tags for user '{}'", name));
+            }
+            self.tags = tags;
+        }
+
+        let vhost = cell("vhost");
+        let patterns = [cell("configure"), cell("write"), cell("read")];
+        if vhost.is_empty() {
+            if patterns.iter().any(|p| !p.is_empty()) {
+                return Err("permissions require a virtual host".to_string());
+            }
+        } else if self.permissions.iter().any(|p| p.vhost == vhost) {
+            return Err(format!(
+                "duplicate permissions for user '{}' in virtual host '{}'",
+                name, vhost
+            ));
+        } else {
+            let [configure, write, read] = patterns;
+            self.permissions.push(PermissionRecord {
+                vhost,
+                configure,
+                write,
+                read,
+            });
+        }
+        Ok(())
+    }
+
+    /// Validates the credentials once all of the user's rows were merged.
+    fn validate_credentials(&self) -> Result<(), String> {
+        match (&self.password, &self.password_hash) {
+            (None, None) => Err(format!(
+                "user '{}' has neither a password nor a password hash",
+                self.name
+            )),
+            (Some(_), Some(_)) => Err(format!(
+                "user '{}' has both a password and a password hash, provide only one",
+                self.name
+            )),
+            (Some(_), None) if self.hashing_algorithm == MD5_MODULE => Err(format!(
+                "user '{}' uses MD5, which is only supported for existing password hashes",
+                self.name
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for UserRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.name.is_empty() {
+            write!(f, "row {}", self.row)?;
+        } else {
+            write!(f, "user '{}'", self.name)?;
+        }
+        match self.permissions.len() {
+            0 => {}
+            1 => write!(f, " with permissions in 1 virtual host")?,
+            n => write!(f, " with permissions in {} virtual hosts", n)?,
+        }
+        if !self.errors.is_empty() {
+            write!(f, " (invalid: {})", self.errors.join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Assembles users from CSV rows. The first row is a header naming the columns
+/// (any subset of [`CSV_COLUMNS`] that includes `name`). A user with permissions in
+/// several virtual hosts spans several rows; the credentials and tags can be repeated
+/// on every row or provided on one of them only.
+///
+/// Malformed CSV and an invalid header fail the entire file. Invalid rows are recorded
+/// in [`UserRecord::errors`] of the user they belong to, so that other users can still be imported.
+pub fn users_from_csv(input: &str) -> Result<Vec<UserRecord>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(input.as_bytes());
+    let header: Vec<String> = reader
+        .headers()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    if header.is_empty() {
+        return Err("the file is empty".to_string());
+    }
+    for column in &header {
+        if !CSV_COLUMNS.contains(&column.as_str()) {
+            return Err(format!(
+                "unknown column '{}', supported columns are: {}",
+                column,
+                CSV_COLUMNS.join(", ")
+            ));
+        }
+    }
+    if !header.iter().any(|h| h == "name") {
+        return Err("the header must include a 'name' column".to_string());
+    }
+
+    let mut users: Vec<UserRecord> = Vec::new();
+    for (index, row) in reader.records().enumerate() {
+        let row = row.map_err(|e| e.to_string())?;
+        // the header is row 1
+        let row_number = index + 2;
+        let cell = |column: &str| -> String {
+            header
+                .iter()
+                .position(|h| h == column)
+                .and_then(|i| row.get(i))
+                .map(|v| v.trim().to_string())
+                .unwrap_or_default()
+        };
+
+        let name = cell("name");
+        let position = users
+            .iter()
+            .position(|u| !name.is_empty() && u.name == name);
+        let user = match position {
+            Some(i) => &mut users[i],
+            None => {
+                users.push(UserRecord::new(&name, row_number));
+                users.last_mut().unwrap()
+            }
+        };
+        if let Err(message) = user.merge_row(cell) {
+            user.errors.push(format!("row {}: {}", row_number, message));
+        }
+    }
+
+    // credentials are incomplete if any of the user's rows could not be merged
+    for user in users.iter_mut().filter(|u| u.errors.is_empty()) {
+        if let Err(message) = user.validate_credentials() {
+            user.errors.push(message);
+        }
+    }
+    Ok(users)
+}
+
+fn merge_cell(target: &mut Option<String>, value: &str) -> Result<(), ()> {
+    if value.is_empty() {
+        return Ok(());
+    }
+    match target {
+        Some(existing) if existing != value => Err(()),
+        _ => {
+            *target = Some(value.to_string());
+            Ok(())
+        }
+    }
+}
+
+/// Formats the users and permissions in definitions as CSV, one row per user
+/// and virtual host. Password hashes are only included when asked for.
+pub fn users_to_csv(defs: &Value, include_password_hashes: bool) -> String {
+    let columns: Vec<&str> = CSV_COLUMNS
+        .iter()
+        .copied()
+        .filter(|c| *c != "password")
+        .filter(|c| include_password_hashes || !matches!(*c, "password_hash" | "hashing_algorithm"))
+        .collect();
+
+    let mut users: Vec<&Value> = collection(defs, DefinitionKind::Users).iter().collect();
+    users.sort_by_key(|u| str_field(u, "name").unwrap_or_default());
+    let permissions = collection(defs, DefinitionKind::Permissions);
+
+    // writing to a Vec<u8> cannot fail
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&columns).unwrap();
+    for user in users {
+        let name = str_field(user, "name").unwrap_or_default();
+        let tags = tags_of(user).join(",");
+        let mut granted: Vec<&Value> = permissions
+            .iter()
+            .filter(|p| str_field(p, "user") == Some(name))
+            .collect();
+        granted.sort_by_key(|p| str_field(p, "vhost").unwrap_or_default());
+
+        let grants: Vec<Option<&Value>> = if granted.is_empty() {
+            vec![None]
+        } else {
+            granted.into_iter().map(Some).collect()
+        };
+        for grant in grants {
+            let field = |key: &str| grant.and_then(|p| str_field(p, key)).unwrap_or_default();
+            let row: Vec<&str> = columns
+                .iter()
+                .map(|column| match *column {
+                    "name" => name,
+                    "password_hash" => str_field(user, "password_hash").unwrap_or_default(),
+                    "hashing_algorithm" => hashing_algorithm_name(
+                        str_field(user, "hashing_algorithm").unwrap_or(SHA256_MODULE),
+                    ),
+                    "tags" => tags.as_str(),
+                    other => field(other),
+                })
+                .collect();
+            writer.write_record(&row).unwrap();
+        }
+    }
+    String::from_utf8(writer.into_inner().unwrap()).unwrap()
+}
+
+//
+// Bulk deletion
+//
+
+/// Decides which users a bulk deletion must skip: the currently authenticated
+/// user, and any administrator whose deletion would leave no administrators.
+/// `candidates` are `(name, is_administrator)` pairs in deletion order, `administrators`
+/// is the number of administrators that currently exist.
+pub fn deletion_protection(
+    candidates: &[(&str, bool)],
+    administrators: usize,
+    current_user: &str,
+) -> Vec<Option<String>> {
+    let mut remaining = administrators;
+    candidates
+        .iter()
+        .map(|(name, is_administrator)| {
+            if *name == current_user {
+                Some("currently authenticated user".to_string())
+            } else if *is_administrator && remaining <= 1 {
+                Some("last administrator".to_string())
+            } else {
+                if *is_administrator {
+                    remaining -= 1;
+                }
+                None
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,203 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Planning for `vhosts clone` and `vhosts rename`.
+//!
+//! RabbitMQ cannot rename virtual hosts, so both commands create a new virtual host
+//! from the metadata, limits, permissions and definitions of an existing one.
+//! The plan is a list of steps that is either previewed (`--dry-run`) or executed in order.
+
+use crate::transformations;
+use rabbitmq_http_client::responses;
+use serde_json::Value;
+use std::fmt;
+
+/// The top-level key of virtual host-specific definition files that names the exported virtual host.
+const ORIGINAL_VHOST_NAME_KEY: &str = "original_vhost_name";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CloneStep {
+    CreateVirtualHost {
+        name: String,
+        description: Option<String>,
+        tags: Vec<String>,
+        default_queue_type: Option<String>,
+    },
+    ImportDefinitions {
+        vhost: String,
+        definitions: Value,
+    },
+    GrantPermissions {
+        user: String,
+        vhost: String,
+        configure: String,
+        read: String,
+        write: String,
+    },
+    GrantTopicPermissions {
+        user: String,
+        vhost: String,
+        exchange: String,
+        read: String,
+        write: String,
+    },
+    SetLimit {
+        vhost: String,
+        kind: String,
+        value: i64,
+    },
+    DeleteVirtualHost {
+        name: String,
+    },
+}
+
+impl fmt::Display for CloneStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CloneStep::CreateVirtualHost { name, .. } => {
+                write!(f, "create virtual host '{}'", name)
+            }
+            CloneStep::ImportDefinitions { vhost, definitions } => {
+                let count = |key: &str| definitions[key].as_array().map_or(0, Vec::len);
+                write!(
+                    f,
+                    "import definitions into '{}' ({} queues, {} exchanges, {} bindings, {} policies, {} runtime parameters)",
+                    vhost,
+                    count("queues"),
+                    count("exchanges"),
+                    count("bindings"),
+                    count("policies"),
+                    count("parameters")
+                )
+            }
+            CloneStep::GrantPermissions { user, vhost, .. } => {
+                write!(f, "grant permissions to user '{}' in '{}'", user, vhost)
+            }
+            CloneStep::GrantTopicPermissions {
+                user,
+                vhost,
+                exchange,
+                ..
+            } => write!(
+                f,
+                "grant topic permissions for exchange '{}' to user '{}' in '{}'",
+                exchange, user, vhost
+            ),
+            CloneStep::SetLimit { vhost, kind, value } => {
+                write!(f, "set limit '{}' to {} on '{}'", kind, value, vhost)
+            }
+            CloneStep::DeleteVirtualHost { name } => {
+                write!(f, "delete virtual host '{}'", name)
+            }
+        }
+    }
+}
+
+/// Everything about the source virtual host that is copied to the destination.
+#[derive(Debug, Clone)]
+pub struct CloneSource {
+    pub vhost: responses::VirtualHost,
+    pub limits: Vec<responses::VirtualHostLimits>,
+    pub permissions: Vec<responses::Permissions>,
+    pub topic_permissions: Vec<responses::TopicPermission>,
+    /// Virtual host-specific definitions, as returned by `export_vhost_definitions`
+    pub definitions: Value,
+}
+
+/// Produces the steps that copy `source` to a new virtual host named `destination`,
+/// optionally followed by the deletion of the source.
+///
+/// Limits are set last so that a `max-queues` limit cannot fail the import.
+pub fn plan_clone(source: CloneSource, destination: &str, delete_source: bool) -> Vec<CloneStep> {
+    let CloneSource {
+        vhost,
+        limits,
+        permissions,
+        topic_permissions,
+        mut definitions,
+    } = source;
+
+    let mut steps = vec![CloneStep::CreateVirtualHost {
+        name: destination.to_owned(),
+        description: vhost.description.clone().filter(|d| !d.is_empty()),
+        tags: vhost
+            .tags
+            .iter()
+            .flat_map(|tags| tags.iter())
+            .cloned()
+            .collect(),
+        default_queue_type: vhost.default_queue_type.clone(),
+    }];
+
+    rewrite_vhost_references(&mut definitions, &vhost.name, destination);
+    steps.push(CloneStep::ImportDefinitions {
+        vhost: destination.to_owned(),
+        definitions,
+    });
+
+    steps.extend(
+        permissions
+            .into_iter()
+            .map(|p| CloneStep::GrantPermissions {
+                user: p.user,
+                vhost: destination.to_owned(),
+                configure: p.configure,
+                read: p.read,
+                write: p.write,
+            }),
+    );
+    steps.extend(
+        topic_permissions
+            .into_iter()
+            .map(|p| CloneStep::GrantTopicPermissions {
+                user: p.user,
+                vhost: destination.to_owned(),
+                exchange: p.exchange,
+                read: p.read,
+                write: p.write,
+            }),
+    );
+
+    steps.extend(
+        limits
+            .iter()
+            .flat_map(|l| l.limits.iter())
+            .filter_map(|(kind, value)| {
+                Some(CloneStep::SetLimit {
+                    vhost: destination.to_owned(),
+                    kind: kind.clone(),
+                    value: value.as_i64()?,
+                })
+            }),
+    );
+
+    if delete_source {
+        steps.push(CloneStep::DeleteVirtualHost { name: vhost.name });
+    }
+
+    steps
+}
+
+/// Updates references to virtual host `from` in virtual host-specific definitions.
+pub fn rewrite_vhost_references(defs: &mut Value, from: &str, to: &str) {
+    if let Some(obj) = defs.as_object_mut()
+        && obj.contains_key(ORIGINAL_VHOST_NAME_KEY)
+    {
+        obj.insert(
+            ORIGINAL_VHOST_NAME_KEY.to_owned(),
+            Value::String(to.to_owned()),
+        );
+    }
+    transformations::rename_vhost(defs, from, to);
+}
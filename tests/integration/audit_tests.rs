@@ -0,0 +1,100 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use predicates::prelude::*;
+use std::error::Error;
+
+use crate::test_helpers::*;
+
+#[test]
+fn test_audit_topology_of_definitions_file() -> Result<(), Box<dyn Error>> {
+    run_succeeds([
+        "audit",
+        "topology",
+        "--file",
+        "tests/fixtures/definitions/cluster.definitions.1.json",
+    ])
+    .stdout(
+        output_includes("guest_user")
+            .and(output_includes("quorum_queue_recommended"))
+            .and(output_includes("remediation")),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_audit_topology_of_a_live_node() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.audit_topology";
+    let q = "audit.classic.queue";
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["vhosts", "declare", "--name", vh]);
+    run_succeeds([
+        "-V",
+        vh,
+        "queues",
+        "declare",
+        "--name",
+        q,
+        "--type",
+        "classic",
+        "--durable",
+        "true",
+    ]);
+    await_queue_metric_emission();
+
+    run_succeeds(["audit", "topology"]).stdout(
+        output_includes(q)
+            .and(output_includes("quorum_queue_recommended"))
+            .and(output_includes("queue_without_consumers")),
+    );
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
+
+#[test]
+fn test_users_audit() -> Result<(), Box<dyn Error>> {
+    let username = "rabbitmqadmin.users_audit.without_permissions";
+    run_succeeds([
+        "users",
+        "declare",
+        "--name",
+        username,
+        "--password",
+        "pa$$w0rd",
+    ]);
+
+    run_succeeds(["users", "audit"]).stdout(
+        output_includes(username)
+            .and(output_includes("user_without_permissions"))
+            .and(output_includes("guest_user")),
+    );
+
+    let assert = run_succeeds(["users", "audit", "--output", "json"]);
+    let findings: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout)?;
+    assert!(
+        findings
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|f| { f["subject"] == username && f["check"] == "user_without_permissions" })
+    );
+
+    delete_user(username)?;
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::test_helpers::*;
+use std::error::Error;
+use std::fs;
+
+const CONFIG_FILE: &str = "tests/fixtures/config_files/two_nodes.toml";
+
+#[test]
+fn test_definitions_copy_with_unknown_node_alias_fails() -> Result<(), Box<dyn Error>> {
+    run_fails([
+        "--config",
+        CONFIG_FILE,
+        "definitions",
+        "copy",
+        "--from-node",
+        "blue",
+        "--to-node",
+        "does-not-exist",
+        "--dry-run",
+    ])
+    .stderr(output_includes("does-not-exist"));
+
+    Ok(())
+}
+
+#[test]
+fn test_definitions_copy_ignores_global_connection_options() -> Result<(), Box<dyn Error>> {
+    // the node aliases are configured by their sections only
+    run_succeeds([
+        "--config",
+        CONFIG_FILE,
+        "--host",
+        "does-not-exist.invalid",
+        "--port",
+        "1",
+        "definitions",
+        "copy",
+        "--from-node",
+        "blue",
+        "--to-node",
+        "green",
+        "--dry-run",
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_definitions_copy_requires_approve() -> Result<(), Box<dyn Error>> {
+    run_fails([
+        "--config",
+        CONFIG_FILE,
+        "definitions",
+        "copy",
+        "--from-node",
+        "blue",
+        "--to-node",
+        "green",
+    ])
+    .stderr(output_includes("--approve"));
+
+    Ok(())
+}
+
+#[test]
+fn test_definitions_copy_with_renamed_vhost() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.definitions_copy.test1";
+    let copy = "rabbitmqadmin.definitions_copy.test1.copy";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    delete_vhost(copy).expect("failed to delete a virtual host");
+    run_succeeds(["declare", "vhost", "--name", vh]);
+    run_succeeds([
+        "-V",
+        vh,
+        "declare",
+        "queue",
+        "--name",
+        "definitions_copy.q1",
+        "--type",
+        "quorum",
+    ]);
+
+    // both aliases point at the same node, so the copy goes into a renamed virtual host
+    let rules = std::env::temp_dir().join("rabbitmqadmin.definitions_copy.rules.toml");
+    fs::write(
+        &rules,
+        format!(
+            "[[rule]]\naction = \"rename_vhost\"\nfrom = \"{}\"\nto = \"{}\"\n",
+            vh, copy
+        ),
+    )?;
+    let include_vhosts = format!("^{}$", regex::escape(vh));
+    let args = [
+        "--config",
+        CONFIG_FILE,
+        "definitions",
+        "copy",
+        "--from-node",
+        "blue",
+        "--to-node",
+        "green",
+        "--include-vhosts",
+        &include_vhosts,
+        "--transformation-rules",
+        rules.to_str().unwrap(),
+    ];
+
+    let mut dry_run_args = args.to_vec();
+    dry_run_args.push("--dry-run");
+    run_succeeds(dry_run_args)
+        .stdout(output_includes("create"))
+        .stdout(output_includes(copy))
+        .stdout(output_includes("definitions_copy.q1"));
+    assert!(api_client().get_vhost(copy).is_err());
+
+    let mut approved_args = args.to_vec();
+    approved_args.push("--approve");
+    run_succeeds(approved_args);
+    assert!(
+        api_client()
+            .get_queue_info(copy, "definitions_copy.q1")
+            .is_ok()
+    );
+
+    let _ = fs::remove_file(&rules);
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    delete_vhost(copy).expect("failed to delete a virtual host");
+
+    Ok(())
+}
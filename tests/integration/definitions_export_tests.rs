@@ -12,8 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::test_helpers::run_succeeds;
 use crate::test_helpers::{delete_vhost, output_includes};
+use crate::test_helpers::{run_fails, run_succeeds};
 use predicates::prelude::*;
 use std::error::Error;
 
@@ -136,3 +136,198 @@ fn test_export_vhost_definitions_with_transformations_case1() -> Result<(), Box<
 
     Ok(())
 }
+
+#[test]
+fn test_export_vhost_definitions_with_transformation_rules() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.definitions_export.test4";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["declare", "vhost", "--name", vh]);
+
+    let p1 = "test_export_vhost_definitions.transformation_rules.1";
+    run_succeeds([
+        "--vhost",
+        vh,
+        "declare",
+        "policy",
+        "--name",
+        p1,
+        "--pattern",
+        "^matching\\..+",
+        "--apply-to",
+        "queues",
+        "--priority",
+        "10",
+        "--definition",
+        "{\"max-length\": 10}",
+    ]);
+
+    let q1 = "qq.test_export_vhost_definitions.transformation_rules.1";
+    let q2 = "tmp.test_export_vhost_definitions.transformation_rules.2";
+    run_succeeds([
+        "-V", vh, "declare", "queue", "--name", q1, "--type", "quorum",
+    ]);
+    run_succeeds([
+        "-V", vh, "declare", "queue", "--name", q2, "--type", "classic",
+    ]);
+
+    run_succeeds([
+        "--vhost",
+        vh,
+        "definitions",
+        "export_from_vhost",
+        "--transformation-rules",
+        "tests/fixtures/transformations/rules.1.toml",
+    ])
+    .stdout(output_includes(q1))
+    .stdout(output_includes("x-delivery-limit"))
+    .stdout(output_includes("^rewritten"))
+    .stdout(output_includes(q2).not());
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
+
+#[test]
+fn test_export_cluster_wide_definitions_with_unknown_transformation() -> Result<(), Box<dyn Error>>
+{
+    run_fails([
+        "definitions",
+        "export",
+        "--transformations",
+        "drop_empty_policies,not_a_known_transformation",
+    ])
+    .stderr(output_includes("not_a_known_transformation"));
+
+    Ok(())
+}
+
+#[test]
+fn test_export_cluster_wide_definitions_with_missing_transformation_rules_file()
+-> Result<(), Box<dyn Error>> {
+    run_fails([
+        "definitions",
+        "export",
+        "--transformation-rules",
+        "tests/fixtures/transformations/does-not-exist.toml",
+    ])
+    .stderr(output_includes("does-not-exist.toml"));
+
+    Ok(())
+}
+
+#[test]
+fn test_export_vhost_definitions_in_canonical_form() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.definitions_export.test5";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["declare", "vhost", "--name", vh]);
+
+    for q in ["canonical.b", "canonical.a", "canonical.c"] {
+        run_succeeds([
+            "-V", vh, "declare", "queue", "--name", q, "--type", "quorum",
+        ]);
+    }
+
+    let args = [
+        "--vhost",
+        vh,
+        "definitions",
+        "export_from_vhost",
+        "--canonical",
+        "--drop-volatile-fields",
+    ];
+    let first = run_succeeds(args).get_output().stdout.clone();
+    let second = run_succeeds(args).get_output().stdout.clone();
+    assert_eq!(first, second);
+
+    let output = String::from_utf8(first)?;
+    assert!(!output.contains("rabbitmq_version"));
+    let a = output.find("canonical.a").unwrap();
+    let b = output.find("canonical.b").unwrap();
+    let c = output.find("canonical.c").unwrap();
+    assert!(a < b && b < c);
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
+
+#[test]
+fn test_export_with_drop_volatile_fields_requires_canonical() -> Result<(), Box<dyn Error>> {
+    run_fails(["definitions", "export", "--drop-volatile-fields"])
+        .stderr(output_includes("--canonical"));
+
+    Ok(())
+}
+
+#[test]
+fn test_export_vhost_definitions_with_kind_and_name_filters() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.definitions_export.test6";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["declare", "vhost", "--name", vh]);
+
+    let q1 = "filters.orders.1";
+    let q2 = "filters.tmp.2";
+    for q in [q1, q2] {
+        run_succeeds([
+            "-V", vh, "declare", "queue", "--name", q, "--type", "classic",
+        ]);
+    }
+    let x1 = "filters.orders.events";
+    run_succeeds([
+        "-V", vh, "declare", "exchange", "--name", x1, "--type", "topic",
+    ]);
+
+    run_succeeds([
+        "--vhost",
+        vh,
+        "definitions",
+        "export_from_vhost",
+        "--include-kinds",
+        "queues",
+        "--exclude-names",
+        "\\.tmp\\.",
+    ])
+    .stdout(output_includes(q1))
+    .stdout(output_includes(q2).not())
+    .stdout(output_includes(x1).not());
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
+
+#[test]
+fn test_export_cluster_wide_definitions_with_vhost_filters() -> Result<(), Box<dyn Error>> {
+    let vh1 = "rabbitmqadmin.definitions_export.test7.included";
+    let vh2 = "rabbitmqadmin.definitions_export.test7.excluded";
+    for vh in [vh1, vh2] {
+        delete_vhost(vh).expect("failed to delete a virtual host");
+        run_succeeds(["declare", "vhost", "--name", vh]);
+    }
+
+    run_succeeds([
+        "definitions",
+        "export",
+        "--include-vhosts",
+        "^rabbitmqadmin\\.definitions_export\\.test7\\.",
+        "--exclude-vhosts",
+        "excluded$",
+    ])
+    .stdout(output_includes(vh1))
+    .stdout(output_includes(vh2).not());
+
+    for vh in [vh1, vh2] {
+        delete_vhost(vh).expect("failed to delete a virtual host");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_export_with_invalid_vhost_filter_fails() -> Result<(), Box<dyn Error>> {
+    run_fails(["definitions", "export", "--include-vhosts", "(unclosed"])
+        .stderr(output_includes("include_vhosts"));
+
+    Ok(())
+}
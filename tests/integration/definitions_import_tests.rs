@@ -13,8 +13,10 @@
 // limitations under the License.
 
 use crate::test_helpers::delete_vhost;
-use crate::test_helpers::run_succeeds;
+use crate::test_helpers::{output_includes, run_fails, run_succeeds};
+use predicates::prelude::*;
 use std::error::Error;
+use std::fs;
 #[test]
 fn test_import_cluster_definitions() -> Result<(), Box<dyn Error>> {
     let q = "queue_from_definitions";
@@ -52,3 +54,198 @@ fn test_import_vhost_definitions() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_export_split_into_dir_and_import_from_dir() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.definitions_import.split.1";
+    let q = "qq.test_export_split_into_dir.1";
+    let dir = std::env::temp_dir().join("rabbitmqadmin.definitions_import.split.1");
+    let _ = fs::remove_dir_all(&dir);
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["declare", "vhost", "--name", vh]);
+    run_succeeds([
+        "-V", vh, "declare", "queue", "--name", q, "--type", "quorum",
+    ]);
+
+    run_succeeds([
+        "definitions",
+        "export",
+        "--split-into",
+        dir.to_str().unwrap(),
+    ]);
+
+    let vhost_file = dir.join(format!("vhost.{}.json", vh));
+    assert!(dir.join("cluster.json").is_file());
+    assert!(dir.join("vhost.%2F.json").is_file());
+    assert!(fs::read_to_string(&vhost_file)?.contains(q));
+
+    run_fails([
+        "definitions",
+        "export",
+        "--split-into",
+        dir.to_str().unwrap(),
+    ])
+    .stderr(output_includes("--prune"));
+
+    let stale_file = dir.join("vhost.rabbitmqadmin.definitions_import.split.gone.json");
+    fs::write(&stale_file, "{}")?;
+    run_succeeds([
+        "definitions",
+        "export",
+        "--split-into",
+        dir.to_str().unwrap(),
+        "--prune",
+    ])
+    .stdout(output_includes(
+        "vhost.rabbitmqadmin.definitions_import.split.gone.json",
+    ));
+    assert!(!stale_file.exists());
+    assert!(vhost_file.is_file());
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    run_succeeds(["definitions", "import", "--from-dir", dir.to_str().unwrap()]);
+    run_succeeds(["-V", vh, "list", "queues"]).stdout(output_includes(q));
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    let _ = fs::remove_dir_all(&dir);
+
+    Ok(())
+}
+
+#[test]
+fn test_import_from_dir_without_cluster_file_fails() -> Result<(), Box<dyn Error>> {
+    let dir = std::env::temp_dir().join("rabbitmqadmin.definitions_import.split.2");
+    fs::create_dir_all(&dir)?;
+
+    run_fails(["definitions", "import", "--from-dir", dir.to_str().unwrap()])
+        .stderr(output_includes("cluster.json"));
+
+    let _ = fs::remove_dir_all(&dir);
+
+    Ok(())
+}
+
+#[test]
+fn test_import_definitions_with_uri_credentials_file() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.definitions_import.redacted";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    run_succeeds([
+        "definitions",
+        "import",
+        "--file",
+        "tests/fixtures/definitions/cluster.definitions.redacted.json",
+        "--uri-credentials-file",
+        "tests/fixtures/definitions/uri_credentials.1.toml",
+    ]);
+
+    run_succeeds(["--vhost", vh, "federation", "list_all_upstreams"])
+        .stdout(output_includes("upstream.1"))
+        .stdout(output_includes("$FEDERATION_UPSTREAM").not());
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    Ok(())
+}
+
+#[test]
+fn test_import_definitions_with_missing_uri_credentials_fails() -> Result<(), Box<dyn Error>> {
+    run_fails([
+        "definitions",
+        "import",
+        "--file",
+        "tests/fixtures/definitions/cluster.definitions.redacted.json",
+        "--uri-credentials-from-env",
+    ])
+    .stderr(output_includes(
+        "FEDERATION_UPSTREAM_RABBITMQADMIN_DEFINITIONS_IMPORT_REDACTED_UPSTREAM_1_URI",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_import_definitions_with_placeholders_and_no_uri_credentials_fails()
+-> Result<(), Box<dyn Error>> {
+    run_fails([
+        "definitions",
+        "import",
+        "--file",
+        "tests/fixtures/definitions/cluster.definitions.redacted.json",
+    ])
+    .stderr(
+        output_includes(
+            "FEDERATION_UPSTREAM_RABBITMQADMIN_DEFINITIONS_IMPORT_REDACTED_UPSTREAM_1_URI",
+        )
+        .and(output_includes("--uri-credentials-file")),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_import_definitions_on_conflict() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.definitions_import.on_conflict";
+    let file = "tests/fixtures/definitions/cluster.definitions.on_conflict.json";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["declare", "vhost", "--name", vh]);
+    // the definitions file declares this queue as a quorum queue
+    run_succeeds([
+        "-V",
+        vh,
+        "declare",
+        "queue",
+        "--name",
+        "on_conflict.q1",
+        "--type",
+        "classic",
+    ]);
+
+    run_succeeds([
+        "definitions",
+        "import",
+        "--file",
+        file,
+        "--on-conflict",
+        "skip",
+    ])
+    .stdout(output_includes("conflict"));
+    run_succeeds(["-V", vh, "list", "queues"])
+        .stdout(output_includes("on_conflict.q2"))
+        .stdout(output_includes("quorum").not());
+
+    run_fails([
+        "definitions",
+        "import",
+        "--file",
+        file,
+        "--on-conflict",
+        "fail",
+        "--strict",
+    ]);
+
+    run_fails([
+        "definitions",
+        "import",
+        "--file",
+        file,
+        "--on-conflict",
+        "recreate",
+    ])
+    .stderr(output_includes("--approve"));
+
+    run_succeeds([
+        "definitions",
+        "import",
+        "--file",
+        file,
+        "--on-conflict",
+        "recreate",
+        "--approve",
+    ]);
+    run_succeeds(["-V", vh, "list", "queues"]).stdout(output_includes("quorum"));
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    Ok(())
+}
@@ -0,0 +1,99 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::test_helpers::*;
+use std::error::Error;
+use std::fs;
+
+const FRAGMENT_A: &str = "tests/fixtures/definitions/cluster.definitions.fragment.a.json";
+const FRAGMENT_B: &str = "tests/fixtures/definitions/cluster.definitions.fragment.b.json";
+
+#[test]
+fn test_definitions_merge() -> Result<(), Box<dyn Error>> {
+    let output = std::env::temp_dir().join("rabbitmqadmin.definitions_merge.merged.json");
+    let _ = fs::remove_file(&output);
+
+    run_succeeds([
+        "definitions",
+        "merge",
+        "--file",
+        FRAGMENT_A,
+        "--file",
+        FRAGMENT_B,
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+
+    let merged: serde_json::Value = serde_json::from_str(&fs::read_to_string(&output)?)?;
+    assert_eq!(merged["vhosts"].as_array().unwrap().len(), 1);
+    assert_eq!(merged["queues"].as_array().unwrap().len(), 2);
+
+    let _ = fs::remove_file(&output);
+    Ok(())
+}
+
+#[test]
+fn test_definitions_merge_with_conflicting_duplicates_fails() -> Result<(), Box<dyn Error>> {
+    let conflicting = std::env::temp_dir().join("rabbitmqadmin.definitions_merge.conflicting.json");
+    let output = std::env::temp_dir().join("rabbitmqadmin.definitions_merge.conflicting.out.json");
+    let _ = fs::remove_file(&output);
+    let contents = fs::read_to_string(FRAGMENT_A)?.replace("quorum", "classic");
+    fs::write(&conflicting, contents)?;
+
+    run_fails([
+        "definitions",
+        "merge",
+        "--file",
+        FRAGMENT_A,
+        "--file",
+        conflicting.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ])
+    .stderr(output_includes("definitions_merge.orders"));
+    assert!(!output.exists());
+
+    let _ = fs::remove_file(&conflicting);
+    Ok(())
+}
+
+#[test]
+fn test_definitions_import_of_multiple_files() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.definitions_merge";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    run_succeeds([
+        "definitions",
+        "import",
+        "--file",
+        FRAGMENT_A,
+        "--file",
+        FRAGMENT_B,
+    ]);
+
+    let client = api_client();
+    assert!(
+        client
+            .get_queue_info(vh, "definitions_merge.orders")
+            .is_ok()
+    );
+    assert!(
+        client
+            .get_queue_info(vh, "definitions_merge.invoices")
+            .is_ok()
+    );
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    Ok(())
+}
@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use crate::test_helpers::output_includes;
-use crate::test_helpers::{run_fails, run_succeeds};
+use crate::test_helpers::{await_queue_metric_emission, delete_vhost, run_fails, run_succeeds};
+use predicates::prelude::*;
 use std::error::Error;
 
 #[test]
@@ -91,3 +92,266 @@ fn test_health_check_unreachable_host_produces_useful_error() -> Result<(), Box<
 
     Ok(())
 }
+
+#[test]
+fn test_health_check_all() -> Result<(), Box<dyn Error>> {
+    run_succeeds([
+        "health_check",
+        "all",
+        "--checks",
+        "local_alarms,cluster_wide_alarms,port_listener",
+        "--port",
+        "15672",
+    ])
+    .stdout(
+        output_includes("local_alarms")
+            .and(output_includes("cluster_wide_alarms"))
+            .and(output_includes("port_listener"))
+            .and(output_includes("passed")),
+    );
+
+    // port_listener requires --port
+    run_fails(["health_check", "all", "--checks", "port_listener"]);
+
+    run_succeeds([
+        "health_check",
+        "all",
+        "--checks",
+        "virtual_hosts,ready_to_serve_clients,is_in_service,certificate_expiration",
+        "--within",
+        "1",
+        "--unit",
+        "weeks",
+    ])
+    .stdout(
+        output_includes("virtual_hosts")
+            .and(output_includes("ready_to_serve_clients"))
+            .and(output_includes("is_in_service"))
+            .and(output_includes("certificate_expiration"))
+            .and(output_includes("failed").not()),
+    );
+    // certificate_expiration requires --within
+    run_fails(["health_check", "all", "--checks", "certificate_expiration"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_health_check_all_reports_failures() -> Result<(), Box<dyn Error>> {
+    run_fails([
+        "health_check",
+        "all",
+        "--checks",
+        "port_listener,local_alarms",
+        "--port",
+        "15679",
+    ])
+    .stdout(output_includes("failed").and(output_includes("not run")));
+
+    let assert = run_fails([
+        "health_check",
+        "all",
+        "--checks",
+        "port_listener,local_alarms",
+        "--port",
+        "15679",
+        "--continue-on-failure",
+        "--output",
+        "json",
+    ]);
+    let report: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout)?;
+    assert_eq!(report["healthy"], false);
+    assert_eq!(report["results"][0]["status"], "failed");
+    assert_eq!(report["results"][1]["status"], "passed");
+
+    Ok(())
+}
+
+#[test]
+fn test_health_check_certificate_expiration() -> Result<(), Box<dyn Error>> {
+    run_succeeds([
+        "health_check",
+        "certificate_expiration",
+        "--within",
+        "1",
+        "--unit",
+        "weeks",
+    ])
+    .stdout(output_includes("passed"));
+
+    run_fails([
+        "health_check",
+        "certificate_expiration",
+        "--within",
+        "1",
+        "--unit",
+        "hours",
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_health_check_virtual_hosts() -> Result<(), Box<dyn Error>> {
+    run_succeeds(["health_check", "virtual_hosts"]).stdout(output_includes("passed"));
+
+    Ok(())
+}
+
+#[test]
+fn test_health_check_ready_to_serve_clients() -> Result<(), Box<dyn Error>> {
+    run_succeeds(["health_check", "ready_to_serve_clients"]).stdout(output_includes("passed"));
+
+    Ok(())
+}
+
+#[test]
+fn test_health_check_is_in_service() -> Result<(), Box<dyn Error>> {
+    run_succeeds(["health_check", "is_in_service"]).stdout(output_includes("passed"));
+
+    Ok(())
+}
+
+#[test]
+fn test_health_check_queue_depth() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.health_check_tests.queue_depth";
+    let q = "health_check_tests.queue_depth.1";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["vhosts", "declare", "--name", vh]);
+    run_succeeds([
+        "-V", vh, "queues", "declare", "--name", q, "--type", "classic",
+    ]);
+    run_succeeds([
+        "-V",
+        vh,
+        "publish",
+        "message",
+        "--routing-key",
+        q,
+        "--payload",
+        "1",
+    ]);
+    run_succeeds([
+        "-V",
+        vh,
+        "publish",
+        "message",
+        "--routing-key",
+        q,
+        "--payload",
+        "2",
+    ]);
+    await_queue_metric_emission();
+
+    run_succeeds([
+        "health_check",
+        "queue_depth",
+        "--max",
+        "2",
+        "--name-pattern",
+        "^health_check_tests\\.queue_depth",
+    ])
+    .stdout(output_includes("passed"));
+    run_fails([
+        "health_check",
+        "queue_depth",
+        "--max",
+        "1",
+        "--name-pattern",
+        "^health_check_tests\\.queue_depth",
+    ])
+    .stdout(output_includes(q).and(output_includes("2 messages")));
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
+
+#[test]
+fn test_health_check_consumers() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.health_check_tests.consumers";
+    let q = "health_check_tests.consumers.1";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["vhosts", "declare", "--name", vh]);
+    run_succeeds([
+        "-V", vh, "queues", "declare", "--name", q, "--type", "classic",
+    ]);
+
+    run_succeeds([
+        "health_check",
+        "consumers",
+        "--min",
+        "0",
+        "--name-pattern",
+        "^health_check_tests\\.consumers",
+    ])
+    .stdout(output_includes("passed"));
+    run_fails([
+        "health_check",
+        "consumers",
+        "--name-pattern",
+        "^health_check_tests\\.consumers",
+    ])
+    .stdout(output_includes(q).and(output_includes("0 consumers")));
+
+    // health_check all runs the consumers check with the same default minimum
+    run_fails([
+        "health_check",
+        "all",
+        "--checks",
+        "consumers",
+        "--name-pattern",
+        "^health_check_tests\\.consumers",
+    ])
+    .stdout(output_includes("consumers").and(output_includes("fewer than 1 consumers")));
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
+
+#[test]
+fn test_health_check_unacked() -> Result<(), Box<dyn Error>> {
+    run_succeeds([
+        "health_check",
+        "unacked",
+        "--max",
+        "0",
+        "--name-pattern",
+        "^health_check_tests\\.unacked\\.non_existent",
+    ])
+    .stdout(output_includes("passed"));
+
+    Ok(())
+}
+
+#[test]
+fn test_health_check_connections() -> Result<(), Box<dyn Error>> {
+    run_succeeds(["health_check", "connections", "--max-per-user", "100000"])
+        .stdout(output_includes("passed"));
+
+    run_succeeds([
+        "health_check",
+        "all",
+        "--checks",
+        "queue_depth,unacked,connections",
+        "--max-queue-depth",
+        "0",
+        "--max-unacked",
+        "0",
+        "--name-pattern",
+        "^health_check_tests\\.all\\.non_existent",
+        "--max-connections-per-user",
+        "100000",
+    ])
+    .stdout(
+        output_includes("queue_depth")
+            .and(output_includes("unacked"))
+            .and(output_includes("connections"))
+            .and(output_includes("failed").not()),
+    );
+    // queue_depth requires --max-queue-depth
+    run_fails(["health_check", "all", "--checks", "queue_depth"]);
+
+    Ok(())
+}
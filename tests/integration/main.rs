@@ -14,14 +14,17 @@
 
 mod test_helpers;
 
+mod audit_tests;
 mod auth_attempts_tests;
 mod bindings_tests;
 mod channels_tests;
 mod combined_integration_tests;
 mod config_file_tests;
 mod connections_tests;
+mod definitions_copy_tests;
 mod definitions_export_tests;
 mod definitions_import_tests;
+mod definitions_merge_tests;
 mod deprecated_feature_tests;
 mod exchange_federation_tests;
 mod exchanges_tests;
@@ -35,6 +38,7 @@ mod memory_breakdown_tests;
 mod nodes_tests;
 mod operator_policies_tests;
 mod pagination_tests;
+mod passwords_tests;
 mod permissions_tests;
 mod plugins_tests;
 mod policies_conflicting_tests;
@@ -47,13 +51,17 @@ mod shell_completions_tests;
 mod shovel_destination_uri_modification_tests;
 mod shovel_source_uri_modification_tests;
 mod shovel_tests;
+mod shovels_migrate_cluster_tests;
 mod streams_tests;
+mod tenants_tests;
 mod test_commands_recommended_against_tests;
 mod timeout_tests;
 mod tls_tests;
+mod topic_permissions_tests;
 mod user_limits_tests;
 mod users_tests;
 mod verbose_tests;
 mod vhost_limits_tests;
+mod vhosts_clone_tests;
 mod vhosts_delete_multiple_tests;
 mod vhosts_tests;
@@ -0,0 +1,102 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+
+use crate::test_helpers::*;
+
+#[test]
+fn test_passwords_verify() -> Result<(), Box<dyn Error>> {
+    let assert = run_succeeds([
+        "passwords",
+        "salt_and_hash",
+        "--hashing-algorithm",
+        "SHA512",
+        "pa$$w0rd",
+    ]);
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    let hash = regex::Regex::new(r"[A-Za-z0-9+/=]{40,}")?
+        .find(&stdout)
+        .unwrap()
+        .as_str()
+        .to_string();
+
+    run_succeeds([
+        "passwords",
+        "verify",
+        "--hash",
+        &hash,
+        "--password",
+        "pa$$w0rd",
+        "--hashing-algorithm",
+        "SHA512",
+    ])
+    .stdout(output_includes("matches"));
+    run_fails([
+        "passwords",
+        "verify",
+        "--hash",
+        &hash,
+        "--password",
+        "wr0ng",
+        "--hashing-algorithm",
+        "SHA512",
+    ])
+    .stderr(output_includes("does not match"));
+    // the hash was produced with a different algorithm
+    run_fails([
+        "passwords",
+        "verify",
+        "--hash",
+        &hash,
+        "--password",
+        "pa$$w0rd",
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_passwords_generate() -> Result<(), Box<dyn Error>> {
+    let assert = run_succeeds([
+        "passwords",
+        "generate",
+        "--count",
+        "3",
+        "--length",
+        "20",
+        "--output",
+        "json",
+    ]);
+    let generated: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout)?;
+    let generated = generated.as_array().unwrap();
+    assert_eq!(generated.len(), 3);
+
+    for item in generated {
+        let password = item["password"].as_str().unwrap();
+        assert_eq!(password.len(), 20);
+        run_succeeds([
+            "passwords",
+            "verify",
+            "--hash",
+            item["password_hash"].as_str().unwrap(),
+            "--password",
+            password,
+        ]);
+    }
+
+    run_fails(["passwords", "generate", "--length", "4"]);
+
+    Ok(())
+}
@@ -369,3 +369,116 @@ fn test_permissions_with_separate_auth_and_target_usernames() -> Result<(), Box<
 
     Ok(())
 }
+
+#[test]
+fn test_permissions_declare_in_all_and_copy() -> Result<(), Box<dyn Error>> {
+    let source = "user_perms_declare_in_all";
+    let target = "user_perms_copy_target";
+    let password = "pa$$w0rd";
+    let vh1 = "rabbitmqadmin.permissions_bulk.team-a";
+    let vh2 = "rabbitmqadmin.permissions_bulk.team-b";
+    let vh3 = "rabbitmqadmin.permissions_bulk.other";
+
+    for vh in [vh1, vh2, vh3] {
+        run_succeeds(["declare", "vhost", "--name", vh]);
+    }
+    run_succeeds(["declare", "user", "--name", source, "--password", password]);
+    run_succeeds(["declare", "user", "--name", target, "--password", password]);
+
+    run_succeeds([
+        "permissions",
+        "declare_in_all",
+        "--username",
+        source,
+        "--vhost-pattern",
+        "permissions_bulk\\.team-",
+        "--configure",
+        "^team\\.",
+        "--write",
+        "^team\\.",
+        "--read",
+        ".*",
+        "--dry-run",
+    ])
+    .stdout(
+        output_includes(vh1)
+            .and(output_includes(vh2))
+            .and(output_includes(vh3).not()),
+    );
+    run_succeeds(["users", "permissions"]).stdout(output_includes(source).not());
+
+    run_succeeds([
+        "permissions",
+        "declare_in_all",
+        "--username",
+        source,
+        "--vhost-pattern",
+        "permissions_bulk\\.team-",
+        "--configure",
+        "^team\\.",
+        "--write",
+        "^team\\.",
+        "--read",
+        ".*",
+    ]);
+    // declaring the same permissions again skips every virtual host
+    run_succeeds([
+        "permissions",
+        "declare_in_all",
+        "--username",
+        source,
+        "--vhost-pattern",
+        "permissions_bulk\\.team-",
+        "--configure",
+        "^team\\.",
+        "--write",
+        "^team\\.",
+        "--read",
+        ".*",
+    ])
+    .stdout(output_includes("already present"));
+
+    run_succeeds([
+        "permissions",
+        "copy",
+        "--from-user",
+        source,
+        "--to-user",
+        target,
+        "--vhost-pattern",
+        "team-a$",
+    ]);
+    run_succeeds(["--vhost", vh1, "permissions", "list"]).stdout(output_includes(target));
+    run_succeeds([
+        "--vhost",
+        vh2,
+        "permissions",
+        "check",
+        "--username",
+        target,
+        "--resource-type",
+        "queue",
+        "--name",
+        "team.q",
+        "--operation",
+        "write",
+    ])
+    .stdout(output_includes("denied"));
+
+    run_fails([
+        "permissions",
+        "copy",
+        "--from-user",
+        source,
+        "--to-user",
+        source,
+    ]);
+
+    run_succeeds(["delete", "user", "--name", source]);
+    run_succeeds(["delete", "user", "--name", target]);
+    for vh in [vh1, vh2, vh3] {
+        crate::test_helpers::delete_vhost(vh)?;
+    }
+
+    Ok(())
+}
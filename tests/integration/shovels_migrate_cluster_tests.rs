@@ -0,0 +1,131 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::test_helpers::*;
+use predicates::prelude::*;
+use std::error::Error;
+
+const CONFIG_FILE: &str = "tests/fixtures/config_files/two_nodes.toml";
+
+#[test]
+fn test_shovels_migrate_cluster_with_unknown_node_alias_fails() -> Result<(), Box<dyn Error>> {
+    run_fails([
+        "--config",
+        CONFIG_FILE,
+        "--node",
+        "blue",
+        "shovels",
+        "migrate_cluster",
+        "--to-node",
+        "does-not-exist",
+        "--dry-run",
+    ])
+    .stderr(output_includes("does-not-exist"));
+
+    Ok(())
+}
+
+#[test]
+fn test_shovels_migrate_cluster_requires_approve() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.shovels_migrate_cluster.test1";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["declare", "vhost", "--name", vh]);
+
+    let include_vhosts = format!("^{}$", regex::escape(vh));
+    run_fails([
+        "--config",
+        CONFIG_FILE,
+        "--node",
+        "blue",
+        "shovels",
+        "migrate_cluster",
+        "--to-node",
+        "green",
+        "--include-vhosts",
+        &include_vhosts,
+    ])
+    .stderr(output_includes("--approve"));
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
+
+#[test]
+fn test_shovels_migrate_cluster_dry_run_lists_matching_queues() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.shovels_migrate_cluster.test2";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["declare", "vhost", "--name", vh]);
+    for q in ["migrate_cluster.orders", "migrate_cluster.invoices"] {
+        run_succeeds(["-V", vh, "declare", "queue", "--name", q]);
+    }
+
+    let include_vhosts = format!("^{}$", regex::escape(vh));
+    run_succeeds([
+        "--config",
+        CONFIG_FILE,
+        "--node",
+        "blue",
+        "shovels",
+        "migrate_cluster",
+        "--to-node",
+        "green",
+        "--include-vhosts",
+        &include_vhosts,
+        "--exclude-names",
+        "invoices",
+        "--dry-run",
+    ])
+    .stdout(output_includes("migrate_cluster.orders"))
+    .stdout(output_includes("migrate_cluster.invoices").not());
+
+    let shovels = api_client().list_shovels_in(vh)?;
+    assert!(shovels.is_empty());
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
+
+#[test]
+fn test_shovels_migrate_cluster_status() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.shovels_migrate_cluster.test3";
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["declare", "vhost", "--name", vh]);
+    run_succeeds([
+        "-V",
+        vh,
+        "declare",
+        "queue",
+        "--name",
+        "migrate_cluster.status.q1",
+    ]);
+
+    // an empty queue that no migration shovel was declared for has nothing to migrate
+    let include_vhosts = format!("^{}$", regex::escape(vh));
+    run_succeeds([
+        "shovels",
+        "migrate_cluster",
+        "--status",
+        "--include-vhosts",
+        &include_vhosts,
+    ])
+    .stdout(output_includes("migrate_cluster.status.q1"))
+    .stdout(output_includes("nothing to migrate"))
+    .stdout(output_includes("finished").not());
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use predicates::prelude::*;
+use std::error::Error;
+use std::fs;
+
+use crate::test_helpers::*;
+
+const SPEC_PATH: &str = "tests/fixtures/tenants/tenant.toml";
+const TENANT: &str = "rabbitmqadmin.tenant.1";
+const USER: &str = "rabbitmqadmin.tenant.1.app";
+
+#[test]
+fn test_tenants_provision_dry_run() -> Result<(), Box<dyn Error>> {
+    run_succeeds(["tenants", "provision", "--spec", SPEC_PATH, "--dry-run"]).stdout(
+        output_includes("declare virtual host 'rabbitmqadmin.tenant.1'")
+            .and(output_includes("set limit 'max-queues' to 50")),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_tenants_provision_and_deprovision() -> Result<(), Box<dyn Error>> {
+    delete_vhost(TENANT).expect("failed to delete a virtual host");
+    delete_user(USER).expect("failed to delete a user");
+
+    run_succeeds(["tenants", "provision", "--spec", SPEC_PATH]);
+    // provisioning is idempotent
+    run_succeeds(["tenants", "provision", "--spec", SPEC_PATH]);
+
+    run_succeeds(["-V", TENANT, "list", "queues"])
+        .stdout(output_includes("app.orders").and(output_includes("app.audit")));
+    run_succeeds(["users", "list"]).stdout(output_includes(USER));
+    run_succeeds(["-V", TENANT, "policies", "list"]).stdout(output_includes("app.limits"));
+
+    run_succeeds(["tenants", "deprovision", "--name", TENANT, "--dry-run"])
+        .stdout(output_includes(USER).and(output_includes("app.orders").not()));
+    run_fails(["tenants", "deprovision", "--name", TENANT]);
+    run_succeeds(["tenants", "deprovision", "--name", TENANT, "--approve"]);
+
+    run_succeeds(["vhosts", "list"]).stdout(output_includes(TENANT).not());
+    run_succeeds(["users", "list"]).stdout(output_includes(USER).not());
+    run_fails(["tenants", "deprovision", "--name", TENANT, "--approve"])
+        .stderr(output_includes("No provisioning record"));
+
+    Ok(())
+}
+
+#[test]
+fn test_tenants_provision_rolls_back_on_failure() -> Result<(), Box<dyn Error>> {
+    let tenant = "rabbitmqadmin.tenant.2";
+    let spec = std::env::temp_dir().join("rabbitmqadmin.tenant.2.toml");
+    delete_vhost(tenant).expect("failed to delete a virtual host");
+
+    // the binding refers to a queue that is not in the spec
+    fs::write(
+        &spec,
+        format!(
+            r#"name = "{tenant}"
+
+[[exchanges]]
+name = "events"
+type = "topic"
+
+[[bindings]]
+source = "events"
+destination = "missing"
+"#
+        ),
+    )?;
+
+    run_fails(["tenants", "provision", "--spec", spec.to_str().unwrap()]);
+    run_succeeds(["vhosts", "list"]).stdout(output_includes(tenant).not());
+
+    let _ = fs::remove_file(&spec);
+    Ok(())
+}
@@ -0,0 +1,155 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::test_helpers::*;
+use predicates::prelude::*;
+use std::error::Error;
+
+#[test]
+fn test_topic_permissions() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.topic_permissions.1";
+    let username = "user_with_topic_permissions";
+    let password = "pa$$w0rd";
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["vhosts", "declare", "--name", vh]);
+    run_succeeds([
+        "users",
+        "declare",
+        "--name",
+        username,
+        "--password",
+        password,
+    ]);
+
+    run_succeeds([
+        "-V",
+        vh,
+        "topic_permissions",
+        "declare",
+        "--user",
+        username,
+        "--exchange",
+        "amq.topic",
+        "--write",
+        "^orders\\.",
+        "--read",
+        "^events\\.",
+    ]);
+
+    run_succeeds(["topic_permissions", "list"]).stdout(
+        output_includes(username)
+            .and(output_includes("^orders\\."))
+            .and(output_includes("^events\\.")),
+    );
+    run_succeeds(["-V", vh, "topic_permissions", "list_in"]).stdout(output_includes(username));
+    run_succeeds(["topic_permissions", "list_in"]).stdout(output_includes(username).not());
+
+    run_succeeds([
+        "-V",
+        vh,
+        "topic_permissions",
+        "delete",
+        "--username",
+        username,
+    ]);
+    run_succeeds(["-V", vh, "topic_permissions", "list_in"])
+        .stdout(output_includes(username).not());
+    run_fails([
+        "-V",
+        vh,
+        "topic_permissions",
+        "delete",
+        "--username",
+        username,
+    ]);
+    run_succeeds([
+        "-V",
+        vh,
+        "topic_permissions",
+        "delete",
+        "--username",
+        username,
+        "--idempotently",
+    ]);
+
+    delete_user(username).expect("failed to delete a user");
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
+
+#[test]
+fn test_list_permissions_including_topic_permissions() -> Result<(), Box<dyn Error>> {
+    let vh = "rabbitmqadmin.topic_permissions.2";
+    let username = "user_with_both_permission_kinds";
+    let password = "pa$$w0rd";
+
+    delete_vhost(vh).expect("failed to delete a virtual host");
+    run_succeeds(["vhosts", "declare", "--name", vh]);
+    run_succeeds([
+        "users",
+        "declare",
+        "--name",
+        username,
+        "--password",
+        password,
+    ]);
+    run_succeeds([
+        "-V",
+        vh,
+        "permissions",
+        "declare",
+        "--username",
+        username,
+        "--configure",
+        ".*",
+        "--read",
+        ".*",
+        "--write",
+        ".*",
+    ]);
+    run_succeeds([
+        "-V",
+        vh,
+        "topic_permissions",
+        "declare",
+        "--username",
+        username,
+        "--exchange",
+        "amq.topic",
+        "--write",
+        "^audit\\.",
+        "--read",
+        ".*",
+    ]);
+
+    run_succeeds(["permissions", "list"]).stdout(output_includes("^audit\\.").not());
+    for args in [
+        ["permissions", "list", "--include-topic"],
+        ["list", "permissions", "--include-topic"],
+        ["users", "permissions", "--include-topic"],
+    ] {
+        run_succeeds(args).stdout(
+            output_includes("^audit\\.")
+                .and(output_includes("amq.topic"))
+                .and(output_includes("regular")),
+        );
+    }
+
+    delete_user(username).expect("failed to delete a user");
+    delete_vhost(vh).expect("failed to delete a virtual host");
+
+    Ok(())
+}
@@ -147,3 +147,342 @@ fn test_create_user_using_sha512_for_hashing() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_show_whoami() -> Result<(), Box<dyn Error>> {
+    run_succeeds(["show", "whoami"]).stdout(
+        output_includes("guest")
+            .and(predicate::str::is_match(r"(?m)tags\W+administrator\W*$")?)
+            .and(predicate::str::is_match(
+                r"(?m)administrator access\W+true\W*$",
+            )?),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_administrator_only_commands_fail_early_for_other_users() -> Result<(), Box<dyn Error>> {
+    let username = "monitoring_user_pre_flight_checks";
+    let password = "pa$$w0rd";
+    run_succeeds([
+        "declare",
+        "user",
+        "--name",
+        username,
+        "--password",
+        password,
+        "--tags",
+        "monitoring",
+    ]);
+
+    run_succeeds([
+        "--username",
+        username,
+        "--password",
+        password,
+        "show",
+        "whoami",
+    ])
+    .stdout(
+        output_includes(username)
+            .and(predicate::str::is_match(r"(?m)tags\W+monitoring\W*$")?)
+            .and(predicate::str::is_match(
+                r"(?m)administrator access\W+false\W*$",
+            )?),
+    );
+
+    run_fails([
+        "--username",
+        username,
+        "--password",
+        password,
+        "feature_flags",
+        "enable_all",
+    ])
+    .stderr(output_includes("requires the 'administrator' tag"));
+
+    run_fails([
+        "--username",
+        username,
+        "--password",
+        password,
+        "vhosts",
+        "delete_multiple",
+        "--name-pattern",
+        "^rabbitmqadmin.pre_flight_checks",
+        "--approve",
+    ])
+    .stderr(output_includes("cannot delete virtual hosts"));
+
+    run_succeeds(["delete", "user", "--name", username]);
+
+    Ok(())
+}
+
+#[test]
+fn test_users_rotate_password() -> Result<(), Box<dyn Error>> {
+    let username = "user_with_rotated_password";
+    let password = "pa$$w0rd";
+    run_succeeds([
+        "declare",
+        "user",
+        "--name",
+        username,
+        "--password",
+        password,
+        "--tags",
+        "monitoring",
+    ]);
+
+    let assert = run_succeeds([
+        "users",
+        "rotate_password",
+        "--name",
+        username,
+        "--length",
+        "24",
+        "--hashing-algorithm",
+        "SHA512",
+    ]);
+    let new_password = String::from_utf8_lossy(&assert.get_output().stdout)
+        .trim()
+        .to_string();
+    assert_eq!(new_password.len(), 24);
+
+    // tags are preserved, the old password no longer works
+    run_succeeds([
+        "--username",
+        username,
+        "--password",
+        &new_password,
+        "show",
+        "whoami",
+    ])
+    .stdout(predicate::str::is_match(r"(?m)tags\W+monitoring\W*$")?);
+    run_fails([
+        "--username",
+        username,
+        "--password",
+        password,
+        "show",
+        "whoami",
+    ]);
+
+    // only the CLI's own user can be rotated with --update-config-file
+    run_fails([
+        "users",
+        "rotate_password",
+        "--name",
+        username,
+        "--update-config-file",
+    ])
+    .stderr(output_includes("--update-config-file"));
+
+    run_succeeds(["delete", "user", "--name", username]);
+
+    Ok(())
+}
+
+#[test]
+fn test_users_update_tags_preserves_credentials() -> Result<(), Box<dyn Error>> {
+    let username = "user_with_updated_tags";
+    let password = "pa$$w0rd";
+    run_succeeds([
+        "users",
+        "declare",
+        "--name",
+        username,
+        "--password",
+        password,
+        "--hashing-algorithm",
+        "SHA512",
+        "--tags",
+        "monitoring",
+    ]);
+
+    run_succeeds([
+        "users",
+        "update",
+        "--name",
+        username,
+        "--add-tag",
+        "policymaker",
+        "--add-tag",
+        "management",
+        "--remove-tag",
+        "monitoring",
+    ]);
+    run_succeeds([
+        "--username",
+        username,
+        "--password",
+        password,
+        "show",
+        "whoami",
+    ])
+    .stdout(predicate::str::is_match(
+        r"(?m)tags\W+policymaker, management\W*$",
+    )?);
+
+    run_succeeds([
+        "users",
+        "update",
+        "--name",
+        username,
+        "--tags",
+        "monitoring",
+    ]);
+    run_succeeds([
+        "--username",
+        username,
+        "--password",
+        password,
+        "show",
+        "whoami",
+    ])
+    .stdout(predicate::str::is_match(r"(?m)tags\W+monitoring\W*$")?);
+
+    run_fails([
+        "users",
+        "update",
+        "--name",
+        "user_that_does_not_exist",
+        "--tags",
+        "x",
+    ]);
+    run_fails(["users", "update", "--name", username]);
+
+    run_succeeds(["delete", "user", "--name", username]);
+
+    Ok(())
+}
+
+#[test]
+fn test_users_import_and_export_csv() -> Result<(), Box<dyn Error>> {
+    let csv_path = "tests/fixtures/users/users.csv";
+    let alice = "rabbitmqadmin.csv_import.alice";
+    let bob = "rabbitmqadmin.csv_import.bob";
+    let _ = delete_user(alice);
+    let _ = delete_user(bob);
+
+    run_succeeds(["users", "import", "--csv", csv_path, "--dry-run"])
+        .stdout(output_includes(alice).and(output_includes(bob)));
+    run_succeeds(["users", "list"]).stdout(output_includes(alice).not());
+
+    run_succeeds(["users", "import", "--csv", csv_path, "--output", "json"]);
+    run_succeeds([
+        "--username",
+        alice,
+        "--password",
+        "s3cret-1",
+        "show",
+        "whoami",
+    ])
+    .stdout(predicate::str::is_match(
+        r"(?m)tags\W+monitoring, management\W*$",
+    )?);
+
+    // importing the same file again skips existing users
+    run_succeeds(["users", "import", "--csv", csv_path]).stdout(output_includes("already present"));
+
+    run_succeeds(["users", "export", "--csv"]).stdout(
+        output_includes("name,tags,vhost,configure,write,read")
+            .and(output_includes(
+                r"rabbitmqadmin.csv_import.bob,,/,,^orders\.,.*",
+            ))
+            .and(output_includes("rabbit_password_hashing").not()),
+    );
+    run_succeeds(["users", "export", "--csv", "--include-password-hashes"])
+        .stdout(output_includes("password_hash,hashing_algorithm").and(output_includes("SHA512")));
+
+    delete_user(alice)?;
+    delete_user(bob)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_users_import_csv_reports_invalid_rows_per_user() -> Result<(), Box<dyn Error>> {
+    let csv_path = "tests/fixtures/users/users.invalid_rows.csv";
+    let carol = "rabbitmqadmin.csv_import.carol";
+    let dave = "rabbitmqadmin.csv_import.dave";
+    let _ = delete_user(carol);
+
+    run_fails(["users", "import", "--csv", csv_path, "--strict"])
+        .stderr(output_includes("unsupported hashing algorithm"));
+    run_succeeds(["users", "list"]).stdout(output_includes(carol).and(output_includes(dave).not()));
+
+    delete_user(carol)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_users_delete_multiple() -> Result<(), Box<dyn Error>> {
+    let prefix = "rabbitmqadmin.bulk_delete";
+    let pattern = r"^rabbitmqadmin\.bulk_delete\..*";
+    for i in 1..=3 {
+        let tags = if i == 1 { "management" } else { "" };
+        run_succeeds([
+            "users",
+            "declare",
+            "--name",
+            &format!("{prefix}.{i}"),
+            "--password",
+            "pa$$w0rd",
+            "--tags",
+            tags,
+        ]);
+    }
+
+    let client = api_client();
+    let count = || -> Result<usize, Box<dyn Error>> {
+        let users = client.list_users()?;
+        Ok(users.iter().filter(|u| u.name.starts_with(prefix)).count())
+    };
+    assert_eq!(count()?, 3);
+
+    run_succeeds([
+        "users",
+        "delete_multiple",
+        "--name-pattern",
+        pattern,
+        "--dry-run",
+    ]);
+    assert_eq!(count()?, 3);
+
+    run_succeeds([
+        "users",
+        "delete_multiple",
+        "--name-pattern",
+        pattern,
+        "--tag",
+        "management",
+        "--approve",
+    ]);
+    assert_eq!(count()?, 2);
+
+    run_succeeds([
+        "users",
+        "delete_multiple",
+        "--name-pattern",
+        pattern,
+        "--approve",
+        "--idempotently",
+    ]);
+    assert_eq!(count()?, 0);
+
+    // the currently authenticated user is never deleted
+    run_succeeds([
+        "users",
+        "delete_multiple",
+        "--name-pattern",
+        "^guest$",
+        "--approve",
+    ])
+    .stdout(output_includes("protected: currently authenticated user"));
+    assert!(client.get_user("guest").is_ok());
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::test_helpers::*;
+use predicates::prelude::*;
+use std::error::Error;
+
+const PREFIX: &str = "rabbitmqadmin.test-vhosts-clone";
+
+#[test]
+fn test_vhosts_clone() -> Result<(), Box<dyn Error>> {
+    let src = format!("{}-basic.src", PREFIX);
+    let dst = format!("{}-basic.dst", PREFIX);
+    delete_vhosts_with_prefix(&format!("{}-basic", PREFIX)).ok();
+
+    run_succeeds([
+        "vhosts",
+        "declare",
+        "--name",
+        &src,
+        "--description",
+        "cloned in tests",
+        "--default-queue-type",
+        "quorum",
+    ]);
+    run_succeeds([
+        "-V", &src, "declare", "queue", "--name", "orders", "--type", "classic",
+    ]);
+    run_succeeds([
+        "-V",
+        &src,
+        "vhost_limits",
+        "declare",
+        "--name",
+        "max-connections",
+        "--value",
+        "100",
+    ]);
+    run_succeeds([
+        "declare",
+        "permissions",
+        "--vhost",
+        &src,
+        "--user",
+        "guest",
+        "--configure",
+        "^orders",
+        "--read",
+        ".*",
+        "--write",
+        ".*",
+    ]);
+
+    run_succeeds(["vhosts", "clone", "--source", &src, "--destination", &dst]);
+
+    let client = api_client();
+    let vhost = client.get_vhost(&dst)?;
+    assert_eq!(vhost.description.as_deref(), Some("cloned in tests"));
+    assert_eq!(vhost.default_queue_type.as_deref(), Some("quorum"));
+    assert!(client.get_queue_info(&dst, "orders").is_ok());
+    let permissions = client.list_permissions_in(&dst)?;
+    assert!(
+        permissions
+            .iter()
+            .any(|p| p.user == "guest" && p.configure == "^orders")
+    );
+    let limits = client.list_vhost_limits(&dst)?;
+    assert_eq!(limits[0].limits.get("max-connections"), Some(&100.into()));
+    // the source is left alone
+    assert!(client.get_vhost(&src).is_ok());
+
+    delete_vhosts_with_prefix(&format!("{}-basic", PREFIX)).ok();
+
+    Ok(())
+}
+
+#[test]
+fn test_vhosts_clone_fails_when_destination_exists() -> Result<(), Box<dyn Error>> {
+    let src = format!("{}-exists.src", PREFIX);
+    let dst = format!("{}-exists.dst", PREFIX);
+    delete_vhosts_with_prefix(&format!("{}-exists", PREFIX)).ok();
+    run_succeeds(["vhosts", "declare", "--name", &src]);
+    run_succeeds(["vhosts", "declare", "--name", &dst]);
+
+    run_fails(["vhosts", "clone", "--source", &src, "--destination", &dst])
+        .stderr(output_includes("already exists"));
+
+    delete_vhosts_with_prefix(&format!("{}-exists", PREFIX)).ok();
+
+    Ok(())
+}
+
+#[test]
+fn test_vhosts_rename_dry_run() -> Result<(), Box<dyn Error>> {
+    let src = format!("{}-rename-dry-run.src", PREFIX);
+    let dst = format!("{}-rename-dry-run.dst", PREFIX);
+    delete_vhosts_with_prefix(&format!("{}-rename-dry-run", PREFIX)).ok();
+    run_succeeds(["vhosts", "declare", "--name", &src]);
+
+    run_succeeds([
+        "vhosts",
+        "rename",
+        "--source",
+        &src,
+        "--destination",
+        &dst,
+        "--dry-run",
+    ])
+    .stdout(output_includes(&format!("create virtual host '{}'", dst)))
+    .stdout(output_includes(&format!("delete virtual host '{}'", src)));
+
+    let client = api_client();
+    assert!(client.get_vhost(&src).is_ok());
+    assert!(client.get_vhost(&dst).is_err());
+
+    delete_vhosts_with_prefix(&format!("{}-rename-dry-run", PREFIX)).ok();
+
+    Ok(())
+}
+
+#[test]
+fn test_vhosts_rename_requires_approve_in_interactive_mode() -> Result<(), Box<dyn Error>> {
+    let src = format!("{}-rename-approve.src", PREFIX);
+    let dst = format!("{}-rename-approve.dst", PREFIX);
+    delete_vhosts_with_prefix(&format!("{}-rename-approve", PREFIX)).ok();
+    run_succeeds(["vhosts", "declare", "--name", &src]);
+
+    run_fails(["vhosts", "rename", "--source", &src, "--destination", &dst])
+        .stderr(output_includes("--approve"));
+
+    let client = api_client();
+    assert!(client.get_vhost(&dst).is_err());
+
+    delete_vhosts_with_prefix(&format!("{}-rename-approve", PREFIX)).ok();
+
+    Ok(())
+}
+
+#[test]
+fn test_vhosts_rename() -> Result<(), Box<dyn Error>> {
+    let src = format!("{}-rename-basic.src", PREFIX);
+    let dst = format!("{}-rename-basic.dst", PREFIX);
+    delete_vhosts_with_prefix(&format!("{}-rename-basic", PREFIX)).ok();
+    run_succeeds(["vhosts", "declare", "--name", &src]);
+    run_succeeds([
+        "-V", &src, "declare", "queue", "--name", "orders", "--type", "quorum",
+    ]);
+
+    run_succeeds([
+        "vhosts",
+        "rename",
+        "--source",
+        &src,
+        "--destination",
+        &dst,
+        "--approve",
+    ]);
+
+    let client = api_client();
+    assert!(client.get_vhost(&src).is_err());
+    assert!(client.get_queue_info(&dst, "orders").is_ok());
+
+    run_succeeds(["vhosts", "list"])
+        .stdout(output_includes(&dst))
+        .stdout(output_includes(&src).not());
+
+    delete_vhosts_with_prefix(&format!("{}-rename-basic", PREFIX)).ok();
+
+    Ok(())
+}
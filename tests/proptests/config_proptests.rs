@@ -653,4 +653,56 @@ proptest! {
         prop_assert_eq!(merged.quiet, cli_quiet || config_quiet,
             "quiet should be true if either CLI or config is true");
     }
+
+    /// Property: CLI connection options never override the settings of a node alias section
+    #[test]
+    fn node_alias_section_ignores_cli_connection_options(
+        cli_hostname in "[a-z]{5,10}\\.[a-z]{3,5}",
+        config_hostname in "[a-z]{5,10}\\.[a-z]{3,5}",
+        cli_port in 1024u16..65535u16,
+        config_port in 1024u16..65535u16,
+        cli_username in "[a-z]{5,10}",
+        cli_quiet in proptest::bool::ANY,
+    ) {
+        let parser = create_test_parser();
+        let port_s = cli_port.to_string();
+        let mut args = vec![
+            "test",
+            "--host", &cli_hostname,
+            "--port", &port_s,
+            "--username", &cli_username,
+            "--tls",
+        ];
+        if cli_quiet {
+            args.push("--quiet");
+        }
+        let matches = parser.try_get_matches_from(args).unwrap();
+
+        let section = SharedSettings {
+            hostname: Some(config_hostname.clone()),
+            port: Some(config_port),
+            username: None,
+            scheme: Scheme::Http,
+            path_prefix: "/api".to_string(),
+            tls: false,
+            non_interactive: false,
+            quiet: false,
+            verbose: false,
+            base_uri: None,
+            password: None,
+            virtual_host: None,
+            table_style: None,
+            ca_certificate_bundle_path: None,
+            client_certificate_file_path: None,
+            client_private_key_file_path: None,
+        };
+
+        let settings = SharedSettings::from_node_alias_section(&matches, &section).unwrap();
+
+        prop_assert_eq!(settings.hostname, Some(config_hostname));
+        prop_assert_eq!(settings.port, Some(config_port));
+        prop_assert_eq!(settings.username, Some("guest".to_string()));
+        prop_assert_eq!(settings.scheme, Scheme::Http);
+        prop_assert_eq!(settings.quiet, cli_quiet);
+    }
 }
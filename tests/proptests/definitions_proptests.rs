@@ -0,0 +1,37 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proptest::prelude::*;
+use rabbitmqadmin::definitions::{vhost_definitions_file_name, vhost_from_definitions_file_name};
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(500))]
+
+    /// Any virtual host name survives a round trip through its definitions file name
+    #[test]
+    fn vhost_definitions_file_name_round_trips(vhost in "\\PC{0,40}") {
+        let file_name = vhost_definitions_file_name(&vhost);
+        prop_assert_eq!(vhost_from_definitions_file_name(&file_name), Some(vhost));
+    }
+
+    /// Definitions file names never contain path separators or other unsafe characters
+    #[test]
+    fn vhost_definitions_file_name_is_safe(vhost in "\\PC{0,40}") {
+        let file_name = vhost_definitions_file_name(&vhost);
+        prop_assert!(file_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.%".contains(c)),
+            "unsafe file name: {}", file_name);
+    }
+}
@@ -14,3 +14,4 @@
 
 mod bulk_proptests;
 mod config_proptests;
+mod definitions_proptests;
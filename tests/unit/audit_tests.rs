@@ -0,0 +1,267 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmqadmin::audit::{
+    AuditInput, Finding, Severity, UserAuditInput, audit_topology, audit_users,
+};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+
+const VHOST_DEFINITIONS_PATH: &str = "tests/fixtures/definitions/vhost.definitions.audit.json";
+
+fn checks_of<'a>(findings: &'a [Finding], subject: &str) -> Vec<&'a str> {
+    findings
+        .iter()
+        .filter(|f| f.subject == subject)
+        .map(|f| f.check.as_str())
+        .collect()
+}
+
+#[test]
+fn test_audit_queues_and_exchanges() {
+    let input = AuditInput::from_definitions(json!({
+        "vhosts": [{"name": "orders", "metadata": {"default_queue_type": "quorum"}}],
+        "policies": [
+            {"name": "qq", "vhost": "orders", "pattern": "^qq\\.", "apply-to": "quorum_queues", "priority": 1,
+             "definition": {"delivery-limit": 10, "dead-letter-exchange": "dlx"}}
+        ],
+        "queues": [
+            {"name": "classic", "vhost": "orders", "durable": true, "auto_delete": false, "arguments": {"x-queue-type": "classic"}},
+            {"name": "temporary", "vhost": "orders", "durable": false, "auto_delete": true, "arguments": {"x-queue-type": "classic"}},
+            {"name": "defaults.to.quorum", "vhost": "orders", "durable": true, "auto_delete": false, "arguments": {}},
+            {"name": "qq.covered", "vhost": "orders", "durable": true, "auto_delete": false, "arguments": {"x-queue-type": "quorum"}}
+        ],
+        "exchanges": [
+            {"name": "events", "vhost": "orders", "type": "topic"},
+            {"name": "unused", "vhost": "orders", "type": "fanout"}
+        ],
+        "bindings": [
+            {"source": "events", "vhost": "orders", "destination": "classic", "destination_type": "queue", "routing_key": "#"},
+            {"source": "events", "vhost": "orders", "destination": "temporary", "destination_type": "queue", "routing_key": "#"},
+            {"source": "events", "vhost": "orders", "destination": "defaults.to.quorum", "destination_type": "queue", "routing_key": "#"},
+            {"source": "events", "vhost": "orders", "destination": "qq.covered", "destination_type": "queue", "routing_key": "#"}
+        ]
+    }));
+
+    let findings = audit_topology(&input);
+    assert_eq!(
+        checks_of(&findings, "classic"),
+        vec!["quorum_queue_recommended"]
+    );
+    assert!(checks_of(&findings, "temporary").is_empty());
+    assert_eq!(
+        checks_of(&findings, "defaults.to.quorum"),
+        vec![
+            "quorum_queue_without_delivery_limit",
+            "quorum_queue_without_dead_lettering"
+        ]
+    );
+    assert!(checks_of(&findings, "qq.covered").is_empty());
+    assert!(checks_of(&findings, "events").is_empty());
+    assert_eq!(
+        checks_of(&findings, "unused"),
+        vec!["exchange_without_bindings"]
+    );
+}
+
+#[test]
+fn test_audit_reports_unbound_and_unconsumed_queues() {
+    let mut input = AuditInput::from_definitions(json!({
+        "queues": [
+            {"name": "q1", "vhost": "/", "durable": false, "arguments": {}},
+            {"name": "q2", "vhost": "/", "durable": false, "arguments": {}}
+        ]
+    }));
+    input.consumer_counts = Some(HashMap::from([
+        (("/".to_owned(), "q1".to_owned()), 0),
+        (("/".to_owned(), "q2".to_owned()), 3),
+    ]));
+
+    let findings = audit_topology(&input);
+    assert_eq!(
+        checks_of(&findings, "q1"),
+        vec!["queue_without_consumers", "queue_without_bindings"]
+    );
+    assert_eq!(checks_of(&findings, "q2"), vec!["queue_without_bindings"]);
+}
+
+#[test]
+fn test_audit_vhost_specific_definitions() {
+    let json = std::fs::read_to_string(VHOST_DEFINITIONS_PATH).unwrap();
+    let input = AuditInput::from_definitions(serde_json::from_str(&json).unwrap());
+
+    let findings = audit_topology(&input);
+    // bindings and queues have no virtual host field but still match,
+    // and the virtual host's default queue type and policies apply
+    assert!(checks_of(&findings, "orders").is_empty());
+    assert_eq!(
+        checks_of(&findings, "orders.unbound"),
+        vec!["queue_without_bindings"]
+    );
+}
+
+#[test]
+fn test_audit_policies_with_conflicting_priorities() {
+    let input = AuditInput::from_definitions(json!({
+        "policies": [
+            {"name": "a", "vhost": "/", "pattern": ".*", "apply-to": "queues", "priority": 0, "definition": {}},
+            {"name": "b", "vhost": "/", "pattern": ".*", "apply-to": "quorum_queues", "priority": 0, "definition": {}},
+            {"name": "c", "vhost": "/", "pattern": ".*", "apply-to": "exchanges", "priority": 0, "definition": {}},
+            {"name": "d", "vhost": "other", "pattern": ".*", "apply-to": "all", "priority": 0, "definition": {}}
+        ]
+    }));
+
+    let findings = audit_topology(&input);
+    assert_eq!(
+        checks_of(&findings, "a"),
+        vec!["conflicting_policy_priorities"]
+    );
+    assert_eq!(
+        checks_of(&findings, "b"),
+        vec!["conflicting_policy_priorities"]
+    );
+    assert!(checks_of(&findings, "c").is_empty());
+    assert!(checks_of(&findings, "d").is_empty());
+}
+
+#[test]
+fn test_audit_users_and_permissions() {
+    let vhosts = ["a", "b", "c"];
+    let permissions: Vec<_> = vhosts
+        .iter()
+        .flat_map(|vh| {
+            [
+                json!({"user": "ops", "vhost": vh, "configure": ".*", "write": ".*", "read": ".*"}),
+                json!({"user": "app", "vhost": vh, "configure": "^app\\.", "write": ".*", "read": ".*"}),
+            ]
+        })
+        .collect();
+    let mut input = AuditInput::from_definitions(json!({
+        "users": [{"name": "guest", "tags": ["administrator"]}, {"name": "ops", "tags": []}, {"name": "app", "tags": []}],
+        "permissions": permissions
+    }));
+    input.broad_permissions_threshold = 3;
+
+    let findings = audit_topology(&input);
+    assert_eq!(findings[0].severity, Severity::Critical);
+    assert_eq!(findings[0].check, "guest_user");
+    assert_eq!(checks_of(&findings, "ops"), vec!["broad_user_permissions"]);
+    assert!(checks_of(&findings, "app").is_empty());
+
+    input.broad_permissions_threshold = 4;
+    assert!(checks_of(&audit_topology(&input), "ops").is_empty());
+}
+
+#[test]
+fn test_audit_vhost_limits() {
+    let defs = json!({
+        "vhosts": [
+            {"name": "limited", "limits": {"max-connections": 100}},
+            {"name": "unlimited", "limits": {}},
+            {"name": "unknown"}
+        ]
+    });
+
+    let findings = audit_topology(&AuditInput::from_definitions(defs.clone()));
+    assert_eq!(
+        checks_of(&findings, "unlimited"),
+        vec!["vhost_without_limits"]
+    );
+    assert!(checks_of(&findings, "limited").is_empty());
+    assert!(checks_of(&findings, "unknown").is_empty());
+
+    let mut input = AuditInput::from_definitions(defs);
+    input.vhosts_with_limits = Some(HashSet::from(["unlimited".to_owned()]));
+    let findings = audit_topology(&input);
+    assert!(checks_of(&findings, "unlimited").is_empty());
+    assert_eq!(
+        checks_of(&findings, "limited"),
+        vec!["vhost_without_limits"]
+    );
+    assert_eq!(
+        checks_of(&findings, "unknown"),
+        vec!["vhost_without_limits"]
+    );
+}
+
+#[test]
+fn test_audit_users() {
+    let full = |user: &str, vhost: &str| json!({"user": user, "vhost": vhost, "configure": ".*", "write": ".*", "read": ".*"});
+    let mut input = UserAuditInput::from_definitions(json!({
+        "vhosts": [{"name": "/"}, {"name": "orders"}],
+        "users": [
+            {"name": "admin", "tags": ["administrator"], "password_hash": "abc", "hashing_algorithm": "rabbit_password_hashing_sha256"},
+            {"name": "app", "tags": [], "password_hash": "abc", "hashing_algorithm": "rabbit_password_hashing_sha512"},
+            {"name": "idle", "tags": [], "password_hash": "abc", "hashing_algorithm": "rabbit_password_hashing_sha256"},
+            {"name": "legacy", "tags": [], "password_hash": "abc", "hashing_algorithm": "rabbit_password_hashing_md5"},
+            {"name": "external", "tags": "", "password_hash": ""}
+        ],
+        "permissions": [
+            full("admin", "/"),
+            full("admin", "orders"),
+            full("app", "orders"),
+            {"user": "idle", "vhost": "orders", "configure": "", "write": "", "read": "^idle\\."},
+            full("legacy", "/")
+        ]
+    }));
+
+    // connections are unknown
+    let findings = audit_users(&input);
+    assert_eq!(
+        checks_of(&findings, "admin"),
+        vec!["administrator_with_broad_permissions"]
+    );
+    assert!(checks_of(&findings, "app").is_empty());
+    assert!(checks_of(&findings, "idle").is_empty());
+    assert_eq!(
+        checks_of(&findings, "legacy"),
+        vec!["weak_password_hashing"]
+    );
+    assert_eq!(
+        checks_of(&findings, "external"),
+        vec!["user_without_permissions"]
+    );
+    assert!(checks_of(&findings, "guest").is_empty());
+
+    input.connected_users = Some(HashSet::from(["app".to_owned(), "legacy".to_owned()]));
+    input.failed_auth_attempts = HashMap::from([
+        ("rabbit@node1".to_owned(), 3),
+        ("rabbit@node2".to_owned(), 0),
+    ]);
+    let findings = audit_users(&input);
+    assert!(checks_of(&findings, "app").is_empty());
+    assert_eq!(
+        checks_of(&findings, "idle"),
+        vec!["user_without_connections"]
+    );
+    assert_eq!(
+        checks_of(&findings, "rabbit@node1"),
+        vec!["failed_authentication_attempts"]
+    );
+    assert!(checks_of(&findings, "rabbit@node2").is_empty());
+    // warnings are listed before informational findings
+    assert_eq!(findings[0].severity, Severity::Warning);
+    assert_eq!(findings.last().unwrap().severity, Severity::Info);
+}
+
+#[test]
+fn test_audit_users_reports_guest() {
+    let input = UserAuditInput::from_definitions(json!({
+        "users": [{"name": "guest", "tags": ["administrator"], "password_hash": "abc", "hashing_algorithm": "rabbit_password_hashing_sha256"}]
+    }));
+
+    let findings = audit_users(&input);
+    assert_eq!(findings[0].severity, Severity::Critical);
+    assert_eq!(findings[0].check, "guest_user");
+}
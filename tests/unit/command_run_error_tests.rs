@@ -188,3 +188,14 @@ fn test_not_found_exit_code_is_data_err() {
     handler.report_pre_command_run_error(&cmd_err);
     assert_eq!(handler.exit_code, Some(ExitCode::DataErr));
 }
+
+#[test]
+fn test_insufficient_privileges_exit_code_is_no_perm() {
+    let cmd_err = CommandRunError::InsufficientPrivileges {
+        message: "User 'u' (tags: none) cannot import definitions".to_owned(),
+    };
+    let settings = SharedSettings::default();
+    let mut handler = make_handler(&settings);
+    handler.report_pre_command_run_error(&cmd_err);
+    assert_eq!(handler.exit_code, Some(ExitCode::NoPerm));
+}
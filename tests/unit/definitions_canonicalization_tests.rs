@@ -0,0 +1,151 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmqadmin::definitions::canonicalize;
+use serde_json::{Value, json};
+
+fn definitions() -> Value {
+    json!({
+        "rabbitmq_version": "4.2.0",
+        "rabbit_version": "4.2.0",
+        "users": [
+            {"name": "zed", "tags": ["monitoring", "administrator"], "password_hash": "abc"},
+            {"name": "alice", "tags": [], "password_hash": "def"}
+        ],
+        "vhosts": [{"name": "b"}, {"name": "a"}],
+        "permissions": [
+            {"user": "zed", "vhost": "b", "configure": ".*", "read": ".*", "write": ".*"},
+            {"user": "zed", "vhost": "a", "configure": ".*", "read": ".*", "write": ".*"}
+        ],
+        "policies": [
+            {"name": "p2", "vhost": "a", "pattern": ".*", "apply-to": "queues", "priority": 0, "definition": {"max-length": "100"}},
+            {"name": "p1", "vhost": "a", "pattern": ".*", "apply-to": "queues", "priority": 0, "definition": {"message-ttl": 1000.0}}
+        ],
+        "queues": [
+            {"name": "q1", "vhost": "b", "durable": true, "auto_delete": false, "arguments": {"x-max-length": "10", "x-queue-type": "quorum"}},
+            {"name": "q2", "vhost": "a", "durable": true, "auto_delete": false, "arguments": {"x-single-active-consumer": "true"}},
+            {"name": "q1", "vhost": "a", "durable": true, "auto_delete": false, "arguments": {"x-max-age": "7D"}}
+        ],
+        "bindings": [
+            {"source": "x", "vhost": "a", "destination": "q2", "destination_type": "queue", "routing_key": "b", "arguments": {}},
+            {"source": "x", "vhost": "a", "destination": "q2", "destination_type": "queue", "routing_key": "a", "arguments": {}},
+            {"source": "x", "vhost": "a", "destination": "q1", "destination_type": "queue", "routing_key": "z", "arguments": {}}
+        ]
+    })
+}
+
+fn keys_of(defs: &Value, collection: &str, fields: &[&str]) -> Vec<String> {
+    defs[collection]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|o| {
+            fields
+                .iter()
+                .map(|f| o[*f].as_str().unwrap())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .collect()
+}
+
+#[test]
+fn test_canonicalize_sorts_collections_by_natural_key() {
+    let mut defs = definitions();
+    canonicalize(&mut defs, false);
+
+    assert_eq!(keys_of(&defs, "users", &["name"]), vec!["alice", "zed"]);
+    assert_eq!(keys_of(&defs, "vhosts", &["name"]), vec!["a", "b"]);
+    assert_eq!(
+        keys_of(&defs, "permissions", &["vhost", "user"]),
+        vec!["a/zed", "b/zed"]
+    );
+    assert_eq!(
+        keys_of(&defs, "policies", &["vhost", "name"]),
+        vec!["a/p1", "a/p2"]
+    );
+    assert_eq!(
+        keys_of(&defs, "queues", &["vhost", "name"]),
+        vec!["a/q1", "a/q2", "b/q1"]
+    );
+    assert_eq!(
+        keys_of(&defs, "bindings", &["destination", "routing_key"]),
+        vec!["q1/z", "q2/a", "q2/b"]
+    );
+    assert_eq!(
+        defs["users"][1]["tags"],
+        json!(["administrator", "monitoring"])
+    );
+}
+
+#[test]
+fn test_canonicalize_normalizes_argument_value_types() {
+    let mut defs = definitions();
+    canonicalize(&mut defs, false);
+
+    assert_eq!(defs["queues"][0]["arguments"]["x-max-age"], json!("7D"));
+    assert_eq!(
+        defs["queues"][1]["arguments"]["x-single-active-consumer"],
+        json!(true)
+    );
+    assert_eq!(defs["queues"][2]["arguments"]["x-max-length"], json!(10));
+    assert_eq!(
+        defs["queues"][2]["arguments"]["x-queue-type"],
+        json!("quorum")
+    );
+    assert_eq!(
+        defs["policies"][0]["definition"]["message-ttl"],
+        json!(1000)
+    );
+    assert_eq!(defs["policies"][1]["definition"]["max-length"], json!(100));
+}
+
+#[test]
+fn test_canonicalize_optionally_drops_volatile_fields() {
+    let mut defs = definitions();
+    canonicalize(&mut defs, false);
+    assert_eq!(defs["rabbitmq_version"], json!("4.2.0"));
+
+    let mut defs = definitions();
+    canonicalize(&mut defs, true);
+    assert!(defs.get("rabbitmq_version").is_none());
+    assert!(defs.get("rabbit_version").is_none());
+}
+
+#[test]
+fn test_canonicalize_is_independent_of_input_order() {
+    let mut a = definitions();
+    let mut b = definitions();
+    for key in ["users", "vhosts", "queues", "bindings"] {
+        b[key].as_array_mut().unwrap().reverse();
+    }
+
+    canonicalize(&mut a, true);
+    canonicalize(&mut b, true);
+
+    assert_eq!(
+        serde_json::to_string_pretty(&a).unwrap(),
+        serde_json::to_string_pretty(&b).unwrap()
+    );
+}
+
+#[test]
+fn test_canonicalize_is_idempotent() {
+    let mut once = definitions();
+    canonicalize(&mut once, true);
+    let mut twice = once.clone();
+    canonicalize(&mut twice, true);
+
+    assert_eq!(once, twice);
+}
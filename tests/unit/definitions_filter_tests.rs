@@ -0,0 +1,166 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmqadmin::definitions::{DefinitionFilter, DefinitionKind};
+use regex::Regex;
+use serde_json::{Value, json};
+
+fn definitions() -> Value {
+    json!({
+        "users": [{"name": "guest", "password_hash": "abc", "tags": []}],
+        "vhosts": [{"name": "billing-eu"}, {"name": "billing-us"}, {"name": "shipping"}],
+        "permissions": [
+            {"user": "guest", "vhost": "billing-eu", "configure": ".*", "read": ".*", "write": ".*"},
+            {"user": "guest", "vhost": "shipping", "configure": ".*", "read": ".*", "write": ".*"}
+        ],
+        "parameters": [],
+        "policies": [
+            {"name": "limits", "vhost": "billing-eu", "pattern": ".*", "apply-to": "queues", "priority": 0, "definition": {}}
+        ],
+        "queues": [
+            {"name": "invoices", "vhost": "billing-eu", "durable": true, "auto_delete": false, "arguments": {}},
+            {"name": "invoices", "vhost": "billing-us", "durable": true, "auto_delete": false, "arguments": {}},
+            {"name": "parcels", "vhost": "shipping", "durable": true, "auto_delete": false, "arguments": {}}
+        ],
+        "exchanges": [
+            {"name": "billing.events", "vhost": "billing-eu", "type": "topic", "durable": true, "auto_delete": false, "arguments": {}},
+            {"name": "audit", "vhost": "billing-eu", "type": "fanout", "durable": true, "auto_delete": false, "arguments": {}}
+        ],
+        "bindings": [
+            {"source": "billing.events", "vhost": "billing-eu", "destination": "invoices", "destination_type": "queue", "routing_key": "#", "arguments": {}},
+            {"source": "audit", "vhost": "billing-eu", "destination": "audit.log", "destination_type": "queue", "routing_key": "", "arguments": {}}
+        ]
+    })
+}
+
+fn names(defs: &Value, key: &str, field: &str) -> Vec<String> {
+    defs[key]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|o| o[field].as_str().unwrap().to_owned())
+        .collect()
+}
+
+#[test]
+fn test_empty_filter_keeps_everything() {
+    let filter = DefinitionFilter::default();
+    assert!(filter.is_empty());
+
+    let mut defs = definitions();
+    filter.apply(&mut defs);
+    assert_eq!(defs, definitions());
+}
+
+#[test]
+fn test_include_and_exclude_vhosts() {
+    let filter = DefinitionFilter {
+        include_vhosts: Some(Regex::new("^billing-").unwrap()),
+        exclude_vhosts: Some(Regex::new("-us$").unwrap()),
+        ..Default::default()
+    };
+
+    let mut defs = definitions();
+    filter.apply(&mut defs);
+
+    assert_eq!(names(&defs, "vhosts", "name"), vec!["billing-eu"]);
+    assert_eq!(names(&defs, "queues", "vhost"), vec!["billing-eu"]);
+    assert_eq!(names(&defs, "permissions", "vhost"), vec!["billing-eu"]);
+    assert_eq!(names(&defs, "users", "name"), vec!["guest"]);
+}
+
+#[test]
+fn test_include_kinds() {
+    let filter = DefinitionFilter {
+        include_vhosts: Some(Regex::new("^billing-").unwrap()),
+        include_kinds: Some(vec![DefinitionKind::Exchanges, DefinitionKind::Bindings]),
+        ..Default::default()
+    };
+
+    let mut defs = definitions();
+    filter.apply(&mut defs);
+
+    assert_eq!(defs["queues"], json!([]));
+    assert_eq!(defs["policies"], json!([]));
+    assert_eq!(defs["exchanges"].as_array().unwrap().len(), 2);
+    assert_eq!(defs["bindings"].as_array().unwrap().len(), 2);
+    // cluster-level objects are not affected by kind filters
+    assert_eq!(names(&defs, "users", "name"), vec!["guest"]);
+}
+
+#[test]
+fn test_exclude_kinds() {
+    let filter = DefinitionFilter {
+        exclude_kinds: vec![DefinitionKind::Bindings, DefinitionKind::Policies],
+        ..Default::default()
+    };
+
+    let mut defs = definitions();
+    filter.apply(&mut defs);
+
+    assert_eq!(defs["bindings"], json!([]));
+    assert_eq!(defs["policies"], json!([]));
+    assert_eq!(defs["queues"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn test_name_filters() {
+    let filter = DefinitionFilter {
+        include_names: Some(Regex::new("^(billing|invoices)").unwrap()),
+        exclude_names: Some(Regex::new("^audit").unwrap()),
+        ..Default::default()
+    };
+
+    let mut defs = definitions();
+    filter.apply(&mut defs);
+
+    assert_eq!(names(&defs, "exchanges", "name"), vec!["billing.events"]);
+    assert_eq!(
+        names(&defs, "queues", "vhost"),
+        vec!["billing-eu", "billing-us"]
+    );
+    assert_eq!(names(&defs, "bindings", "source"), vec!["billing.events"]);
+    assert_eq!(defs["policies"], json!([]));
+}
+
+#[test]
+fn test_filters_apply_to_vhost_specific_definitions() {
+    let mut defs = json!({
+        "queues": [
+            {"name": "invoices", "durable": true, "auto_delete": false, "arguments": {}},
+            {"name": "parcels", "durable": true, "auto_delete": false, "arguments": {}}
+        ],
+        "exchanges": []
+    });
+    let filter = DefinitionFilter {
+        exclude_names: Some(Regex::new("^parcels$").unwrap()),
+        ..Default::default()
+    };
+    filter.apply(&mut defs);
+
+    assert_eq!(names(&defs, "queues", "name"), vec!["invoices"]);
+}
+
+#[test]
+fn test_definition_kind_parsing() {
+    assert_eq!(
+        "queues".parse::<DefinitionKind>(),
+        Ok(DefinitionKind::Queues)
+    );
+    assert_eq!(
+        "virtual_hosts".parse::<DefinitionKind>(),
+        Ok(DefinitionKind::Vhosts)
+    );
+    assert!("widgets".parse::<DefinitionKind>().is_err());
+}
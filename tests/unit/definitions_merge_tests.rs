@@ -0,0 +1,130 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmqadmin::definitions::{
+    DefinitionKind, DefinitionsFormat, DefinitionsFragment, MergeError, detect_format, merge,
+};
+use serde_json::{Value, json};
+
+fn fragment(source: &str, definitions: Value) -> DefinitionsFragment {
+    DefinitionsFragment {
+        source: source.to_owned(),
+        definitions,
+    }
+}
+
+#[test]
+fn test_merge_unions_collections() {
+    let a = fragment(
+        "a.json",
+        json!({
+            "rabbitmq_version": "4.2.0",
+            "vhosts": [{"name": "orders"}],
+            "queues": [{"name": "q1", "vhost": "orders", "durable": true, "arguments": {"x-max-length": 10}}]
+        }),
+    );
+    let b = fragment(
+        "b.json",
+        json!({
+            "rabbitmq_version": "4.1.0",
+            "virtual_hosts": [{"name": "orders"}, {"name": "billing"}],
+            "queues": [
+                {"name": "q1", "vhost": "orders", "durable": true, "arguments": {"x-max-length": "10"}},
+                {"name": "q1", "vhost": "billing", "durable": true, "arguments": {}}
+            ],
+            "exchanges": [{"name": "x1", "vhost": "billing", "type": "topic"}]
+        }),
+    );
+
+    let merged = merge(&[a, b]).unwrap();
+    assert_eq!(merged["rabbitmq_version"], json!("4.2.0"));
+    assert_eq!(
+        merged["vhosts"],
+        json!([{"name": "orders"}, {"name": "billing"}])
+    );
+    assert!(merged.get("virtual_hosts").is_none());
+    // the equivalent duplicate of orders/q1 is only included once
+    assert_eq!(merged["queues"].as_array().unwrap().len(), 2);
+    assert_eq!(merged["exchanges"].as_array().unwrap().len(), 1);
+    assert!(merged.get("bindings").is_none());
+}
+
+#[test]
+fn test_merge_reports_conflicting_duplicates() {
+    let a = fragment(
+        "a.json",
+        json!({"queues": [{"name": "q1", "vhost": "orders", "arguments": {"x-queue-type": "quorum"}}]}),
+    );
+    let b = fragment(
+        "b.json",
+        json!({"queues": [{"name": "q1", "vhost": "orders", "arguments": {"x-queue-type": "classic"}}]}),
+    );
+
+    let Err(MergeError::Conflicts(conflicts)) = merge(&[a, b]) else {
+        panic!("expected a conflict");
+    };
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].object.kind, DefinitionKind::Queues);
+    assert_eq!(
+        conflicts[0].to_string(),
+        "queues 'q1' in virtual host 'orders' is defined differently in a.json and b.json"
+    );
+}
+
+#[test]
+fn test_merge_virtual_host_specific_fragments() {
+    let a = fragment(
+        "a.json",
+        json!({"queues": [{"name": "q1", "durable": true}], "policies": []}),
+    );
+    let b = fragment(
+        "b.json",
+        json!({"queues": [{"name": "q2", "durable": true}], "bindings": [{"source": "x", "destination": "q2", "destination_type": "queue", "routing_key": ""}]}),
+    );
+
+    let merged = merge(&[a, b]).unwrap();
+    assert_eq!(detect_format(&merged), Some(DefinitionsFormat::VirtualHost));
+    assert_eq!(merged["queues"].as_array().unwrap().len(), 2);
+    assert_eq!(merged["policies"], json!([]));
+}
+
+#[test]
+fn test_merge_rejects_mixed_formats() {
+    let a = fragment("a.json", json!({"queues": [{"name": "q1", "vhost": "/"}]}));
+    let empty = fragment("empty.json", json!({"queues": []}));
+    let b = fragment("b.json", json!({"queues": [{"name": "q2"}]}));
+
+    assert!(matches!(
+        merge(&[a, empty, b]),
+        Err(MergeError::MixedFormats { ref first_source, ref second_source, .. })
+            if first_source == "a.json" && second_source == "b.json"
+    ));
+}
+
+#[test]
+fn test_detect_format() {
+    assert_eq!(
+        detect_format(&json!({"users": [{"name": "app"}]})),
+        Some(DefinitionsFormat::ClusterWide)
+    );
+    assert_eq!(
+        detect_format(&json!({"exchanges": [{"name": "x", "vhost": "/"}]})),
+        Some(DefinitionsFormat::ClusterWide)
+    );
+    assert_eq!(
+        detect_format(&json!({"exchanges": [{"name": "x"}]})),
+        Some(DefinitionsFormat::VirtualHost)
+    );
+    assert_eq!(detect_format(&json!({"queues": [], "users": []})), None);
+}
@@ -0,0 +1,198 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmqadmin::definitions::{
+    ChangeAction, ConflictStrategy, DefinitionChange, DefinitionKind, find_conflicts, plan_import,
+    split_into_objects,
+};
+use serde_json::json;
+
+fn change(action: ChangeAction, kind: &str, vhost: &str, name: &str) -> DefinitionChange {
+    DefinitionChange {
+        action,
+        kind: kind.to_string(),
+        vhost: vhost.to_string(),
+        name: name.to_string(),
+    }
+}
+
+#[test]
+fn test_plan_import_into_an_empty_cluster() {
+    let incoming = json!({
+        "vhosts": [{"name": "orders"}],
+        "queues": [{"name": "q1", "vhost": "orders", "durable": true, "auto_delete": false, "arguments": {}}],
+        "bindings": [
+            {"source": "amq.direct", "vhost": "orders", "destination": "q1", "destination_type": "queue", "routing_key": "q1", "arguments": {}}
+        ]
+    });
+
+    assert_eq!(
+        plan_import(&incoming, &json!({})),
+        vec![
+            change(ChangeAction::Create, "vhosts", "", "orders"),
+            change(ChangeAction::Create, "queues", "orders", "q1"),
+            change(
+                ChangeAction::Create,
+                "bindings",
+                "orders",
+                "amq.direct -> queue q1 (routing key: 'q1')"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_plan_import_skips_identical_objects() {
+    let incoming = json!({
+        "rabbitmq_version": "4.2.0",
+        "users": [{"name": "app", "password_hash": "abc", "tags": ["monitoring", "management"]}],
+        "policies": [{"name": "limits", "vhost": "/", "pattern": ".*", "apply-to": "queues", "priority": 0, "definition": {"max-length": "100"}}]
+    });
+    // same objects in a different (but equivalent) form
+    let existing = json!({
+        "rabbitmq_version": "4.1.0",
+        "users": [{"name": "app", "password_hash": "abc", "tags": ["management", "monitoring"]}],
+        "policies": [{"name": "limits", "vhost": "/", "pattern": ".*", "apply-to": "queues", "priority": 0, "definition": {"max-length": 100}}]
+    });
+
+    assert!(plan_import(&incoming, &existing).is_empty());
+}
+
+#[test]
+fn test_plan_import_detects_updates() {
+    let incoming = json!({
+        "virtual_hosts": [{"name": "orders", "description": "new"}],
+        "parameters": [{"name": "upstream", "vhost": "orders", "component": "federation-upstream", "value": {"uri": "amqp://b"}}],
+        "permissions": [{"user": "app", "vhost": "orders", "configure": ".*", "read": ".*", "write": ".*"}]
+    });
+    let existing = json!({
+        "vhosts": [{"name": "orders", "description": "old"}, {"name": "other"}],
+        "parameters": [{"name": "upstream", "vhost": "orders", "component": "federation-upstream", "value": {"uri": "amqp://a"}}],
+        "permissions": [{"user": "app", "vhost": "orders", "configure": ".*", "read": ".*", "write": ".*"}]
+    });
+
+    assert_eq!(
+        plan_import(&incoming, &existing),
+        vec![
+            change(ChangeAction::Update, "vhosts", "", "orders"),
+            change(
+                ChangeAction::Update,
+                "parameters",
+                "orders",
+                "federation-upstream/upstream"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_find_conflicts_only_reports_queues_and_exchanges() {
+    let incoming = json!({
+        "vhosts": [{"name": "orders", "description": "new"}],
+        "queues": [
+            {"name": "q1", "vhost": "orders", "durable": true, "auto_delete": false, "arguments": {"x-queue-type": "quorum"}},
+            {"name": "q2", "vhost": "orders", "durable": true, "auto_delete": false, "arguments": {}}
+        ],
+        "exchanges": [{"name": "x1", "vhost": "orders", "type": "topic", "durable": true, "auto_delete": false, "internal": false, "arguments": {}}]
+    });
+    let existing = json!({
+        "vhosts": [{"name": "orders", "description": "old"}],
+        "queues": [{"name": "q1", "vhost": "orders", "durable": true, "auto_delete": false, "arguments": {"x-queue-type": "classic"}}],
+        "exchanges": [{"name": "x1", "vhost": "orders", "type": "topic", "durable": true, "auto_delete": false, "internal": false, "arguments": {}}]
+    });
+
+    assert_eq!(
+        find_conflicts(&incoming, &existing),
+        vec![change(ChangeAction::Update, "queues", "orders", "q1")]
+    );
+}
+
+#[test]
+fn test_find_conflicts_ignores_cosmetic_and_server_populated_fields() {
+    let incoming = json!({
+        "queues": [
+            {"name": "q1", "vhost": "orders", "durable": true, "auto_delete": false, "arguments": {"x-queue-type": "quorum"}},
+            {"name": "q2", "vhost": "orders", "durable": true, "auto_delete": false, "arguments": {}}
+        ],
+        "exchanges": [{"name": "x1", "vhost": "orders", "type": "topic", "durable": true, "auto_delete": false, "internal": false, "arguments": {}}],
+        "policies": [{"name": "p1", "vhost": "orders", "pattern": "^q", "apply-to": "queues", "priority": 1, "definition": {"max-length": 10}}]
+    });
+    // the same objects as a newer version exports them, with extra fields
+    let existing = json!({
+        "queues": [
+            {"name": "q1", "vhost": "orders", "type": "quorum", "durable": true, "auto_delete": false, "arguments": {"x-queue-type": "quorum"}, "node": "rabbit@green-1"},
+            {"name": "q2", "vhost": "orders", "type": "classic", "durable": true, "auto_delete": false, "arguments": {}, "owner_pid": "none"}
+        ],
+        "exchanges": [{"name": "x1", "vhost": "orders", "type": "topic", "durable": true, "auto_delete": false, "internal": false, "arguments": {}, "user_who_performed_action": "admin"}],
+        "policies": [{"name": "p1", "vhost": "orders", "pattern": "^q", "apply-to": "queues", "priority": 1, "definition": {"max-length": 10}, "description": "added later"}]
+    });
+
+    assert!(find_conflicts(&incoming, &existing).is_empty());
+    assert!(plan_import(&incoming, &existing).is_empty());
+
+    // a defining field still makes a difference
+    let existing = json!({
+        "queues": [{"name": "q2", "vhost": "orders", "durable": false, "auto_delete": false, "arguments": {}, "owner_pid": "none"}]
+    });
+    assert_eq!(
+        find_conflicts(&incoming, &existing),
+        vec![change(ChangeAction::Update, "queues", "orders", "q2")]
+    );
+}
+
+#[test]
+fn test_split_into_objects() {
+    let defs = json!({
+        "bindings": [{"source": "x1", "vhost": "orders", "destination": "q1", "destination_type": "queue", "routing_key": "", "arguments": {}}],
+        "queues": [{"name": "q1", "vhost": "orders"}],
+        "exchanges": [{"name": "x1", "vhost": "orders"}],
+        "virtual_hosts": [{"name": "orders"}],
+        "users": [{"name": "app"}]
+    });
+
+    let objects = split_into_objects(&defs);
+    let kinds: Vec<DefinitionKind> = objects.iter().map(|o| o.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            DefinitionKind::Users,
+            DefinitionKind::Vhosts,
+            DefinitionKind::Queues,
+            DefinitionKind::Exchanges,
+            DefinitionKind::Bindings,
+        ]
+    );
+
+    assert_eq!(objects[0].to_string(), "users 'app'");
+    assert_eq!(
+        objects[2].to_string(),
+        "queues 'q1' in virtual host 'orders'"
+    );
+    assert_eq!(
+        objects[2].to_document(),
+        json!({"queues": [{"name": "q1", "vhost": "orders"}]})
+    );
+    assert!(objects[2].conflicts_with(&change(ChangeAction::Update, "queues", "orders", "q1")));
+    assert!(!objects[3].conflicts_with(&change(ChangeAction::Update, "queues", "orders", "x1")));
+}
+
+#[test]
+fn test_conflict_strategy_from_str() {
+    assert_eq!(ConflictStrategy::from("fail"), ConflictStrategy::Fail);
+    assert_eq!(ConflictStrategy::from("skip"), ConflictStrategy::Skip);
+    assert_eq!(
+        ConflictStrategy::from("recreate"),
+        ConflictStrategy::Recreate
+    );
+}
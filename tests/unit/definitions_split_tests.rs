@@ -0,0 +1,123 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmqadmin::definitions::{
+    into_cluster_format, split_by_vhost, vhost_definitions_file_name,
+    vhost_from_definitions_file_name,
+};
+use serde_json::{Value, json};
+
+fn definitions() -> Value {
+    json!({
+        "rabbitmq_version": "4.2.0",
+        "users": [{"name": "guest", "password_hash": "abc", "tags": ["administrator"]}],
+        "vhosts": [{"name": "/"}, {"name": "billing"}],
+        "permissions": [
+            {"user": "guest", "vhost": "/", "configure": ".*", "read": ".*", "write": ".*"}
+        ],
+        "topic_permissions": [
+            {"user": "guest", "vhost": "billing", "exchange": "amq.topic", "read": ".*", "write": ".*"}
+        ],
+        "global_parameters": [{"name": "cluster_name", "value": "rabbit@localhost"}],
+        "parameters": [],
+        "policies": [
+            {"name": "limits", "vhost": "billing", "pattern": ".*", "apply-to": "queues", "priority": 0, "definition": {"max-length": 10}}
+        ],
+        "queues": [
+            {"name": "invoices", "vhost": "billing", "durable": true, "auto_delete": false, "arguments": {}},
+            {"name": "q1", "vhost": "/", "durable": true, "auto_delete": false, "arguments": {}}
+        ],
+        "exchanges": [],
+        "bindings": [
+            {"source": "amq.topic", "vhost": "billing", "destination": "invoices", "destination_type": "queue", "routing_key": "#", "arguments": {}}
+        ]
+    })
+}
+
+#[test]
+fn test_split_by_vhost_keeps_cluster_level_objects_in_cluster_part() {
+    let split = split_by_vhost(&definitions());
+
+    let cluster = split.cluster.as_object().unwrap();
+    for key in [
+        "users",
+        "vhosts",
+        "permissions",
+        "topic_permissions",
+        "global_parameters",
+        "rabbitmq_version",
+    ] {
+        assert!(cluster.contains_key(key), "missing {}", key);
+    }
+    for key in ["parameters", "policies", "queues", "exchanges", "bindings"] {
+        assert!(!cluster.contains_key(key), "unexpected {}", key);
+    }
+}
+
+#[test]
+fn test_split_by_vhost_produces_vhost_scoped_parts() {
+    let split = split_by_vhost(&definitions());
+
+    let names: Vec<_> = split.vhosts.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(names, vec!["/", "billing"]);
+
+    let billing = &split.vhosts[1].1;
+    assert_eq!(
+        billing["queues"],
+        json!([{"name": "invoices", "durable": true, "auto_delete": false, "arguments": {}}])
+    );
+    assert_eq!(billing["policies"][0]["name"], "limits");
+    assert!(billing["policies"][0].get("vhost").is_none());
+    assert_eq!(billing["bindings"].as_array().unwrap().len(), 1);
+    assert_eq!(billing["exchanges"], json!([]));
+    assert_eq!(billing["rabbitmq_version"], "4.2.0");
+
+    let default_vhost = &split.vhosts[0].1;
+    assert_eq!(default_vhost["queues"][0]["name"], "q1");
+    assert_eq!(default_vhost["bindings"], json!([]));
+}
+
+#[test]
+fn test_into_cluster_format_restores_split_objects() {
+    let original = definitions();
+    let split = split_by_vhost(&original);
+
+    let (vhost, scoped) = &split.vhosts[1];
+    let restored = into_cluster_format(vhost, scoped);
+
+    assert_eq!(restored["queues"], json!([original["queues"][0]]));
+    assert_eq!(restored["policies"], original["policies"]);
+    assert_eq!(restored["bindings"], original["bindings"]);
+}
+
+#[test]
+fn test_vhost_definitions_file_names() {
+    assert_eq!(vhost_definitions_file_name("/"), "vhost.%2F.json");
+    assert_eq!(
+        vhost_definitions_file_name("billing-1"),
+        "vhost.billing-1.json"
+    );
+    assert_eq!(
+        vhost_definitions_file_name("tenant a/b"),
+        "vhost.tenant%20a%2Fb.json"
+    );
+
+    assert_eq!(
+        vhost_from_definitions_file_name("vhost.%2F.json"),
+        Some("/".to_string())
+    );
+    assert_eq!(vhost_from_definitions_file_name("cluster.json"), None);
+    assert_eq!(vhost_from_definitions_file_name("vhost.a.toml"), None);
+    assert_eq!(vhost_from_definitions_file_name("vhost.%2.json"), None);
+}
@@ -0,0 +1,288 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmq_http_client::responses::{Connection, QueueInfo};
+use rabbitmqadmin::errors::CommandRunError;
+use rabbitmqadmin::health_checks::{
+    self, CheckStatus, EXTENDED_HEALTH_CHECKS, HealthCheck, HealthCheckFailure,
+};
+use regex::Regex;
+use serde_json::json;
+
+fn queue(vhost: &str, name: &str, messages: u64, unacked: u64, consumers: u16) -> QueueInfo {
+    serde_json::from_value(json!({
+        "name": name,
+        "vhost": vhost,
+        "type": "quorum",
+        "durable": true,
+        "auto_delete": false,
+        "exclusive": false,
+        "arguments": {},
+        "messages": messages,
+        "messages_unacknowledged": unacked,
+        "consumers": consumers
+    }))
+    .unwrap()
+}
+
+fn connection(name: &str, user: &str) -> Connection {
+    serde_json::from_value(json!({
+        "name": name,
+        "node": "rabbit@localhost",
+        "protocol": "AMQP 0-9-1",
+        "user": user,
+        "connected_at": 0,
+        "client_properties": {}
+    }))
+    .unwrap()
+}
+
+fn statuses(report: &health_checks::HealthCheckReport) -> Vec<CheckStatus> {
+    report.results.iter().map(|r| r.status).collect()
+}
+
+#[test]
+fn test_health_check_names_round_trip() {
+    for check in HealthCheck::ALL {
+        assert_eq!(check.name().parse::<HealthCheck>(), Ok(check));
+    }
+    assert!("local_alarm".parse::<HealthCheck>().is_err());
+}
+
+#[test]
+fn test_run_checks_all_pass() {
+    let checks = [HealthCheck::LocalAlarms, HealthCheck::ClusterWideAlarms];
+    let report = health_checks::run_checks(&checks, false, |_| Ok(()));
+    assert!(report.healthy);
+    assert_eq!(
+        statuses(&report),
+        vec![CheckStatus::Passed, CheckStatus::Passed]
+    );
+    assert_eq!(report.results[1].check, "cluster_wide_alarms");
+}
+
+#[test]
+fn test_run_checks_stops_at_first_failure() {
+    let checks = [
+        HealthCheck::LocalAlarms,
+        HealthCheck::NodeIsQuorumCritical,
+        HealthCheck::DeprecatedFeaturesInUse,
+    ];
+    let mut executed = Vec::new();
+    let report = health_checks::run_checks(&checks, false, |check| {
+        executed.push(check);
+        match check {
+            HealthCheck::NodeIsQuorumCritical => Err("quorum critical".to_string()),
+            _ => Ok(()),
+        }
+    });
+
+    assert!(!report.healthy);
+    assert_eq!(
+        executed,
+        vec![HealthCheck::LocalAlarms, HealthCheck::NodeIsQuorumCritical]
+    );
+    assert_eq!(
+        statuses(&report),
+        vec![
+            CheckStatus::Passed,
+            CheckStatus::Failed,
+            CheckStatus::NotRun
+        ]
+    );
+    assert_eq!(report.results[1].details, "quorum critical");
+}
+
+#[test]
+fn test_run_checks_continue_on_failure() {
+    let checks = [
+        HealthCheck::LocalAlarms,
+        HealthCheck::ClusterWideAlarms,
+        HealthCheck::DeprecatedFeaturesInUse,
+    ];
+    let report = health_checks::run_checks(&checks, true, |check| match check {
+        HealthCheck::LocalAlarms => Err("alarm in effect".to_string()),
+        _ => Ok(()),
+    });
+
+    assert!(!report.healthy);
+    assert_eq!(
+        statuses(&report),
+        vec![
+            CheckStatus::Failed,
+            CheckStatus::Passed,
+            CheckStatus::Passed
+        ]
+    );
+}
+
+#[test]
+fn test_extended_health_checks_can_be_run_by_health_check_all() {
+    for name in EXTENDED_HEALTH_CHECKS {
+        assert_eq!(name.parse::<HealthCheck>().map(|c| c.name()), Ok(name));
+    }
+}
+
+#[test]
+fn test_health_check_failure_with_virtual_hosts_that_are_down() {
+    let json = r#"{"status":"failed","reason":"Some virtual hosts are down","virtual-hosts":["vh1","vh2"]}"#;
+    let failure: HealthCheckFailure = serde_json::from_str(json).unwrap();
+
+    assert_eq!(failure.reason, "Some virtual hosts are down");
+    assert_eq!(failure.virtual_hosts, vec!["vh1", "vh2"]);
+    assert!(failure.expired.is_empty());
+}
+
+#[test]
+fn test_health_check_failure_with_expiring_certificates() {
+    let json = r#"{
+        "status": "failed",
+        "reason": "Certificates expiring",
+        "expired": [{
+            "node": "rabbit@hostname",
+            "protocol": "amqp/ssl",
+            "interface": "[::]",
+            "port": 5671,
+            "certfile": "/path/to/server_certificate.pem",
+            "expiry_date": "2026-10-20T10:00:00Z"
+        }]
+    }"#;
+    let failure: HealthCheckFailure = serde_json::from_str(json).unwrap();
+
+    assert_eq!(failure.expired.len(), 1);
+    let listener = &failure.expired[0];
+    assert_eq!(listener.node, "rabbit@hostname");
+    assert_eq!(listener.port, 5671);
+    assert_eq!(listener.expiry_date, "2026-10-20T10:00:00Z");
+    assert!(failure.virtual_hosts.is_empty());
+}
+
+#[test]
+fn test_check_queue_depth() {
+    let queues = [
+        queue("/", "orders", 12_000, 0, 1),
+        queue("/", "events", 10_000, 0, 1),
+        queue("/", "audit", 50_000, 0, 1),
+        queue("staging", "orders", 20_000, 0, 1),
+    ];
+
+    assert!(health_checks::check_queue_depth(&queues, 50_000, None).is_ok());
+
+    let failure = health_checks::check_queue_depth(&queues, 10_000, None).unwrap_err();
+    assert_eq!(failure.reason, "3 queue(s) have more than 10000 messages");
+    assert_eq!(failure.metric, "messages");
+    let objects: Vec<&str> = failure
+        .violations
+        .iter()
+        .map(|v| v.object.as_str())
+        .collect();
+    assert_eq!(
+        objects,
+        vec![
+            "queue 'audit' in virtual host '/'",
+            "queue 'orders' in virtual host '/'",
+            "queue 'orders' in virtual host 'staging'",
+        ]
+    );
+    assert_eq!(failure.violations[0].value, 50_000);
+}
+
+#[test]
+fn test_check_queue_depth_with_name_pattern() {
+    let queues = [
+        queue("/", "orders", 12_000, 0, 1),
+        queue("/", "audit", 50_000, 0, 1),
+    ];
+    let pattern = Regex::new("^ord").unwrap();
+
+    let failure = health_checks::check_queue_depth(&queues, 10_000, Some(&pattern)).unwrap_err();
+    assert_eq!(failure.violations.len(), 1);
+    assert_eq!(
+        failure.violations[0].object,
+        "queue 'orders' in virtual host '/'"
+    );
+
+    let pattern = Regex::new("^events$").unwrap();
+    assert!(health_checks::check_queue_depth(&queues, 10_000, Some(&pattern)).is_ok());
+}
+
+#[test]
+fn test_check_consumers() {
+    let queues = [queue("/", "orders", 0, 0, 2), queue("/", "events", 0, 0, 0)];
+
+    assert!(health_checks::check_consumers(&queues, 0, None).is_ok());
+
+    let failure = health_checks::check_consumers(&queues, 1, None).unwrap_err();
+    assert_eq!(failure.reason, "1 queue(s) have fewer than 1 consumers");
+    assert_eq!(
+        failure.violations[0].object,
+        "queue 'events' in virtual host '/'"
+    );
+    assert_eq!(failure.violations[0].value, 0);
+
+    let failure = health_checks::check_consumers(&queues, 3, None).unwrap_err();
+    assert_eq!(failure.violations.len(), 2);
+}
+
+#[test]
+fn test_check_unacknowledged_messages() {
+    let queues = [
+        queue("/", "orders", 500, 500, 1),
+        queue("/", "events", 500, 10, 1),
+    ];
+
+    assert!(health_checks::check_unacknowledged_messages(&queues, 500, None).is_ok());
+
+    let failure = health_checks::check_unacknowledged_messages(&queues, 100, None).unwrap_err();
+    assert_eq!(failure.metric, "unacknowledged messages");
+    assert_eq!(failure.violations.len(), 1);
+    assert_eq!(failure.violations[0].value, 500);
+}
+
+#[test]
+fn test_check_connections_per_user() {
+    let connections = [
+        connection("c1", "app"),
+        connection("c2", "app"),
+        connection("c3", "app"),
+        connection("c4", "monitoring"),
+    ];
+
+    assert!(health_checks::check_connections_per_user(&connections, 3).is_ok());
+
+    let failure = health_checks::check_connections_per_user(&connections, 2).unwrap_err();
+    assert_eq!(failure.reason, "1 user(s) have more than 2 connections");
+    assert_eq!(failure.metric, "connections");
+    assert_eq!(failure.violations.len(), 1);
+    assert_eq!(failure.violations[0].object, "user 'app'");
+    assert_eq!(failure.violations[0].value, 3);
+}
+
+#[test]
+fn test_threshold_check_failure_into_error() {
+    let queues = [queue("/", "orders", 12_000, 0, 1)];
+    let failure = health_checks::check_queue_depth(&queues, 10_000, None).unwrap_err();
+
+    match failure.into_error(HealthCheck::QueueDepth) {
+        CommandRunError::HealthCheckFailed(info) => {
+            assert_eq!(info.health_check_path, "queue_depth");
+            assert_eq!(info.status_code.as_u16(), 503);
+            assert_eq!(
+                info.details.reason(),
+                "1 queue(s) have more than 10000 messages"
+            );
+        }
+        other => panic!("expected a health check failure, got {:?}", other),
+    }
+}
@@ -13,11 +13,27 @@
 // limitations under the License.
 
 mod arg_helpers_tests;
+mod audit_tests;
 mod bulk_classification_tests;
 mod bulk_tests;
 mod columns_tests;
 mod command_run_error_tests;
+mod definitions_canonicalization_tests;
+mod definitions_filter_tests;
+mod definitions_merge_tests;
+mod definitions_plan_tests;
+mod definitions_split_tests;
 mod error_helper_tests;
 mod exit_code_tests;
+mod health_checks_tests;
 mod interactivity_mode_tests;
+mod passwords_tests;
+mod permission_check_tests;
+mod privilege_check_tests;
 mod scheme_tests;
+mod shovel_migration_tests;
+mod tenants_tests;
+mod transformation_rules_tests;
+mod uri_credentials_redaction_tests;
+mod users_tests;
+mod vhost_cloning_tests;
@@ -0,0 +1,103 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmq_http_client::password_hashing::{self, HashingAlgorithm};
+use rabbitmqadmin::passwords::{self, PasswordCharset};
+
+#[test]
+fn test_generated_password_has_requested_length() {
+    for length in [12, 32, 100] {
+        assert_eq!(
+            passwords::generate(length, PasswordCharset::Alphanumeric).len(),
+            length
+        );
+    }
+}
+
+#[test]
+fn test_generated_password_uses_only_charset_characters() {
+    let alphanumeric = passwords::generate(256, PasswordCharset::Alphanumeric);
+    assert!(alphanumeric.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    let hex = passwords::generate(256, PasswordCharset::Hex);
+    assert!(
+        hex.chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase())
+    );
+
+    let with_symbols = passwords::generate(256, PasswordCharset::AlphanumericWithSymbols);
+    assert!(
+        with_symbols
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.~".contains(c))
+    );
+}
+
+#[test]
+fn test_generated_passwords_differ() {
+    let a = passwords::generate(32, PasswordCharset::Alphanumeric);
+    let b = passwords::generate(32, PasswordCharset::Alphanumeric);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_password_charset_from_str() {
+    assert_eq!(PasswordCharset::from("hex"), PasswordCharset::Hex);
+    assert_eq!(
+        PasswordCharset::from("alphanumeric_with_symbols"),
+        PasswordCharset::AlphanumericWithSymbols
+    );
+    assert_eq!(
+        PasswordCharset::from("alphanumeric"),
+        PasswordCharset::Alphanumeric
+    );
+    assert_eq!(PasswordCharset::Hex.to_string(), "hex");
+}
+
+#[test]
+fn test_verify_password_hash() {
+    for algorithm in [HashingAlgorithm::SHA256, HashingAlgorithm::SHA512] {
+        let hash = algorithm
+            .salt_and_hash(&password_hashing::salt(), "s3cRe7")
+            .unwrap();
+        assert_eq!(passwords::verify(&hash, "s3cRe7", &algorithm), Ok(true));
+        assert_eq!(passwords::verify(&hash, "s3cRe8", &algorithm), Ok(false));
+    }
+}
+
+#[test]
+fn test_verify_rejects_invalid_hashes() {
+    let sha512_hash = HashingAlgorithm::SHA512
+        .salt_and_hash(&password_hashing::salt(), "s3cRe7")
+        .unwrap();
+    assert!(passwords::verify(&sha512_hash, "s3cRe7", &HashingAlgorithm::SHA256).is_err());
+    assert!(passwords::verify("not base64!", "s3cRe7", &HashingAlgorithm::SHA256).is_err());
+    assert!(passwords::verify("", "s3cRe7", &HashingAlgorithm::SHA256).is_err());
+}
+
+#[test]
+fn test_generate_with_hash() {
+    let generated =
+        passwords::generate_with_hash(24, PasswordCharset::Hex, &HashingAlgorithm::SHA512);
+    assert_eq!(generated.password.len(), 24);
+    assert_eq!(generated.hashing_algorithm, "SHA-512");
+    assert_eq!(
+        passwords::verify(
+            &generated.password_hash,
+            &generated.password,
+            &HashingAlgorithm::SHA512
+        ),
+        Ok(true)
+    );
+}
@@ -0,0 +1,260 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmq_http_client::responses::{Permissions, TopicPermission};
+use rabbitmqadmin::permission_check::{
+    self, Decision, Operation, Resource, ResourceType, UserAccess,
+};
+
+fn permissions(user: &str, vhost: &str, configure: &str, write: &str, read: &str) -> Permissions {
+    Permissions {
+        user: user.to_owned(),
+        vhost: vhost.to_owned(),
+        configure: configure.to_owned(),
+        write: write.to_owned(),
+        read: read.to_owned(),
+    }
+}
+
+fn topic_permission(
+    user: &str,
+    vhost: &str,
+    exchange: &str,
+    write: &str,
+    read: &str,
+) -> TopicPermission {
+    TopicPermission {
+        user: user.to_owned(),
+        vhost: vhost.to_owned(),
+        exchange: exchange.to_owned(),
+        write: write.to_owned(),
+        read: read.to_owned(),
+    }
+}
+
+fn resource(resource_type: ResourceType, name: &str) -> Resource {
+    Resource {
+        resource_type,
+        name: name.to_owned(),
+        routing_key: None,
+    }
+}
+
+fn topic(exchange: &str, routing_key: &str) -> Resource {
+    Resource {
+        resource_type: ResourceType::Topic,
+        name: exchange.to_owned(),
+        routing_key: Some(routing_key.to_owned()),
+    }
+}
+
+#[test]
+fn test_patterns_are_unanchored() {
+    let p = permissions("u", "v", "orders", ".*", "^orders\\.");
+    let access = UserAccess {
+        permissions: Some(&p),
+        topic_permissions: vec![],
+    };
+
+    let verdict = permission_check::check(
+        "u",
+        "v",
+        &access,
+        &resource(ResourceType::Queue, "new.orders.q"),
+        Operation::Configure,
+    );
+    assert!(verdict.is_allowed());
+    assert_eq!(
+        verdict.rule,
+        "configure permission pattern 'orders' matches queue 'new.orders.q'"
+    );
+
+    let verdict = permission_check::check(
+        "u",
+        "v",
+        &access,
+        &resource(ResourceType::Queue, "new.orders.q"),
+        Operation::Read,
+    );
+    assert_eq!(verdict.decision, Decision::Denied);
+    assert!(verdict.rule.contains("does not match"));
+}
+
+#[test]
+fn test_patterns_the_regex_engine_does_not_support_cannot_be_evaluated() {
+    // a negative lookahead is valid for the broker but not for the regex crate
+    let p = permissions("u", "v", "^(?!amq\\.).*", ".*", ".*");
+    let access = UserAccess {
+        permissions: Some(&p),
+        topic_permissions: vec![],
+    };
+
+    let verdict = permission_check::check(
+        "u",
+        "v",
+        &access,
+        &resource(ResourceType::Queue, "q"),
+        Operation::Configure,
+    );
+    assert_eq!(verdict.decision, Decision::CannotEvaluate);
+    assert!(verdict.rule.contains("cannot be evaluated locally"));
+
+    let rows = permission_check::matrix(
+        &["u".to_owned()],
+        &["v".to_owned()],
+        std::slice::from_ref(&p),
+        &[],
+        &resource(ResourceType::Queue, "q"),
+    );
+    assert_eq!(rows[0].configure, "cannot evaluate");
+    assert_eq!(rows[0].write, "allowed");
+    assert!(!rows[0].is_evaluated());
+}
+
+#[test]
+fn test_empty_patterns_only_match_the_default_exchange() {
+    let p = permissions("u", "v", "", "", "");
+    let access = UserAccess {
+        permissions: Some(&p),
+        topic_permissions: vec![],
+    };
+
+    let verdict = permission_check::check(
+        "u",
+        "v",
+        &access,
+        &resource(ResourceType::Exchange, ""),
+        Operation::Write,
+    );
+    assert!(verdict.is_allowed());
+    assert!(verdict.rule.contains("treated as '^$'"));
+
+    let verdict = permission_check::check(
+        "u",
+        "v",
+        &access,
+        &resource(ResourceType::Exchange, "amq.direct"),
+        Operation::Write,
+    );
+    assert_eq!(verdict.decision, Decision::Denied);
+}
+
+#[test]
+fn test_no_permissions_in_virtual_host() {
+    let verdict = permission_check::check(
+        "u",
+        "v",
+        &UserAccess::default(),
+        &resource(ResourceType::Queue, "q"),
+        Operation::Read,
+    );
+    assert_eq!(verdict.decision, Decision::Denied);
+    assert_eq!(
+        verdict.rule,
+        "user 'u' has no permissions in virtual host 'v'"
+    );
+}
+
+#[test]
+fn test_topic_permissions() {
+    let p = permissions("u", "v", ".*", ".*", ".*");
+    let tp = topic_permission("u", "v", "amq.topic", "^{username}\\.", "");
+    let with_topic = UserAccess {
+        permissions: Some(&p),
+        topic_permissions: vec![&tp],
+    };
+    let without_topic = UserAccess {
+        permissions: Some(&p),
+        topic_permissions: vec![],
+    };
+
+    let check = |access: &UserAccess, rk: &str, op: Operation| {
+        permission_check::check("u", "v", access, &topic("amq.topic", rk), op)
+    };
+
+    assert!(check(&with_topic, "u.orders", Operation::Write).is_allowed());
+    assert_eq!(
+        check(&with_topic, "other.orders", Operation::Write).decision,
+        Decision::Denied
+    );
+    // an empty topic pattern only matches an empty routing key
+    assert_eq!(
+        check(&with_topic, "u.orders", Operation::Read).decision,
+        Decision::Denied
+    );
+    assert_eq!(
+        check(&with_topic, "u.orders", Operation::Configure).decision,
+        Decision::NotApplicable
+    );
+    let verdict = check(&without_topic, "anything", Operation::Write);
+    assert!(verdict.is_allowed());
+    assert!(verdict.rule.contains("all routing keys are allowed"));
+}
+
+#[test]
+fn test_topic_checks_require_exchange_permissions() {
+    let p = permissions("u", "v", ".*", "^queues\\.", ".*");
+    let access = UserAccess {
+        permissions: Some(&p),
+        topic_permissions: vec![],
+    };
+    let verdict = permission_check::check(
+        "u",
+        "v",
+        &access,
+        &topic("amq.topic", "rk"),
+        Operation::Write,
+    );
+    assert_eq!(verdict.decision, Decision::Denied);
+    assert!(verdict.rule.contains("exchange 'amq.topic'"));
+}
+
+#[test]
+fn test_matrix() {
+    let users = vec!["a".to_owned(), "b".to_owned()];
+    let vhosts = vec!["/".to_owned(), "v".to_owned()];
+    let perms = vec![
+        permissions("a", "/", ".*", ".*", ".*"),
+        permissions("b", "v", "", "^q", ""),
+    ];
+
+    let rows = permission_check::matrix(
+        &users,
+        &vhosts,
+        &perms,
+        &[],
+        &resource(ResourceType::Queue, "q1"),
+    );
+    let cells: Vec<(&str, &str, &str, &str, &str)> = rows
+        .iter()
+        .map(|r| {
+            (
+                r.user.as_str(),
+                r.vhost.as_str(),
+                r.configure.as_str(),
+                r.write.as_str(),
+                r.read.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        cells,
+        vec![
+            ("a", "/", "allowed", "allowed", "allowed"),
+            ("a", "v", "denied", "denied", "denied"),
+            ("b", "/", "denied", "denied", "denied"),
+            ("b", "v", "denied", "allowed", "denied"),
+        ]
+    );
+}
@@ -0,0 +1,66 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmq_http_client::responses::{CurrentUser, TagList};
+use rabbitmqadmin::errors::CommandRunError;
+use rabbitmqadmin::pre_flight;
+
+fn user_with_tags(tags: &[&str]) -> CurrentUser {
+    CurrentUser {
+        name: "u".to_owned(),
+        tags: TagList(tags.iter().map(|t| t.to_string()).collect()),
+    }
+}
+
+#[test]
+fn test_require_tag_passes_when_the_tag_is_present() {
+    let user = user_with_tags(&["monitoring", "administrator"]);
+    assert!(pre_flight::require_tag(&user, "administrator", "import definitions").is_ok());
+}
+
+#[test]
+fn test_require_tag_describes_what_is_missing() {
+    let user = user_with_tags(&["monitoring"]);
+    let err = pre_flight::require_tag(&user, "administrator", "import definitions").unwrap_err();
+    assert!(matches!(
+        err,
+        CommandRunError::InsufficientPrivileges { .. }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "User 'u' (tags: monitoring) cannot import definitions: this requires the 'administrator' tag"
+    );
+
+    let err = pre_flight::require_tag(
+        &user_with_tags(&[]),
+        "administrator",
+        "delete virtual hosts",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("(tags: none)"));
+}
+
+#[test]
+fn test_administrator_tag_grants_monitoring_access() {
+    let admin = user_with_tags(&["administrator"]);
+    assert!(pre_flight::is_administrator(&admin));
+    assert!(pre_flight::has_monitoring_access(&admin));
+
+    let monitoring = user_with_tags(&["monitoring"]);
+    assert!(!pre_flight::is_administrator(&monitoring));
+    assert!(pre_flight::has_monitoring_access(&monitoring));
+
+    let management = user_with_tags(&["management"]);
+    assert!(!pre_flight::has_monitoring_access(&management));
+}
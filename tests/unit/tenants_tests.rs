@@ -0,0 +1,176 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmq_http_client::password_hashing::HashingAlgorithm;
+use rabbitmqadmin::passwords;
+use rabbitmqadmin::tenants::{self, TenantObject, TenantRecord, TenantStep};
+use serde_json::json;
+use std::fs;
+
+const SPEC_PATH: &str = "tests/fixtures/tenants/tenant.toml";
+
+fn queue(vhost: &str, name: &str) -> TenantObject {
+    TenantObject::Queue {
+        vhost: vhost.to_owned(),
+        name: name.to_owned(),
+    }
+}
+
+#[test]
+fn test_parse_spec_and_plan_provisioning() {
+    let spec = tenants::parse_spec(&fs::read_to_string(SPEC_PATH).unwrap()).unwrap();
+    assert_eq!(spec.vhost_name(), "rabbitmqadmin.tenant.1");
+
+    let steps = tenants::plan_provisioning(&spec);
+    let descriptions: Vec<String> = steps.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        descriptions,
+        vec![
+            "declare virtual host 'rabbitmqadmin.tenant.1'",
+            "declare user 'rabbitmqadmin.tenant.1.app'",
+            "grant permissions of user 'rabbitmqadmin.tenant.1.app' in 'rabbitmqadmin.tenant.1'",
+            "grant topic permissions for exchange 'app.events' to user 'rabbitmqadmin.tenant.1.app' in 'rabbitmqadmin.tenant.1'",
+            "declare policy 'app.limits' in 'rabbitmqadmin.tenant.1'",
+            "declare exchange 'app.events' in 'rabbitmqadmin.tenant.1'",
+            "declare queue 'app.orders' in 'rabbitmqadmin.tenant.1'",
+            "declare queue 'app.audit' in 'rabbitmqadmin.tenant.1'",
+            "declare binding of queue 'app.orders' to exchange 'app.events' with routing key 'orders.#' in 'rabbitmqadmin.tenant.1'",
+            "set limit 'max-connections' to 100 on 'rabbitmqadmin.tenant.1'",
+            "set limit 'max-queues' to 50 on 'rabbitmqadmin.tenant.1'",
+            "save the provisioning record of tenant 'rabbitmqadmin.tenant.1'",
+        ]
+    );
+
+    // queues without a type use the default queue type of the virtual host
+    let queue_types: Vec<Option<&str>> = steps
+        .iter()
+        .filter_map(|s| match s {
+            TenantStep::DeclareQueue { spec, .. } => Some(spec.queue_type.as_deref()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(queue_types, vec![Some("quorum"), Some("classic")]);
+}
+
+#[test]
+fn test_parse_spec_rejects_invalid_specs() {
+    let err = tenants::parse_spec("name = \"t\"\n[[users]]\nname = \"u\"\n").unwrap_err();
+    assert!(err.contains("either a password or a password_hash"));
+
+    let err = tenants::parse_spec(
+        "name = \"t\"\n[[users]]\nname = \"u\"\npassword = \"p\"\npassword_hash = \"h\"\n",
+    )
+    .unwrap_err();
+    assert!(err.contains("either a password or a password_hash"));
+
+    let err = tenants::parse_spec(
+        "name = \"t\"\n[[users]]\nname = \"u\"\npassword = \"p\"\nhashing_algorithm = \"SHA1\"\n",
+    )
+    .unwrap_err();
+    assert!(err.contains("unsupported hashing_algorithm 'SHA1'"));
+
+    let err = tenants::parse_spec(
+        "name = \"t\"\n[[users]]\nname = \"u\"\npassword = \"p\"\nhashing_algorithm = \"MD5\"\n",
+    )
+    .unwrap_err();
+    assert!(err.contains("only supported for a password_hash"));
+
+    let err = tenants::parse_spec(
+        "name = \"t\"\n[[bindings]]\nsource = \"x\"\ndestination = \"q\"\ndestination_type = \"stream\"\n",
+    )
+    .unwrap_err();
+    assert!(err.contains("destination type"));
+
+    assert!(tenants::parse_spec("name = \"t\"\nunknown = 1\n").is_err());
+    assert!(tenants::parse_spec("name = \"\"\n").is_err());
+}
+
+#[test]
+fn test_user_definitions_carry_the_hashing_algorithm() {
+    let spec = tenants::parse_spec(
+        "name = \"t\"\n[[users]]\nname = \"u\"\npassword = \"s3cret\"\nhashing_algorithm = \"SHA512\"\ntags = [\"monitoring\"]\n\n[[users]]\nname = \"legacy\"\npassword_hash = \"aGFzaA==\"\nhashing_algorithm = \"MD5\"\n",
+    )
+    .unwrap();
+
+    let defs = spec.users[0].to_definitions().unwrap();
+    let user = &defs["users"][0];
+    assert_eq!(user["name"], "u");
+    assert_eq!(user["hashing_algorithm"], "rabbit_password_hashing_sha512");
+    assert_eq!(user["tags"], json!(["monitoring"]));
+    let hash = user["password_hash"].as_str().unwrap();
+    assert_eq!(
+        passwords::verify(hash, "s3cret", &HashingAlgorithm::SHA512),
+        Ok(true)
+    );
+    assert!(!defs.to_string().contains("s3cret"));
+
+    let defs = spec.users[1].to_definitions().unwrap();
+    assert_eq!(defs["users"][0]["password_hash"], "aGFzaA==");
+    assert_eq!(
+        defs["users"][0]["hashing_algorithm"],
+        "rabbit_password_hashing_md5"
+    );
+}
+
+#[test]
+fn test_vhost_name_defaults_to_tenant_name() {
+    let spec = tenants::parse_spec("name = \"t\"\n[vhost]\nname = \"vh\"\n").unwrap();
+    assert_eq!(spec.vhost_name(), "vh");
+    let spec = tenants::parse_spec("name = \"t\"\n").unwrap();
+    assert_eq!(spec.vhost_name(), "t");
+}
+
+#[test]
+fn test_plan_removal_skips_objects_in_removed_virtual_hosts() {
+    let objects = vec![
+        TenantObject::VirtualHost {
+            name: "t".to_owned(),
+        },
+        TenantObject::User {
+            name: "u".to_owned(),
+        },
+        queue("t", "q1"),
+        queue("shared", "q2"),
+        queue("shared", "q2"),
+    ];
+
+    assert_eq!(
+        tenants::plan_removal(&objects),
+        vec![
+            queue("shared", "q2"),
+            TenantObject::User {
+                name: "u".to_owned()
+            },
+            TenantObject::VirtualHost {
+                name: "t".to_owned()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_tenant_record_round_trip() {
+    let mut record = TenantRecord::new("t");
+    record.extend([queue("t", "q1"), queue("t", "q2")]);
+    record.extend([queue("t", "q1"), queue("t", "q3")]);
+    assert_eq!(
+        record.objects,
+        vec![queue("t", "q1"), queue("t", "q2"), queue("t", "q3")]
+    );
+    assert_eq!(record.parameter_name(), "rabbitmqadmin.tenant.t");
+
+    let value = serde_json::Value::Object(record.to_parameter_value());
+    assert_eq!(value["objects"][0]["type"], "queue");
+    assert_eq!(TenantRecord::from_parameter_value(value).unwrap(), record);
+}
@@ -0,0 +1,333 @@
+// Copyright (C) 2023-2026 RabbitMQ Core Team (teamrabbitmq@gmail.com)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rabbitmq_http_client::password_hashing::HashingAlgorithm;
+use rabbitmqadmin::users::{self, TagChanges};
+use serde_json::json;
+
+fn tags(list: &[&str]) -> Vec<String> {
+    list.iter().map(|t| t.to_string()).collect()
+}
+
+#[test]
+fn test_tags_of_list_and_string_forms() {
+    let list_form = json!({"name": "u", "tags": ["administrator", "monitoring"]});
+    let string_form = json!({"name": "u", "tags": "administrator, monitoring"});
+    let empty_string_form = json!({"name": "u", "tags": ""});
+
+    assert_eq!(
+        users::tags_of(&list_form),
+        tags(&["administrator", "monitoring"])
+    );
+    assert_eq!(
+        users::tags_of(&string_form),
+        tags(&["administrator", "monitoring"])
+    );
+    assert!(users::tags_of(&empty_string_form).is_empty());
+    assert!(users::has_tag(&string_form, "monitoring"));
+}
+
+#[test]
+fn test_with_tags_preserves_shape_and_credentials() {
+    let user = json!({
+        "name": "u",
+        "password_hash": "abc",
+        "hashing_algorithm": "rabbit_password_hashing_sha512",
+        "tags": ["monitoring"],
+        "limits": {"max-connections": 10}
+    });
+    let updated = users::with_tags(&user, &tags(&["monitoring", "management"]));
+    assert_eq!(updated["tags"], json!(["monitoring", "management"]));
+    assert_eq!(updated["password_hash"], user["password_hash"]);
+    assert_eq!(updated["hashing_algorithm"], user["hashing_algorithm"]);
+    assert_eq!(updated["limits"], user["limits"]);
+
+    let legacy = json!({"name": "u", "tags": "monitoring"});
+    let updated = users::with_tags(&legacy, &tags(&["monitoring", "management"]));
+    assert_eq!(updated["tags"], json!("monitoring,management"));
+}
+
+#[test]
+fn test_with_password_hash_carries_the_algorithm_and_keeps_tags() {
+    let user = json!({
+        "name": "svc",
+        "password_hash": "b2xk",
+        "hashing_algorithm": "rabbit_password_hashing_sha256",
+        "tags": ["monitoring", "policymaker"],
+        "limits": {"max-connections": 10}
+    });
+    let updated =
+        users::with_password_hash(&user, "bmV3", users::module_of(&HashingAlgorithm::SHA512));
+    assert_eq!(updated["password_hash"], "bmV3");
+    assert_eq!(
+        updated["hashing_algorithm"],
+        "rabbit_password_hashing_sha512"
+    );
+    assert_eq!(updated["name"], user["name"]);
+    assert_eq!(updated["tags"], user["tags"]);
+    assert_eq!(updated["limits"], user["limits"]);
+}
+
+#[test]
+fn test_tag_changes() {
+    let current = tags(&["monitoring", "management"]);
+
+    let add_and_remove = TagChanges {
+        replace: None,
+        add: tags(&["policymaker", "monitoring"]),
+        remove: tags(&["management"]),
+    };
+    assert_eq!(
+        add_and_remove.apply(&current),
+        tags(&["monitoring", "policymaker"])
+    );
+
+    let replace = TagChanges {
+        replace: Some(users::parse_tags("administrator,,impersonator ")),
+        ..Default::default()
+    };
+    assert_eq!(
+        replace.apply(&current),
+        tags(&["administrator", "impersonator"])
+    );
+
+    let clear = TagChanges {
+        replace: Some(users::parse_tags("")),
+        ..Default::default()
+    };
+    assert!(clear.apply(&current).is_empty());
+}
+
+#[test]
+fn test_find_user() {
+    let defs = json!({"users": [{"name": "a", "tags": []}, {"name": "b", "tags": []}]});
+    assert_eq!(users::find_user(&defs, "b").unwrap()["name"], "b");
+    assert!(users::find_user(&defs, "c").is_none());
+}
+
+#[test]
+fn test_users_from_csv_quoting() {
+    let records = users::users_from_csv(
+        "name,password,tags\r\n\"a\",p,\"administrator,monitoring\"\n\nb,\"say \"\"hi\"\"\",\nc,\"multi\nline\",",
+    )
+    .unwrap();
+    let fields: Vec<(&str, Option<&str>, Vec<String>)> = records
+        .iter()
+        .map(|r| (r.name.as_str(), r.password.as_deref(), r.tags.clone()))
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("a", Some("p"), tags(&["administrator", "monitoring"])),
+            ("b", Some("say \"hi\""), vec![]),
+            ("c", Some("multi\nline"), vec![]),
+        ]
+    );
+}
+
+#[test]
+fn test_users_from_csv_groups_rows_by_user() {
+    let input = "\
+name,password,password_hash,hashing_algorithm,tags,vhost,configure,write,read
+alice,s3cret,,SHA512,\"monitoring,management\",/,.*,.*,.*
+alice,,,,,team-a,^team\\.,^team\\.,.*
+bob,,aGFzaA==,,,,,,
+";
+    let records = users::users_from_csv(input).unwrap();
+    assert_eq!(records.len(), 2);
+
+    let alice = &records[0];
+    assert_eq!(alice.name, "alice");
+    assert_eq!(alice.password.as_deref(), Some("s3cret"));
+    assert_eq!(alice.hashing_algorithm, "rabbit_password_hashing_sha512");
+    assert_eq!(alice.tags, tags(&["monitoring", "management"]));
+    assert_eq!(alice.permissions.len(), 2);
+    assert_eq!(alice.permissions[1].vhost, "team-a");
+    assert_eq!(alice.permissions[1].configure, "^team\\.");
+
+    let bob = &records[1];
+    assert_eq!(bob.password_hash.as_deref(), Some("aGFzaA=="));
+    assert_eq!(bob.hashing_algorithm, "rabbit_password_hashing_sha256");
+    assert!(bob.permissions.is_empty());
+}
+
+#[test]
+fn test_users_from_csv_rejects_invalid_headers() {
+    let errors = [
+        ("", "the file is empty"),
+        ("name,colour\na,red\n", "unknown column 'colour'"),
+        ("tags\nmonitoring\n", "'name' column"),
+    ];
+    for (input, expected) in errors {
+        let err = users::users_from_csv(input).unwrap_err();
+        assert!(
+            err.contains(expected),
+            "'{}' does not contain '{}'",
+            err,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_users_from_csv_records_row_errors_per_user() {
+    let errors = [
+        (
+            "name,password\na,\n",
+            "neither a password nor a password hash",
+        ),
+        (
+            "name,password,password_hash\na,p,aGFzaA==\n",
+            "both a password and a password hash",
+        ),
+        (
+            "name,password\na,p1\na,p2\n",
+            "row 3: conflicting passwords",
+        ),
+        (
+            "name,password,hashing_algorithm\na,p,SHA1\n",
+            "unsupported hashing algorithm",
+        ),
+        (
+            "name,password,hashing_algorithm\na,p,MD5\n",
+            "only supported for existing password hashes",
+        ),
+        (
+            "name,password,vhost,configure\na,p,,.*\n",
+            "permissions require a virtual host",
+        ),
+        (
+            "name,password,vhost\na,p,/\na,,/\n",
+            "duplicate permissions",
+        ),
+        ("name,password\n,p\n", "row 2: the name is empty"),
+    ];
+    for (input, expected) in errors {
+        let records = users::users_from_csv(input).unwrap();
+        let err = records[0].errors.join("; ");
+        assert!(
+            err.contains(expected),
+            "'{}' does not contain '{}'",
+            err,
+            expected
+        );
+    }
+
+    // other users are not affected
+    let records = users::users_from_csv("name,password,vhost\na,p,/\na,,/\nb,p,/\n,p,/\n").unwrap();
+    let names: Vec<String> = records.iter().map(|r| r.item_name()).collect();
+    assert_eq!(names, vec!["a", "b", "row 5"]);
+    assert_eq!(records[0].errors.len(), 1);
+    assert!(records[1].errors.is_empty());
+    assert_eq!(records[2].errors.len(), 1);
+}
+
+#[test]
+fn test_users_from_csv_accepts_md5_password_hashes() {
+    let records = users::users_from_csv(
+        "name,password_hash,hashing_algorithm\na,aGFzaA==,rabbit_password_hashing_md5\nb,aGFzaA==,MD5\n",
+    )
+    .unwrap();
+    for record in &records {
+        assert!(record.errors.is_empty());
+        assert_eq!(record.hashing_algorithm, "rabbit_password_hashing_md5");
+    }
+
+    let defs = json!({
+        "users": [{"name": "a", "password_hash": "aGFzaA==", "hashing_algorithm": "rabbit_password_hashing_md5", "tags": []}]
+    });
+    let csv = users::users_to_csv(&defs, true);
+    assert!(csv.contains("a,aGFzaA==,MD5"));
+    assert!(users::users_from_csv(&csv).unwrap()[0].errors.is_empty());
+}
+
+#[test]
+fn test_user_record_to_definitions_hashes_passwords_locally() {
+    let records = users::users_from_csv(
+        "name,password,tags,vhost,configure,write,read\na,s3cret,monitoring,/,,.*,.*\n",
+    )
+    .unwrap();
+    let defs = records[0].to_definitions().unwrap();
+    let user = &defs["users"][0];
+    assert_eq!(user["name"], "a");
+    assert_eq!(user["hashing_algorithm"], "rabbit_password_hashing_sha256");
+    assert_eq!(user["tags"], json!(["monitoring"]));
+    let hash = user["password_hash"].as_str().unwrap();
+    assert!(!hash.is_empty());
+    assert!(!defs.to_string().contains("s3cret"));
+    assert_eq!(
+        defs["permissions"],
+        json!([{"user": "a", "vhost": "/", "configure": "", "write": ".*", "read": ".*"}])
+    );
+}
+
+#[test]
+fn test_users_to_csv_round_trip() {
+    let defs = json!({
+        "users": [
+            {"name": "b", "password_hash": "aGFzaA==", "hashing_algorithm": "rabbit_password_hashing_sha512", "tags": ["monitoring", "management"]},
+            {"name": "a", "password_hash": "aGFzaDI=", "hashing_algorithm": "rabbit_password_hashing_sha256", "tags": []}
+        ],
+        "permissions": [
+            {"user": "b", "vhost": "v2", "configure": "", "write": ".*", "read": ".*"},
+            {"user": "b", "vhost": "/", "configure": ".*", "write": ".*", "read": ".*"}
+        ]
+    });
+
+    let without_hashes = users::users_to_csv(&defs, false);
+    assert_eq!(
+        without_hashes,
+        "\
+name,tags,vhost,configure,write,read
+a,,,,,
+b,\"monitoring,management\",/,.*,.*,.*
+b,\"monitoring,management\",v2,,.*,.*
+"
+    );
+    assert!(!without_hashes.contains("aGFzaA=="));
+
+    let with_hashes = users::users_to_csv(&defs, true);
+    let records = users::users_from_csv(&with_hashes).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].name, "b");
+    assert_eq!(records[1].password_hash.as_deref(), Some("aGFzaA=="));
+    assert_eq!(
+        records[1].hashing_algorithm,
+        "rabbit_password_hashing_sha512"
+    );
+    assert_eq!(records[1].permissions.len(), 2);
+}
+
+#[test]
+fn test_deletion_protection_skips_current_user() {
+    let candidates = [("alice", false), ("bob", false)];
+    let protection = users::deletion_protection(&candidates, 2, "alice");
+    assert_eq!(
+        protection,
+        vec![Some("currently authenticated user".to_string()), None]
+    );
+}
+
+#[test]
+fn test_deletion_protection_keeps_last_administrator() {
+    let candidates = [("admin1", true), ("user1", false), ("admin2", true)];
+    let protection = users::deletion_protection(&candidates, 2, "operator");
+    assert_eq!(
+        protection,
+        vec![None, None, Some("last administrator".to_string())]
+    );
+
+    let protection = users::deletion_protection(&candidates, 3, "operator");
+    assert_eq!(protection, vec![None, None, None]);
+}